@@ -0,0 +1,73 @@
+#![cfg(feature = "alloc")]
+#![feature(test)]
+
+extern crate std;
+extern crate test;
+
+use coca::collections::binary_heap::DaryHeap;
+use coca::storage::{AllocStorage, ArrayLayout};
+use rand::{rngs::SmallRng, RngCore, SeedableRng};
+use test::Bencher;
+
+type AllocDaryHeap<const D: usize> = DaryHeap<u32, AllocStorage<ArrayLayout<u32>>, usize, D>;
+
+macro_rules! push_then_pop {
+    ($fnn:ident, $d:expr, $n:expr) => {
+        #[bench]
+        fn $fnn(b: &mut Bencher) {
+            let mut rng = SmallRng::seed_from_u64(0x5432_1012_3454_3210);
+            let mut heap = AllocDaryHeap::<$d>::with_capacity($n);
+            b.iter(|| {
+                for _ in 0..$n {
+                    heap.push(rng.next_u32());
+                }
+                while let Some(x) = heap.pop() {
+                    test::black_box(x);
+                }
+            })
+        }
+    };
+}
+
+macro_rules! push_heavy {
+    ($fnn:ident, $d:expr, $n:expr) => {
+        #[bench]
+        fn $fnn(b: &mut Bencher) {
+            let mut rng = SmallRng::seed_from_u64(0x5432_1012_3454_3210);
+            let mut heap = AllocDaryHeap::<$d>::with_capacity($n);
+            b.iter(|| {
+                heap.clear();
+                for _ in 0..($n / 4) {
+                    for _ in 0..4 {
+                        heap.push(rng.next_u32());
+                    }
+                    test::black_box(heap.pop());
+                }
+            })
+        }
+    };
+}
+
+mod binary {
+    use super::*;
+
+    push_then_pop!(push_then_pop_00064, 2, 64);
+    push_then_pop!(push_then_pop_01024, 2, 1024);
+    push_then_pop!(push_then_pop_16384, 2, 16384);
+
+    push_heavy!(push_heavy_00064, 2, 64);
+    push_heavy!(push_heavy_01024, 2, 1024);
+    push_heavy!(push_heavy_16384, 2, 16384);
+}
+
+mod quaternary {
+    use super::*;
+
+    push_then_pop!(push_then_pop_00064, 4, 64);
+    push_then_pop!(push_then_pop_01024, 4, 1024);
+    push_then_pop!(push_then_pop_16384, 4, 16384);
+
+    push_heavy!(push_heavy_00064, 4, 64);
+    push_heavy!(push_heavy_01024, 4, 1024);
+    push_heavy!(push_heavy_16384, 4, 16384);
+}
@@ -57,7 +57,7 @@
 //! your target platform's pointer size and the alignment of the passed buffer).
 //! This does not apply to creating sub-arenas.
 
-use crate::collections::cache::CacheTable;
+use crate::collections::{cache::CacheTable, ArenaHeap};
 use crate::storage::{ArenaStorage, ArrayLayout, Capacity, LayoutSpec};
 use crate::ArenaString;
 
@@ -1045,6 +1045,44 @@ impl<'src> Arena<'src> {
         Some(result)
     }
 
+    /// Constructs an empty [`ArenaHeap`] with the given capacity.
+    ///
+    /// # Panics
+    /// Panics if the remaining space in the arena is insufficient.
+    /// See [`try_binary_heap`](Arena::try_binary_heap) for a checked version
+    /// that never panics.
+    #[track_caller]
+    pub fn binary_heap<T: Ord, I: Capacity>(&mut self, capacity: I) -> ArenaHeap<'src, T, I> {
+        self.try_binary_heap(capacity)
+            .expect("unexpected allocation failure in `binary_heap`")
+    }
+
+    /// Constructs an empty [`ArenaHeap`] with the given capacity.
+    ///
+    /// Returns [`None`] if the remaining space in the arena is insufficient.
+    ///
+    /// # Examples
+    /// ```
+    /// use coca::arena::Arena;
+    /// use core::mem::MaybeUninit;
+    ///
+    /// let mut backing_region = [MaybeUninit::uninit(); 192];
+    /// let mut arena = Arena::from(&mut backing_region[..]);
+    ///
+    /// let mut heap = arena.try_binary_heap::<u32, u8>(32).unwrap();
+    /// heap.extend(0..32);
+    /// assert!(heap.is_full());
+    /// assert_eq!(heap.pop(), Some(31));
+    ///
+    /// assert!(arena.try_binary_heap::<u32, u8>(32).is_none());
+    /// ```
+    pub fn try_binary_heap<T: Ord, I: Capacity>(
+        &mut self,
+        capacity: I,
+    ) -> Option<ArenaHeap<'src, T, I>> {
+        self.try_with_capacity(capacity.as_usize())
+    }
+
     /// Constructs a new [`CacheTable`] with the specified hash builder and capacity, rounded up to the next multiple of `L::CAPACITY`.
     ///
     /// Returns `None` if the remaining space in the arena is insufficient.
@@ -8,17 +8,47 @@
 //! Converting a vector to a binary heap can be done in-place, and has O(n)
 //! complexity. A binary heap can also be converted to a sorted vector in-place,
 //! allowing it to be used for an O(n log(n)) in-place heap sort.
+//!
+//! [`DaryHeap<T, S, I, D>`](DaryHeap) generalizes this to heaps in which each
+//! node has up to `D` children; `BinaryHeap` is simply an alias for the case
+//! `D = 2`. Higher arities make for shallower trees, trading cheaper pushes
+//! for more comparisons per level when popping, and tend to be more cache
+//! friendly. See [`QuaternaryHeap`] for a common choice.
 
+use crate::arena::Arena;
 use crate::collections::vec::{Drain, Vec};
-use crate::storage::{ArrayLayout, Capacity, Storage};
+use crate::collections::{ArenaHeap, InlineVec};
+#[cfg(feature = "alloc")]
+use crate::storage::{buffer_too_large_for_index_type, AllocStorage, TryReserveError};
+use crate::storage::{
+    ArenaStorage, ArrayLayout, Capacity, InlineStorage, Pod, SliceStorage, Storage,
+};
+use crate::CapacityError;
 
+use core::cmp::Ordering;
 use core::fmt::{self, Debug, Formatter};
-use core::iter::{FromIterator, FusedIterator};
+use core::hash::{Hash, Hasher};
+use core::iter::{FromIterator, FusedIterator, Peekable};
 #[allow(unused_imports)]
 use core::mem::MaybeUninit;
 use core::ops::{Deref, DerefMut};
 
-/// A fixed-capacity priority queue implemented with a binary heap.
+/// A fixed-capacity priority queue implemented with a `D`-ary heap.
+///
+/// Each node of the heap has up to `D` children. All operations behave
+/// identically regardless of the arity, but the tree's depth is inversely
+/// proportional to `log(D)`: pushing takes O(log(n) / log(D)) comparisons,
+/// while popping takes O(D log(n) / log(D)).
+///
+/// The arity must be at least 2, anything else fails to compile:
+///
+/// ```compile_fail
+/// use coca::collections::binary_heap::DaryHeap;
+/// use core::mem::MaybeUninit;
+///
+/// let mut heap = DaryHeap::<u32, [MaybeUninit<u32>; 8], usize, 1>::new();
+/// heap.push(1);
+/// ```
 ///
 /// This will be a max-heap, i.e. [`heap.pop()`](BinaryHeap::pop) will return
 /// the largest value in the queue. [`core::cmp::Reverse`] or a custom `Ord`
@@ -28,31 +58,68 @@ use core::ops::{Deref, DerefMut};
 /// item's ordering relative to any other item, as determined by the `Ord`
 /// trait, changes while it is in the heap. This is normally only possible
 /// through `Cell`, `RefCell`, global state, I/O, or unsafe code.
-pub struct BinaryHeap<T: Ord, S: Storage<ArrayLayout<T>>, I: Capacity = usize> {
+pub struct DaryHeap<T: Ord, S: Storage<ArrayLayout<T>>, I: Capacity = usize, const D: usize = 2> {
     a: Vec<T, S, I>,
 }
 
+/// A fixed-capacity priority queue implemented with a binary heap.
+///
+/// This is a [`DaryHeap`] in which each node has up to two children.
+pub type BinaryHeap<T, S, I = usize> = DaryHeap<T, S, I>;
+
+/// A fixed-capacity priority queue implemented with a 4-ary heap.
+///
+/// This halves the depth of the tree compared to a [`BinaryHeap`], which
+/// tends to speed up push-heavy workloads.
+///
+/// # Examples
+/// ```
+/// use coca::collections::binary_heap::QuaternaryHeap;
+/// use core::mem::MaybeUninit;
+///
+/// let mut backing_region = [MaybeUninit::<u32>::uninit(); 8];
+/// let mut heap = QuaternaryHeap::<_, &mut [_]>::from(&mut backing_region[..]);
+/// heap.extend([3, 1, 4, 1, 5, 9, 2, 6]);
+///
+/// assert_eq!(heap.pop(), Some(9));
+/// assert_eq!(heap.pop(), Some(6));
+/// assert_eq!(heap.pop(), Some(5));
+/// ```
+pub type QuaternaryHeap<T, S, I = usize> = DaryHeap<T, S, I, 4>;
+
 /// Structure wrapping a mutable reference to the greatest item on a `BinaryHeap`.
 ///
 /// This `struct` is created by the [`BinaryHeap::peek_mut()`] method. See its
 /// documentation for more.
-pub struct PeekMut<'a, T: 'a + Ord, S: Storage<ArrayLayout<T>>, I: Capacity = usize> {
-    heap: &'a mut BinaryHeap<T, S, I>,
+pub struct PeekMut<
+    'a,
+    T: 'a + Ord,
+    S: Storage<ArrayLayout<T>>,
+    I: Capacity = usize,
+    const D: usize = 2,
+> {
+    heap: &'a mut DaryHeap<T, S, I, D>,
 }
 
-impl<T: Ord + Debug, S: Storage<ArrayLayout<T>>, I: Capacity> Debug for PeekMut<'_, T, S, I> {
+impl<T: Ord + Debug, const D: usize, S: Storage<ArrayLayout<T>>, I: Capacity> Debug
+    for PeekMut<'_, T, S, I, D>
+{
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         f.debug_tuple("PeekMut").field(&self.heap.peek()).finish()
     }
 }
 
-impl<T: Ord, S: Storage<ArrayLayout<T>>, I: Capacity> Drop for PeekMut<'_, T, S, I> {
+impl<T: Ord, const D: usize, S: Storage<ArrayLayout<T>>, I: Capacity> Drop
+    for PeekMut<'_, T, S, I, D>
+{
     fn drop(&mut self) {
-        heapify(self.heap.a.as_mut_slice(), 0);
+        heapify::<T, D>(self.heap.a.as_mut_slice(), 0);
     }
 }
 
-impl<T: Ord, S: Storage<ArrayLayout<T>>, I: Capacity> Deref for PeekMut<'_, T, S, I> {
+impl<T: Ord, const D: usize, S: Storage<ArrayLayout<T>>, I: Capacity> Deref
+    for PeekMut<'_, T, S, I, D>
+{
     type Target = T;
 
     fn deref(&self) -> &Self::Target {
@@ -61,16 +128,18 @@ impl<T: Ord, S: Storage<ArrayLayout<T>>, I: Capacity> Deref for PeekMut<'_, T, S
     }
 }
 
-impl<T: Ord, S: Storage<ArrayLayout<T>>, I: Capacity> DerefMut for PeekMut<'_, T, S, I> {
+impl<T: Ord, const D: usize, S: Storage<ArrayLayout<T>>, I: Capacity> DerefMut
+    for PeekMut<'_, T, S, I, D>
+{
     fn deref_mut(&mut self) -> &mut Self::Target {
         debug_assert!(!self.heap.is_empty());
         unsafe { self.heap.a.get_unchecked_mut(0) }
     }
 }
 
-impl<T: Ord, S: Storage<ArrayLayout<T>>, I: Capacity> PeekMut<'_, T, S, I> {
+impl<T: Ord, const D: usize, S: Storage<ArrayLayout<T>>, I: Capacity> PeekMut<'_, T, S, I, D> {
     /// Removes the peeked value from the heap and returns it.
-    pub fn pop(this: PeekMut<'_, T, S, I>) -> T {
+    pub fn pop(this: PeekMut<'_, T, S, I, D>) -> T {
         debug_assert!(!this.heap.is_empty());
         if let Some(value) = this.heap.pop() {
             core::mem::forget(this);
@@ -81,76 +150,586 @@ impl<T: Ord, S: Storage<ArrayLayout<T>>, I: Capacity> PeekMut<'_, T, S, I> {
     }
 }
 
-impl<T: Ord, S: Storage<ArrayLayout<T>>, I: Capacity> From<S> for BinaryHeap<T, S, I> {
+/// Structure wrapping a mutable reference to the vector underlying a `BinaryHeap`.
+///
+/// The heap property is restored when this is dropped, in O(n) time.
+///
+/// This `struct` is created by the [`BinaryHeap::bulk_mut()`] method. See its
+/// documentation for more.
+pub struct HeapGuard<
+    'a,
+    T: 'a + Ord,
+    S: Storage<ArrayLayout<T>>,
+    I: Capacity = usize,
+    const D: usize = 2,
+> {
+    heap: &'a mut DaryHeap<T, S, I, D>,
+}
+
+impl<T: Ord + Debug, const D: usize, S: Storage<ArrayLayout<T>>, I: Capacity> Debug
+    for HeapGuard<'_, T, S, I, D>
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("HeapGuard").field(&self.heap.a).finish()
+    }
+}
+
+impl<T: Ord, const D: usize, S: Storage<ArrayLayout<T>>, I: Capacity> Drop
+    for HeapGuard<'_, T, S, I, D>
+{
+    fn drop(&mut self) {
+        build_heap::<T, D>(self.heap.a.as_mut_slice());
+    }
+}
+
+impl<T: Ord, const D: usize, S: Storage<ArrayLayout<T>>, I: Capacity> Deref
+    for HeapGuard<'_, T, S, I, D>
+{
+    type Target = Vec<T, S, I>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.heap.a
+    }
+}
+
+impl<T: Ord, const D: usize, S: Storage<ArrayLayout<T>>, I: Capacity> DerefMut
+    for HeapGuard<'_, T, S, I, D>
+{
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.heap.a
+    }
+}
+
+impl<T: Ord, const D: usize, S: Storage<ArrayLayout<T>>, I: Capacity> From<S>
+    for DaryHeap<T, S, I, D>
+{
     /// Converts a contiguous block of memory into an empty binary heap.
     ///
     /// # Panics
     /// This may panic if the index type I cannot represent `buf.capacity()`.
     fn from(buf: S) -> Self {
-        BinaryHeap { a: Vec::from(buf) }
+        Self::from_vec_unchecked(Vec::from(buf))
     }
 }
 
 // This implementatin is largely based on the pseudocode given in
 // CLRS - Introduction to Algorithms (third edition), Chapter 6
 
-// These utility functions for binary tree traversal differ from the reference
-// because we're using 0-based indexing, i.e. these are equivalent to
-// `PARENT(i + 1) - 1`, `LEFT(i + 1) - 1`, and `RIGHT(i + 1) - 1`, respectively.
+// These utility functions for tree traversal differ from the reference
+// because we're using 0-based indexing and arbitrary arity, i.e. for `D = 2`
+// they are equivalent to `PARENT(i + 1) - 1` and `LEFT(i + 1) - 1`, with
+// `RIGHT(i + 1) - 1` being the next index after the first child.
 #[inline(always)]
-fn parent(i: usize) -> usize {
-    (i + 1) / 2 - 1
+fn parent<const D: usize>(i: usize) -> usize {
+    (i - 1) / D
 }
 
 #[inline(always)]
-fn left(i: usize) -> usize {
-    2 * (i + 1) - 1
+fn first_child<const D: usize>(i: usize) -> usize {
+    i.saturating_mul(D).saturating_add(1)
 }
 
+// Returns the index of the first node without children in a heap of the
+// given length; all nodes at or after this index are leaves.
 #[inline(always)]
-fn right(i: usize) -> usize {
-    2 * (i + 1)
+fn first_leaf<const D: usize>(len: usize) -> usize {
+    if len <= 1 {
+        0
+    } else {
+        parent::<D>(len - 1) + 1
+    }
 }
 
-fn heapify<T: Ord>(a: &mut [T], i: usize) {
-    let l = left(i);
-    let r = right(i);
-    let mut largest = if l < a.len() && a[l] > a[i] { l } else { i };
-    if r < a.len() && a[r] > a[largest] {
-        largest = r;
-    }
-    if largest != i {
+// Moves the element at index `i` towards the leaves until none of its
+// children are greater than it.
+fn heapify<T: Ord, const D: usize>(a: &mut [T], mut i: usize) {
+    loop {
+        let first = first_child::<D>(i);
+        let last = usize::min(first.saturating_add(D), a.len());
+
+        let mut largest = i;
+        for c in first..last {
+            if a[c] > a[largest] {
+                largest = c;
+            }
+        }
+
+        if largest == i {
+            return;
+        }
+
         a.swap(i, largest);
-        heapify(a, largest);
+        i = largest;
+    }
+}
+
+// Restores the heap property for the entire slice, bottom-up.
+fn build_heap<T: Ord, const D: usize>(a: &mut [T]) {
+    for i in (0..first_leaf::<D>(a.len())).rev() {
+        heapify::<T, D>(a, i);
+    }
+}
+
+// Moves the element at index `i` towards the root until its parent is not
+// less than it, returning its final position.
+fn sift_up<T: Ord, const D: usize>(a: &mut [T], mut i: usize) -> usize {
+    while i > 0 && a[parent::<D>(i)] < a[i] {
+        a.swap(i, parent::<D>(i));
+        i = parent::<D>(i);
     }
+    i
 }
 
-impl<T: Ord + Debug, S: Storage<ArrayLayout<T>>, I: Capacity> Debug for BinaryHeap<T, S, I> {
+// Sorts a valid heap in ascending order.
+fn heap_sort<T: Ord, const D: usize>(a: &mut [T]) {
+    for i in (1..a.len()).rev() {
+        a.swap(0, i);
+        heapify::<T, D>(&mut a[..i], 0);
+    }
+}
+
+impl<T: Ord + Debug, const D: usize, S: Storage<ArrayLayout<T>>, I: Capacity> Debug
+    for DaryHeap<T, S, I, D>
+{
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_list().entries(self.iter()).finish()
     }
 }
 
-impl<T: Ord, S: Storage<ArrayLayout<T>>, I: Capacity> From<Vec<T, S, I>> for BinaryHeap<T, S, I> {
+impl<T: Ord + Hash, const D: usize, S: Storage<ArrayLayout<T>>, I: Capacity> Hash
+    for DaryHeap<T, S, I, D>
+{
+    /// Feeds the heap's contents into the given [`Hasher`] in descending order.
+    ///
+    /// Since the internal arrangement of elements is ignored, two heaps
+    /// containing the same elements produce the same hash, regardless of the
+    /// order in which they were inserted.
+    ///
+    /// This requires no scratch memory, but it is O(n²).
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.len().hash(state);
+        for item in self.iter_sorted() {
+            item.hash(state);
+        }
+    }
+}
+
+impl<T, const D: usize, AS, AI, BS, BI> PartialEq<DaryHeap<T, BS, BI, D>> for DaryHeap<T, AS, AI, D>
+where
+    T: Ord,
+    AS: Storage<ArrayLayout<T>>,
+    BS: Storage<ArrayLayout<T>>,
+    AI: Capacity,
+    BI: Capacity,
+{
+    /// Returns `true` if both heaps contain the same elements, i.e. the same
+    /// values with the same multiplicities.
+    ///
+    /// This compares the heaps' contents rather than the arrangement of
+    /// elements in their underlying storage. It requires no scratch memory,
+    /// but it is O(n²).
+    ///
+    /// # Examples
+    /// ```
+    /// use coca::collections::InlineHeap;
+    /// let mut a = InlineHeap::<u32, 4>::new();
+    /// let mut b = InlineHeap::<u32, 4>::new();
+    ///
+    /// a.push(1); a.push(2); a.push(2); a.push(3);
+    /// b.push(3); b.push(2); b.push(1); b.push(2);
+    /// assert_eq!(a, b);
+    ///
+    /// b.pop();
+    /// b.push(1);
+    /// assert_ne!(a, b);
+    /// ```
+    fn eq(&self, other: &DaryHeap<T, BS, BI, D>) -> bool {
+        self.len() == other.len() && self.iter_sorted().eq(other.iter_sorted())
+    }
+}
+
+impl<T: Ord, const D: usize, S: Storage<ArrayLayout<T>>, I: Capacity> Eq for DaryHeap<T, S, I, D> {}
+
+impl<T: Ord, const D: usize, S: Storage<ArrayLayout<T>>, I: Capacity> From<Vec<T, S, I>>
+    for DaryHeap<T, S, I, D>
+{
     /// Converts a [`Vec`] into a binary heap.
     ///
     /// This conversion happens in-place, and has O(n) time complexity.
     fn from(mut vec: Vec<T, S, I>) -> Self {
-        let a = vec.as_mut_slice();
-        for i in (0..(a.len() / 2)).rev() {
-            heapify(a, i);
-        }
-        BinaryHeap { a: vec }
+        build_heap::<T, D>(vec.as_mut_slice());
+        Self::from_vec_unchecked(vec)
+    }
+}
+
+impl<T: Ord, const D: usize, I: Capacity, const C: usize> From<[T; C]>
+    for DaryHeap<T, InlineStorage<T, C>, I, D>
+{
+    /// Moves the items of an array into a full binary heap backed by an
+    /// inline array of the same size.
+    ///
+    /// This conversion has O(n) time complexity.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut heap = coca::collections::InlineHeap::<u32, 5>::from([3, 1, 4, 1, 5]);
+    /// assert!(heap.is_full());
+    /// assert_eq!(heap.pop(), Some(5));
+    /// assert_eq!(heap.pop(), Some(4));
+    /// assert_eq!(heap.pop(), Some(3));
+    /// ```
+    fn from(items: [T; C]) -> Self {
+        Self::from(Vec::from(items))
     }
 }
 
-impl<T: Ord, S: Storage<ArrayLayout<T>>, I: Capacity> BinaryHeap<T, S, I> {
+impl<T: Ord, const D: usize, I: Capacity> Default for DaryHeap<T, SliceStorage<'_, T>, I, D> {
+    /// Constructs a new, empty `SliceHeap<T, I>` backed by an empty slice.
+    ///
+    /// Since the heap has a capacity of zero, any attempt to push an item
+    /// will fail. This is mostly useful for satisfying trait bounds, e.g. in
+    /// `#[derive(Default)]`.
+    fn default() -> Self {
+        Self::from(<&mut [MaybeUninit<T>]>::default())
+    }
+}
+
+impl<'a, T: Ord + Copy, const D: usize, I: Capacity> DaryHeap<T, SliceStorage<'a, T>, I, D> {
+    /// Converts a slice of initialized values into a full binary heap,
+    /// reordering the elements in-place.
+    ///
+    /// Unlike the conversion [`From<&mut [MaybeUninit<T>]>`](From), which
+    /// yields an empty heap, all `slice.len()` elements are adopted as the
+    /// heap's contents. This has O(n) time complexity.
+    ///
+    /// The elements are required to be [`Copy`] because they remain owned by
+    /// the slice's original owner, so the heap must never be responsible for
+    /// dropping them. Once the heap is dropped, the slice will contain some
+    /// permutation of whatever items were left in the heap, followed by
+    /// unspecified (but initialized) values from the original contents.
+    ///
+    /// # Safety
+    /// The heap exposes its storage as uninitialized memory, e.g. through
+    /// [`into_vec`](DaryHeap::into_vec) and
+    /// [`Vec::spare_capacity_mut`](crate::collections::vec::Vec::spare_capacity_mut),
+    /// so safe code can write uninitialized values into the slice. Callers
+    /// must ensure that no such values are written, or that the slice is not
+    /// read again after the heap and everything derived from it is gone.
+    ///
+    /// # Panics
+    /// Panics if `slice.len()` cannot be represented by the index type `I`.
+    ///
+    /// # Examples
+    /// ```
+    /// use coca::collections::SliceHeap;
+    ///
+    /// let mut data = [3, 1, 4, 1, 5, 9];
+    /// // SAFETY: the heap's storage is only accessed through `push` and `pop`.
+    /// let mut heap = unsafe { SliceHeap::<_>::from_full_slice(&mut data[..]) };
+    /// assert!(heap.is_full());
+    /// assert_eq!(heap.pop(), Some(9));
+    /// assert_eq!(heap.pop(), Some(5));
+    /// ```
+    pub unsafe fn from_full_slice(slice: &'a mut [T]) -> Self {
+        let len = slice.len();
+        // SAFETY: `MaybeUninit<T>` has the same layout as `T`, and the caller
+        // guarantees that no uninitialized values are written through the
+        // heap, so every slot stays initialized for the slice's owner. Since
+        // `T: Copy`, the heap dropping its contents is a no-op, ruling out
+        // double drops.
+        let buf = core::slice::from_raw_parts_mut(slice.as_mut_ptr().cast::<MaybeUninit<T>>(), len);
+        let mut vec = Vec::from(buf);
+        // SAFETY: all `len` elements of the buffer are initialized.
+        vec.set_len(I::from_usize(len));
+        Self::from(vec)
+    }
+}
+
+impl<T: Ord, const D: usize, S: Storage<ArrayLayout<T>>, I: Capacity> DaryHeap<T, S, I, D> {
+    const ARITY_IS_VALID: () = assert!(D >= 2, "heap arity must be at least 2");
+
+    // Wraps a vector that already satisfies the heap property.
+    #[inline(always)]
+    fn from_vec_unchecked(a: Vec<T, S, I>) -> Self {
+        #[allow(clippy::let_unit_value)]
+        let () = Self::ARITY_IS_VALID;
+        DaryHeap { a }
+    }
+
+    /// Creates a binary heap in the given storage block from an iterator.
+    ///
+    /// Returns `Err` with the partially filled heap and an iterator over the
+    /// remaining items if the iterator yields more items than the heap can hold.
+    ///
+    /// # Examples
+    /// ```
+    /// use coca::collections::SliceHeap;
+    /// use core::mem::MaybeUninit;
+    ///
+    /// let mut backing_region = [MaybeUninit::<u32>::uninit(); 4];
+    /// let heap = SliceHeap::<_>::try_from_iter(&mut backing_region[..], 1..=4).unwrap();
+    /// assert_eq!(heap.peek(), Some(&4));
+    ///
+    /// let mut backing_region = [MaybeUninit::<u32>::uninit(); 4];
+    /// let (heap, rest) = SliceHeap::<_>::try_from_iter(&mut backing_region[..], 1..=6).unwrap_err();
+    /// assert!(heap.is_full());
+    /// assert_eq!(heap.peek(), Some(&4));
+    /// assert!(rest.eq(5..=6));
+    /// ```
+    pub fn try_from_iter<It: IntoIterator<Item = T>>(
+        storage: S,
+        iter: It,
+    ) -> Result<Self, (Self, Peekable<It::IntoIter>)> {
+        let mut vec = Vec::from(storage);
+        let mut iter = iter.into_iter().peekable();
+        vec.extend_to_capacity(&mut iter);
+
+        let heap = Self::from(vec);
+        if iter.peek().is_some() {
+            Err((heap, iter))
+        } else {
+            Ok(heap)
+        }
+    }
+
+    /// Creates a binary heap in the given storage block from an iterator,
+    /// discarding any items that don't fit.
+    ///
+    /// The heap is filled to capacity, if possible, and then heapified once.
+    /// **All remaining items are taken from the iterator and dropped**, so
+    /// this will not return if the iterator is infinite; use
+    /// [`Iterator::take`] or [`try_from_iter`](BinaryHeap::try_from_iter)
+    /// in that case.
+    ///
+    /// This complements the [`FromIterator`] implementation, which panics
+    /// if the iterator yields too many items.
+    ///
+    /// # Examples
+    /// ```
+    /// use coca::collections::SliceHeap;
+    /// use core::mem::MaybeUninit;
+    ///
+    /// let mut backing_region = [MaybeUninit::<u32>::uninit(); 4];
+    /// let heap = SliceHeap::<_>::from_iter_saturating(&mut backing_region[..], 1..=6);
+    /// assert!(heap.is_full());
+    /// assert_eq!(heap.peek(), Some(&4));
+    /// ```
+    pub fn from_iter_saturating<It: IntoIterator<Item = T>>(storage: S, iter: It) -> Self {
+        let mut vec = Vec::from(storage);
+        if let Some(rest) = vec.extend_to_capacity(iter) {
+            rest.for_each(drop);
+        }
+
+        Self::from(vec)
+    }
+
+    /// Creates a binary heap in the given storage block from an iterator,
+    /// keeping only the smallest items that fit.
+    ///
+    /// After filling the heap to capacity, each remaining item replaces the
+    /// greatest item in the heap if it is smaller, using
+    /// [`pop_if_greater_then_push`](BinaryHeap::pop_if_greater_then_push).
+    /// For a capacity of `n`, this is equivalent to Python's
+    /// `heapq.nsmallest(n, iter)`, taking O(k log(n)) time for k items, and
+    /// like [`from_iter_saturating`](BinaryHeap::from_iter_saturating), it
+    /// will not return if the iterator is infinite.
+    ///
+    /// To keep the greatest items instead, use a heap of
+    /// [`Reverse`](core::cmp::Reverse) items.
+    ///
+    /// # Examples
+    /// ```
+    /// use coca::collections::SliceHeap;
+    /// use core::mem::MaybeUninit;
+    ///
+    /// let mut backing_region = [MaybeUninit::<u32>::uninit(); 3];
+    /// let heap = SliceHeap::<_>::from_iter_keeping_smallest(
+    ///     &mut backing_region[..],
+    ///     [8, 3, 9, 1, 7, 2, 6],
+    /// );
+    /// assert_eq!(heap.into_sorted_vec(), &[1, 2, 3][..]);
+    /// ```
+    pub fn from_iter_keeping_smallest<It: IntoIterator<Item = T>>(storage: S, iter: It) -> Self {
+        let mut vec = Vec::from(storage);
+        let rest = vec.extend_to_capacity(iter);
+
+        let mut heap = Self::from(vec);
+        if let Some(rest) = rest {
+            for item in rest {
+                heap.pop_if_greater_then_push(item);
+            }
+        }
+
+        heap
+    }
+
     /// Returns a reference to the greatest item in the binary heap, or [`None`] if it is empty.
     #[inline]
     pub fn peek(&self) -> Option<&T> {
         self.a.first()
     }
 
+    /// Returns the number of levels of the tree stored in the heap, or zero if
+    /// it is empty.
+    ///
+    /// For a binary heap, this is `floor(log2(len)) + 1`.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut heap = coca::collections::InlineHeap::<u32, 8>::new();
+    /// assert_eq!(heap.depth(), 0);
+    ///
+    /// heap.extend([3, 1, 4, 1]);
+    /// assert_eq!(heap.depth(), 3);
+    /// ```
+    pub fn depth(&self) -> usize {
+        let len = self.len();
+        let (mut depth, mut start, mut width) = (0, 0usize, 1usize);
+        while start < len {
+            depth += 1;
+            start = start.saturating_add(width);
+            width = width.saturating_mul(D);
+        }
+        depth
+    }
+
+    /// Returns the items on the given level of the tree stored in the heap,
+    /// counting from zero at the root.
+    ///
+    /// Each level occupies a contiguous range of the underlying storage: a
+    /// level `k` holds up to `D^k` items, starting at index
+    /// `(D^k - 1) / (D - 1)`. The last level may be only partially filled,
+    /// and levels beyond the heap's [`depth`](BinaryHeap::depth) are empty.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut heap = coca::collections::InlineHeap::<u32, 8>::new();
+    /// heap.extend([1, 2, 3, 4, 5]);
+    ///
+    /// assert_eq!(heap.level_slice(0), &[5]);
+    /// assert_eq!(heap.level_slice(1).len(), 2);
+    /// assert_eq!(heap.level_slice(2).len(), 2);
+    /// assert!(heap.level_slice(3).is_empty());
+    /// ```
+    pub fn level_slice(&self, level: usize) -> &[T] {
+        let a = self.a.as_slice();
+        let (mut start, mut width) = (0usize, 1usize);
+        for _ in 0..level {
+            if start >= a.len() {
+                break;
+            }
+            start = start.saturating_add(width);
+            width = width.saturating_mul(D);
+        }
+
+        let start = start.min(a.len());
+        let end = start.saturating_add(width).min(a.len());
+        &a[start..end]
+    }
+
+    /// Returns a reference to the least item in the binary heap, or [`None`]
+    /// if it is empty.
+    ///
+    /// # Remarks
+    /// Since every item with children is greater than or equal to them, the
+    /// least item must be a leaf. Only the leaves are scanned, which make up
+    /// the last `(D - 1) / D` of the underlying storage, so this is still
+    /// O(n), but touches only about half as many items in a binary heap.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut heap = coca::collections::InlineHeap::<u32, 8>::new();
+    /// assert_eq!(heap.min(), None);
+    ///
+    /// heap.extend([3, 1, 4, 1, 5, 9, 2, 6]);
+    /// assert_eq!(heap.min(), Some(&1));
+    /// ```
+    pub fn min(&self) -> Option<&T> {
+        let a = self.a.as_slice();
+        a[first_leaf::<D>(a.len())..].iter().min()
+    }
+
+    /// Returns a reference to the `k`-th largest item in the binary heap,
+    /// counting from zero, or [`None`] if `k >= self.len()`.
+    ///
+    /// `heap.kth_largest(0)` is equivalent to `heap.peek()`.
+    ///
+    /// # Remarks
+    /// This is equivalent to `heap.top_k(k + 1).last()`, and shares its
+    /// complexity: O(k D log(k)) while the candidates fit into the iterator's
+    /// inline buffer, O(k min(n, D^k)) otherwise. See
+    /// [`top_k`](BinaryHeap::top_k) for details, and
+    /// [`kth_largest_in`](BinaryHeap::kth_largest_in) for a version using a
+    /// caller-provided scratch buffer instead.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut heap = coca::collections::InlineHeap::<u32, 8>::new();
+    /// heap.extend([3, 1, 4, 1, 5, 9, 2, 6]);
+    ///
+    /// assert_eq!(heap.kth_largest(0), Some(&9));
+    /// assert_eq!(heap.kth_largest(2), Some(&5));
+    /// assert_eq!(heap.kth_largest(7), Some(&1));
+    /// assert_eq!(heap.kth_largest(8), None);
+    /// ```
+    pub fn kth_largest(&self, k: usize) -> Option<&T> {
+        if k >= self.len() {
+            return None;
+        }
+
+        self.top_k(k + 1).last()
+    }
+
+    /// Returns the number of items in the binary heap that are greater than
+    /// or equal to `threshold`.
+    ///
+    /// # Remarks
+    /// Since no item is greater than its parent, any subtree whose root is
+    /// less than `threshold` is skipped entirely. The heap is traversed
+    /// without scratch memory, visiting at most `D` items per counted item,
+    /// making this O(min(n, D * m)), where m is the result.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut heap = coca::collections::InlineHeap::<u32, 8>::new();
+    /// heap.extend([3, 1, 4, 1, 5, 9, 2, 6]);
+    ///
+    /// assert_eq!(heap.count_ge(&5), 3);
+    /// assert_eq!(heap.count_ge(&1), 8);
+    /// assert_eq!(heap.count_ge(&10), 0);
+    /// ```
+    pub fn count_ge(&self, threshold: &T) -> usize {
+        let a = self.a.as_slice();
+        if a.is_empty() {
+            return 0;
+        }
+
+        let mut count = 0;
+        let mut i = 0;
+        loop {
+            if a[i] >= *threshold {
+                count += 1;
+                let child = first_child::<D>(i);
+                if child < a.len() {
+                    i = child;
+                    continue;
+                }
+            }
+
+            // move on to the next sibling, ascending past last children:
+            loop {
+                if i == 0 {
+                    return count;
+                }
+                if i % D != 0 && i + 1 < a.len() {
+                    i += 1;
+                    break;
+                }
+                i = parent::<D>(i);
+            }
+        }
+    }
+
     /// Returns a mutable reference to the greatest item in the binary heap, or
     /// [`None`] if it is empty.
     ///
@@ -175,7 +754,7 @@ impl<T: Ord, S: Storage<ArrayLayout<T>>, I: Capacity> BinaryHeap<T, S, I> {
     /// assert_eq!(heap.pop(), Some(0));
     /// ```
     #[inline]
-    pub fn peek_mut(&mut self) -> Option<PeekMut<T, S, I>> {
+    pub fn peek_mut(&mut self) -> Option<PeekMut<T, S, I, D>> {
         if self.is_empty() {
             None
         } else {
@@ -183,8 +762,81 @@ impl<T: Ord, S: Storage<ArrayLayout<T>>, I: Capacity> BinaryHeap<T, S, I> {
         }
     }
 
+    /// Returns a mutable reference to the greatest item in the binary heap,
+    /// first pushing the result of `default` if the heap is empty.
+    ///
+    /// As with [`peek_mut`](BinaryHeap::peek_mut), the heap property is
+    /// restored when the returned `PeekMut` is dropped.
+    ///
+    /// # Panics
+    /// Panics if the heap is empty and has zero capacity.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut heap = coca::collections::InlineHeap::<u32, 4>::new();
+    ///
+    /// *heap.peek_mut_or_insert_with(|| 1) += 10;
+    /// assert_eq!(heap.peek(), Some(&11));
+    ///
+    /// heap.push(5);
+    /// *heap.peek_mut_or_insert_with(|| unreachable!()) -= 10;
+    /// assert_eq!(heap.into_sorted_vec(), &[1, 5][..]);
+    /// ```
+    #[track_caller]
+    pub fn peek_mut_or_insert_with<F: FnOnce() -> T>(
+        &mut self,
+        default: F,
+    ) -> PeekMut<'_, T, S, I, D> {
+        if self.is_empty() {
+            self.push(default());
+        }
+
+        PeekMut { heap: self }
+    }
+
+    /// Returns a guard granting mutable access to the underlying vector,
+    /// allowing arbitrary modifications of the heap's contents. The heap
+    /// property is restored exactly once, when the guard is dropped.
+    ///
+    /// This is more efficient than individual [`push`](BinaryHeap::push) and
+    /// [`pop`](BinaryHeap::pop) calls when making many changes at once, as
+    /// restoring the heap property takes O(n) time regardless of the number
+    /// of modifications made.
+    ///
+    /// Note: If the `HeapGuard` value is leaked, the heap may be left in an
+    /// inconsistent state.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut heap = coca::collections::InlineHeap::<u32, 8>::new();
+    /// heap.extend([3, 1, 4, 1, 5]);
+    ///
+    /// {
+    ///     let mut vec = heap.bulk_mut();
+    ///     vec.retain(|&x| x != 1);
+    ///     vec.push(2);
+    ///     vec[0] = 0;
+    /// }
+    ///
+    /// assert_eq!(heap.pop(), Some(4));
+    /// assert_eq!(heap.pop(), Some(3));
+    /// assert_eq!(heap.pop(), Some(2));
+    /// assert_eq!(heap.pop(), Some(0));
+    /// assert_eq!(heap.pop(), None);
+    /// ```
+    #[inline]
+    pub fn bulk_mut(&mut self) -> HeapGuard<'_, T, S, I, D> {
+        HeapGuard { heap: self }
+    }
+
     /// Removes the greatest element from the binary heap and returns it, or [`None`] if it is empty.
     ///
+    /// # Remarks
+    /// This takes O(log(n)) time in all builds; the heap property is
+    /// maintained by construction, and never verified here, not even in debug
+    /// builds. Use [`is_valid_heap`](BinaryHeap::is_valid_heap) to check it
+    /// explicitly.
+    ///
     /// # Examples
     /// ```
     /// use coca::collections::{SliceHeap, SliceVec};
@@ -204,29 +856,171 @@ impl<T: Ord, S: Storage<ArrayLayout<T>>, I: Capacity> BinaryHeap<T, S, I> {
         }
 
         let result = self.a.swap_remove(I::from_usize(0));
-        heapify(self.a.as_mut_slice(), 0);
+        heapify::<T, D>(self.a.as_mut_slice(), 0);
         Some(result)
     }
 
-    /// Pushes an item onto the binary heap.
+    /// Removes the greatest item from the binary heap and returns it, or
+    /// [`None`] if it is empty, **without** restoring the heap property.
     ///
-    /// # Panics
-    /// Panics if the heap is already at capacity. See [`try_push`](BinaryHeap::try_push)
-    /// for a checked version that never panics.
-    #[inline]
-    pub fn push(&mut self, item: T) {
-        #[cold]
-        #[inline(never)]
-        fn assert_failed() -> ! {
-            panic!("binary heap is already at capacity")
-        }
-
-        if self.try_push(item).is_err() {
+    /// The last item is moved into the root position, so the heap is
+    /// generally left invalid. Until [`repair`](BinaryHeap::repair) is
+    /// called, it is a logic error to call any method that relies on the heap
+    /// property, such as [`peek`](BinaryHeap::peek), [`pop`](BinaryHeap::pop)
+    /// or [`push`](BinaryHeap::push). Calling this method again without a
+    /// repair no longer returns the greatest item. The behavior resulting
+    /// from such a logic error is not specified, but will not be undefined.
+    ///
+    /// This is only useful in batch operations that remove the maximum and
+    /// then rebuild the heap anyway; use [`pop`](BinaryHeap::pop) otherwise.
+    ///
+    /// # Remarks
+    /// This takes O(1) time, compared to O(log(n)) for `pop`.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut heap = coca::collections::InlineHeap::<u32, 8>::new();
+    /// heap.extend([3, 1, 4, 1, 5, 9, 2, 6]);
+    ///
+    /// assert_eq!(heap.swap_remove_max(), Some(9));
+    /// heap.repair();
+    /// assert_eq!(heap.pop(), Some(6));
+    /// ```
+    pub fn swap_remove_max(&mut self) -> Option<T> {
+        if self.is_empty() {
+            return None;
+        }
+
+        Some(self.a.swap_remove(I::from_usize(0)))
+    }
+
+    /// Removes up to `n` of the greatest items from the binary heap and
+    /// appends them to `dst` in descending order, returning how many items
+    /// were moved.
+    ///
+    /// Stops early if the heap becomes empty or `dst` becomes full; any
+    /// remaining items stay in the heap. The destination may use different
+    /// storage and index types than the heap.
+    ///
+    /// # Examples
+    /// ```
+    /// use coca::collections::{InlineHeap, InlineVec};
+    ///
+    /// let mut heap = InlineHeap::<u32, 8>::new();
+    /// heap.extend([3, 1, 4, 1, 5, 9, 2, 6]);
+    ///
+    /// let mut dst = InlineVec::<u32, 4, u8>::new();
+    /// assert_eq!(heap.pop_n(3, &mut dst), 3);
+    /// assert_eq!(dst, [9, 6, 5]);
+    ///
+    /// assert_eq!(heap.pop_n(3, &mut dst), 1);
+    /// assert_eq!(dst, [9, 6, 5, 4]);
+    /// assert_eq!(heap.len(), 4);
+    /// ```
+    pub fn pop_n<S2, I2>(&mut self, n: usize, dst: &mut Vec<T, S2, I2>) -> usize
+    where
+        S2: Storage<ArrayLayout<T>>,
+        I2: Capacity,
+    {
+        let n = n.min(dst.capacity() - dst.len());
+        let mut count = 0;
+        while count < n {
+            match self.pop() {
+                Some(item) => dst.push(item),
+                None => break,
+            }
+            count += 1;
+        }
+        count
+    }
+
+    /// Replaces the greatest item in the binary heap with `item` if `item`
+    /// is smaller, returning the previous greatest item.
+    ///
+    /// If the heap is empty, or `item` is not smaller than the greatest item,
+    /// the heap is left unchanged, `item` is dropped, and [`None`] is returned.
+    /// This never requires spare capacity, making it suitable for maintaining
+    /// the `k` smallest items seen so far in a full heap of capacity `k`.
+    ///
+    /// # Remarks
+    /// This takes O(log(n)) time if the item is replaced, and O(1) time
+    /// otherwise.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut heap = coca::collections::InlineHeap::<u32, 3>::new();
+    /// heap.extend([5, 8, 2]);
+    ///
+    /// for x in [7, 1, 9, 3] {
+    ///     heap.pop_if_greater_then_push(x);
+    /// }
+    ///
+    /// assert_eq!(heap.into_sorted_vec(), [1, 2, 3]);
+    /// ```
+    pub fn pop_if_greater_then_push(&mut self, item: T) -> Option<T> {
+        let a = self.a.as_mut_slice();
+        match a.first_mut() {
+            Some(max) if item < *max => {
+                let result = core::mem::replace(max, item);
+                heapify::<T, D>(a, 0);
+                Some(result)
+            }
+            _ => None,
+        }
+    }
+
+    /// Pushes an item onto the binary heap.
+    ///
+    /// # Panics
+    /// Panics if the heap is already at capacity. See [`try_push`](BinaryHeap::try_push)
+    /// for a checked version that never panics.
+    #[inline]
+    pub fn push(&mut self, item: T) {
+        #[cold]
+        #[inline(never)]
+        fn assert_failed() -> ! {
+            panic!("binary heap is already at capacity")
+        }
+
+        if self.try_push(item).is_err() {
             assert_failed();
         }
     }
 
-    /// Pushes an item onto the binary heap, returning `Err(item)` if it is full.
+    /// Pushes an item onto the binary heap unless an equal item is already
+    /// present, returning whether it was inserted.
+    ///
+    /// This allows using the heap as a priority set. Items that were not
+    /// inserted are dropped.
+    ///
+    /// # Remarks
+    /// Checking for an equal item takes O(n) time, in addition to the
+    /// O(log(n)) time taken by the push itself.
+    ///
+    /// # Panics
+    /// Panics if the item is not yet present, but the heap is already at
+    /// capacity.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut heap = coca::collections::InlineHeap::<u32, 4>::new();
+    /// assert!(heap.push_unique(3));
+    /// assert!(heap.push_unique(1));
+    /// assert!(!heap.push_unique(3));
+    /// assert_eq!(heap.len(), 2);
+    /// ```
+    #[track_caller]
+    pub fn push_unique(&mut self, item: T) -> bool {
+        if self.a.contains(&item) {
+            return false;
+        }
+
+        self.push(item);
+        true
+    }
+
+    /// Pushes an item onto the binary heap, returning it back in a
+    /// [`CapacityError`] if the heap is full.
     ///
     /// # Examples
     /// ```
@@ -239,17 +1033,302 @@ impl<T: Ord, S: Storage<ArrayLayout<T>>, I: Capacity> BinaryHeap<T, S, I> {
     /// assert_eq!(heap.len(), 3);
     /// assert_eq!(heap.peek(), Some(&5));
     /// ```
-    pub fn try_push(&mut self, item: T) -> Result<(), T> {
+    pub fn try_push(&mut self, item: T) -> Result<(), CapacityError<T>> {
         self.a.try_push(item)?;
         let a = self.a.as_mut_slice();
-        let mut i = a.len() - 1;
-        while i > 0 && a[parent(i)] < a[i] {
-            a.swap(i, parent(i));
-            i = parent(i);
+        sift_up::<T, D>(a, a.len() - 1);
+        Ok(())
+    }
+
+    /// Pushes the item returned by `f` onto the binary heap, writing it
+    /// directly into the backing storage.
+    ///
+    /// If the heap is full, `f` is not called and an error is returned
+    /// instead. If `f` panics, the heap is left unchanged.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut heap = coca::collections::InlineHeap::<[u8; 256], 2>::new();
+    /// assert!(heap.push_with(|| [1; 256]).is_ok());
+    /// assert!(heap.push_with(|| [2; 256]).is_ok());
+    /// assert!(heap.push_with(|| unreachable!()).is_err());
+    /// assert_eq!(heap.peek(), Some(&[2; 256]));
+    /// ```
+    pub fn push_with<F: FnOnce() -> T>(&mut self, f: F) -> crate::Result<()> {
+        let len = self.len();
+        let slot = self.a.spare_capacity_mut().first_mut();
+        match slot {
+            Some(slot) => {
+                slot.write(f());
+            }
+            None => return CapacityError::new(),
         }
+
+        // SAFETY: the slot just past the end was initialized above
+        unsafe { self.a.set_len(I::from_usize(len + 1)) };
+        sift_up::<T, D>(self.a.as_mut_slice(), len);
         Ok(())
     }
 
+    /// Pushes an item onto the binary heap if there is room, or replaces the
+    /// smallest item if the heap is full and `item` is greater than it.
+    ///
+    /// This never fails, making it suitable for bounded caches that should
+    /// retain the items of highest priority.
+    ///
+    /// # Remarks
+    /// Finding the smallest item in a full heap takes O(n) time, see
+    /// [`min`](BinaryHeap::min).
+    ///
+    /// # Examples
+    /// ```
+    /// use coca::collections::binary_heap::InsertOutcome;
+    ///
+    /// let mut heap = coca::collections::InlineHeap::<u32, 2>::new();
+    /// assert_eq!(heap.insert_bounded(5), InsertOutcome::Inserted);
+    /// assert_eq!(heap.insert_bounded(3), InsertOutcome::Inserted);
+    /// assert_eq!(heap.insert_bounded(2), InsertOutcome::Rejected(2));
+    /// assert_eq!(heap.insert_bounded(4), InsertOutcome::Replaced(3));
+    /// assert_eq!(heap.into_sorted_vec(), [4, 5]);
+    /// ```
+    pub fn insert_bounded(&mut self, item: T) -> InsertOutcome<T> {
+        if !self.is_full() {
+            self.push(item);
+            return InsertOutcome::Inserted;
+        }
+
+        let a = self.a.as_mut_slice();
+        let first_leaf = first_leaf::<D>(a.len());
+        let min = a[first_leaf..]
+            .iter()
+            .enumerate()
+            .min_by(|(_, x), (_, y)| x.cmp(y))
+            .map(|(i, _)| first_leaf + i);
+
+        match min {
+            Some(i) if item > a[i] => {
+                let evicted = core::mem::replace(&mut a[i], item);
+                sift_up::<T, D>(a, i);
+                InsertOutcome::Replaced(evicted)
+            }
+            _ => InsertOutcome::Rejected(item),
+        }
+    }
+
+    /// Pushes all items yielded by an iterator onto the binary heap.
+    ///
+    /// The items are appended in bulk before the heap property is restored
+    /// once, which takes O(n + k) time for k new items, rather than the
+    /// O(k log(n + k)) time taken by pushing them one at a time.
+    ///
+    /// # Panics
+    /// Panics if the iterator yields more items than the heap has room for.
+    /// See [`try_push_all`](BinaryHeap::try_push_all) for a checked version
+    /// that never panics.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut heap = coca::collections::InlineHeap::<u32, 8>::new();
+    /// heap.push_all([3, 1, 4, 1, 5]);
+    /// assert_eq!(heap.len(), 5);
+    /// assert_eq!(heap.peek(), Some(&5));
+    /// ```
+    #[track_caller]
+    pub fn push_all<It: IntoIterator<Item = T>>(&mut self, iter: It) {
+        #[cold]
+        #[inline(never)]
+        #[track_caller]
+        fn assert_failed() -> ! {
+            panic!("binary heap is already at capacity")
+        }
+
+        if self.try_push_all(iter).is_err() {
+            assert_failed();
+        }
+    }
+
+    /// Pushes all items yielded by an iterator onto the binary heap, or as
+    /// many as there is room for.
+    ///
+    /// Like [`push_all`](BinaryHeap::push_all), this restores the heap
+    /// property only once. If the iterator yields more items than the heap
+    /// has room for, the heap is filled to capacity, and the remaining items
+    /// are returned in an [`Err`]. See [`Vec::checked_extend`] for details.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut heap = coca::collections::InlineHeap::<u32, 4>::new();
+    /// assert!(heap.try_push_all([3, 1]).is_ok());
+    ///
+    /// let rest = heap.try_push_all([4, 1, 5, 9]).unwrap_err();
+    /// assert!(rest.eq([5, 9]));
+    /// assert_eq!(heap.into_sorted_vec(), &[1, 1, 3, 4][..]);
+    /// ```
+    pub fn try_push_all<It: IntoIterator<Item = T>>(
+        &mut self,
+        iter: It,
+    ) -> Result<(), Peekable<It::IntoIter>> {
+        let result = self.a.checked_extend(iter);
+        build_heap::<T, D>(self.a.as_mut_slice());
+        result
+    }
+
+    /// Clones and pushes all items in a slice sorted in descending order onto
+    /// the binary heap.
+    ///
+    /// Since the appended items are already ordered among themselves, only
+    /// the ancestors of the new items need to be checked, which is cheaper
+    /// than restoring the heap property for the entire heap as
+    /// [`push_all`](BinaryHeap::push_all) does.
+    ///
+    /// # Panics
+    /// Panics if the slice is longer than the heap's remaining capacity. See
+    /// [`try_append_sorted_desc`](BinaryHeap::try_append_sorted_desc) for a
+    /// checked version that never panics.
+    ///
+    /// In debug builds, also panics if the slice is not sorted in descending
+    /// order.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut heap = coca::collections::InlineHeap::<u32, 8>::new();
+    /// heap.extend([3, 1, 4]);
+    /// heap.append_sorted_desc(&[9, 6, 5, 2]);
+    /// assert_eq!(heap.into_sorted_vec(), &[1, 2, 3, 4, 5, 6, 9][..]);
+    /// ```
+    #[track_caller]
+    pub fn append_sorted_desc(&mut self, sorted: &[T])
+    where
+        T: Clone,
+    {
+        #[cold]
+        #[inline(never)]
+        #[track_caller]
+        fn assert_failed(len: usize, remaining: usize) -> ! {
+            panic!(
+                "slice length (is {}) should be <= remaining capacity (is {})",
+                len, remaining
+            )
+        }
+
+        if self.try_append_sorted_desc(sorted).is_err() {
+            assert_failed(sorted.len(), self.capacity() - self.len());
+        }
+    }
+
+    /// Clones and pushes all items in a slice sorted in descending order onto
+    /// the binary heap, or returns [`Err`] without modifying the heap if its
+    /// remaining capacity is insufficient.
+    ///
+    /// See [`append_sorted_desc`](BinaryHeap::append_sorted_desc) for details.
+    ///
+    /// # Panics
+    /// In debug builds, panics if the slice is not sorted in descending order.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut heap = coca::collections::InlineHeap::<u32, 4>::new();
+    /// assert!(heap.try_append_sorted_desc(&[5, 3, 1]).is_ok());
+    /// assert!(heap.try_append_sorted_desc(&[4, 2]).is_err());
+    /// assert_eq!(heap.len(), 3);
+    /// ```
+    #[track_caller]
+    pub fn try_append_sorted_desc(&mut self, sorted: &[T]) -> crate::Result<()>
+    where
+        T: Clone,
+    {
+        debug_assert!(
+            sorted.windows(2).all(|w| w[0] >= w[1]),
+            "slice should be sorted in descending order"
+        );
+
+        let old_len = self.len();
+        if sorted.len() > self.capacity() - old_len {
+            return CapacityError::new();
+        }
+
+        if sorted.is_empty() {
+            return Ok(());
+        }
+
+        self.a.extend(sorted.iter().cloned());
+        let a = self.a.as_mut_slice();
+        if old_len == 0 {
+            return Ok(());
+        }
+
+        // Only ancestors of the new items can violate the heap property.
+        // These form a contiguous range of indices on each level of the tree,
+        // which are visited bottom-up, skipping nodes visited already.
+        let mut lo = parent::<D>(old_len);
+        let mut hi = parent::<D>(a.len() - 1);
+        loop {
+            for i in (lo..=hi).rev() {
+                heapify::<T, D>(a, i);
+            }
+
+            if lo == 0 {
+                return Ok(());
+            }
+
+            hi = usize::min(parent::<D>(hi), lo - 1);
+            lo = parent::<D>(lo);
+        }
+    }
+
+    /// Clones and appends all items in a slice to the underlying storage,
+    /// assuming the result already satisfies the heap property.
+    ///
+    /// This is a fast path for reconstructing a heap from items stored in
+    /// heap order, e.g. after deserializing the output of
+    /// [`iter`](BinaryHeap::iter). The heap property is not restored; in
+    /// debug builds, it is verified afterwards instead, which takes O(n) time.
+    ///
+    /// If the assumption does not hold, the heap's behavior is unspecified,
+    /// though memory safe; [`repair`](BinaryHeap::repair) can be used to
+    /// recover from this. Prefer [`push_all`](BinaryHeap::push_all) for items
+    /// in arbitrary order.
+    ///
+    /// # Panics
+    /// Panics if the slice is longer than the heap's remaining capacity.
+    ///
+    /// In debug builds, also panics if the heap property does not hold
+    /// after appending the items.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut heap = coca::collections::InlineHeap::<u32, 8>::new();
+    /// heap.extend_heap_ordered(&[9, 5, 8, 1, 4]);
+    /// assert_eq!(heap.pop(), Some(9));
+    /// assert_eq!(heap.pop(), Some(8));
+    /// ```
+    #[track_caller]
+    pub fn extend_heap_ordered(&mut self, items: &[T])
+    where
+        T: Clone,
+    {
+        #[cold]
+        #[inline(never)]
+        #[track_caller]
+        fn assert_failed(len: usize, remaining: usize) -> ! {
+            panic!(
+                "slice length (is {}) should be <= remaining capacity (is {})",
+                len, remaining
+            )
+        }
+
+        let remaining = self.capacity() - self.len();
+        if items.len() > remaining {
+            assert_failed(items.len(), remaining);
+        }
+
+        self.a.extend(items.iter().cloned());
+        debug_assert!(
+            self.is_valid_heap(),
+            "appended items should preserve the heap property"
+        );
+    }
+
     /// Returns the number of elements the binary heap can hold.
     #[inline]
     pub fn capacity(&self) -> usize {
@@ -262,6 +1341,59 @@ impl<T: Ord, S: Storage<ArrayLayout<T>>, I: Capacity> BinaryHeap<T, S, I> {
         self.a.len()
     }
 
+    /// Returns the number of elements the binary heap can hold, represented
+    /// as a value of the index type `I`.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut heap = coca::collections::InlineHeap::<char, 200, u8>::new();
+    /// heap.push('a');
+    ///
+    /// assert_eq!(heap.capacity_index(), 200u8);
+    /// assert_eq!(heap.len_index(), 1u8);
+    /// ```
+    #[inline]
+    pub fn capacity_index(&self) -> I {
+        I::from_usize(self.capacity())
+    }
+
+    /// Returns the number of elements in the binary heap, represented as a
+    /// value of the index type `I`.
+    ///
+    /// See [`capacity_index`](BinaryHeap::capacity_index) for an example.
+    #[inline]
+    pub fn len_index(&self) -> I {
+        I::from_usize(self.len())
+    }
+
+    /// Returns the size of the binary heap's storage in bytes, i.e. the
+    /// capacity multiplied by the size of `T`.
+    ///
+    /// This does not include any padding or bookkeeping overhead of the
+    /// storage block or the heap itself.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut heap = coca::collections::InlineHeap::<u32, 16>::new();
+    /// heap.extend([3, 1, 4]);
+    ///
+    /// assert_eq!(heap.capacity_bytes(), 64);
+    /// assert_eq!(heap.len_bytes(), 12);
+    /// ```
+    #[inline]
+    pub fn capacity_bytes(&self) -> usize {
+        self.capacity() * core::mem::size_of::<T>()
+    }
+
+    /// Returns the number of bytes occupied by the elements in the binary
+    /// heap, i.e. the length multiplied by the size of `T`.
+    ///
+    /// See [`capacity_bytes`](BinaryHeap::capacity_bytes) for an example.
+    #[inline]
+    pub fn len_bytes(&self) -> usize {
+        self.len() * core::mem::size_of::<T>()
+    }
+
     /// Returns `true` if the binary heap contains no elements.
     #[inline]
     pub fn is_empty(&self) -> bool {
@@ -274,335 +1406,4240 @@ impl<T: Ord, S: Storage<ArrayLayout<T>>, I: Capacity> BinaryHeap<T, S, I> {
         self.a.is_full()
     }
 
-    /// Returns an iterator visiting all values in the underlying vector in arbitrary order.
-    pub fn iter(&self) -> impl Iterator<Item = &T> {
-        self.a.iter()
+    /// Asserts that the binary heap can hold at least `at_least` elements in
+    /// total, regardless of how many it currently contains.
+    ///
+    /// This is useful for failing fast during setup, rather than on some
+    /// later call to [`push`](BinaryHeap::push).
+    ///
+    /// # Panics
+    /// Panics if `self.capacity() < at_least`.
+    ///
+    /// # Examples
+    /// ```
+    /// let heap = coca::collections::InlineHeap::<u32, 8>::new();
+    /// heap.assert_capacity(8);
+    /// ```
+    /// ```should_panic
+    /// let heap = coca::collections::InlineHeap::<u32, 8>::new();
+    /// heap.assert_capacity(9);
+    /// ```
+    #[inline]
+    #[track_caller]
+    pub fn assert_capacity(&self, at_least: usize) {
+        #[cold]
+        #[inline(never)]
+        #[track_caller]
+        fn assert_failed(capacity: usize, at_least: usize) -> ! {
+            panic!(
+                "binary heap capacity (is {}) should be >= {}",
+                capacity, at_least
+            );
+        }
+
+        if self.capacity() < at_least {
+            assert_failed(self.capacity(), at_least);
+        }
     }
 
-    /// Clears the binary heap, returning an iterator over the removed elements.
-    /// The elements are removed in arbitrary order.
+    /// Applies `f` to the element at position `index` in the underlying storage,
+    /// then moves it up or down the heap as needed to restore the heap property.
+    ///
+    /// Returns `false` without calling `f` if `index` is out of bounds.
+    ///
+    /// This is the building block for increase-key and decrease-key operations,
+    /// taking O(log(n)) time. Callers must obtain indices from the heap's current
+    /// arrangement, e.g. using [`iter_indexed`](BinaryHeap::iter_indexed); any
+    /// mutation of the heap invalidates previously obtained indices.
+    ///
+    /// If `f` panics, the heap may be left in an inconsistent state.
     ///
     /// # Examples
     /// ```
-    /// let mut backing_region = [core::mem::MaybeUninit::<u32>::uninit(); 3];
-    /// let mut heap = coca::collections::SliceHeap::<_>::from(&mut backing_region[..]);
-    /// heap.push(1); heap.push(3);
-    /// assert!(!heap.is_empty());
+    /// let mut heap = coca::collections::InlineHeap::<u32, 8>::new();
+    /// heap.extend([3, 1, 4, 1, 5]);
     ///
-    /// let mut iter = heap.drain();
-    /// assert!(iter.next().is_some());
-    /// assert!(iter.next().is_some());
-    /// assert!(iter.next().is_none());
-    /// drop(iter);
+    /// let idx = heap.iter().position(|&x| x == 4).unwrap();
+    /// assert!(heap.update_at(idx, |x| *x = 9));
+    /// assert_eq!(heap.peek(), Some(&9));
     ///
-    /// assert!(heap.is_empty());
+    /// assert!(heap.update_at(0, |x| *x = 0));
+    /// assert_eq!(heap.peek(), Some(&5));
+    ///
+    /// assert!(!heap.update_at(5, |x| *x = 10));
     /// ```
-    #[inline]
-    pub fn drain(&mut self) -> Drain<'_, T, S, I> {
-        self.a.drain(..)
+    pub fn update_at<F: FnOnce(&mut T)>(&mut self, index: I, f: F) -> bool {
+        let i = index.as_usize();
+        let a = self.a.as_mut_slice();
+        if i >= a.len() {
+            return false;
+        }
+
+        f(&mut a[i]);
+        let i = sift_up::<T, D>(a, i);
+        heapify::<T, D>(a, i);
+        true
     }
 
-    /// Returns an iterator which retrieves elements in heap order. The retrieved
-    /// elements are removed from the original heap. The remaining elements will
-    /// be removed on drop in heap order.
+    /// Moves the element at position `index` in the underlying storage
+    /// towards the root until its parent is not less than it.
     ///
-    /// # Remarks
-    /// `.drain_sorted()` is O(n log(n)), much slower than [`.drain()`](BinaryHeap::drain).
-    /// The latter is preferable in most cases.
+    /// This restores the heap property if it is violated only because the
+    /// element at `index` is greater than its parent, e.g. after increasing
+    /// its priority. Callers must obtain indices from the heap's current
+    /// arrangement; any other change to the heap invalidates them. If the
+    /// heap property is violated elsewhere, the heap is left in an
+    /// unspecified, but memory-safe, state. This takes O(log(n)) time.
+    ///
+    /// See also [`update_at`](BinaryHeap::update_at), which determines the
+    /// direction automatically.
+    ///
+    /// # Panics
+    /// Panics if `index` is out of bounds.
     ///
     /// # Examples
     /// ```
-    /// let mut backing_region = [core::mem::MaybeUninit::<u32>::uninit(); 3];
-    /// let mut heap = coca::collections::SliceHeap::<_>::from(&mut backing_region[..]);
-    /// heap.push(1); heap.push(3); heap.push(5);
+    /// use core::cell::Cell;
+    /// let mut heap = coca::collections::InlineHeap::<Cell<u32>, 8>::new();
+    /// heap.extend([3, 1, 4, 1, 5].map(Cell::new));
     ///
-    /// let mut iter = heap.drain_sorted();
-    /// assert_eq!(iter.next(), Some(5));
+    /// let idx = heap.iter().position(|x| x.get() == 1).unwrap();
+    /// heap.iter().nth(idx).unwrap().set(9);
+    /// heap.sift_up(idx);
+    /// assert_eq!(heap.peek().map(Cell::get), Some(9));
+    ///
+    /// heap.peek().unwrap().set(0);
+    /// heap.sift_down(0);
+    /// assert_eq!(heap.peek().map(Cell::get), Some(5));
+    /// ```
+    #[track_caller]
+    pub fn sift_up(&mut self, index: I) {
+        let i = self.checked_index(index);
+        sift_up::<T, D>(self.a.as_mut_slice(), i);
+    }
+
+    /// Moves the element at position `index` in the underlying storage
+    /// towards the leaves until none of its children are greater than it.
+    ///
+    /// This restores the heap property if it is violated only because the
+    /// element at `index` is less than one of its children, e.g. after
+    /// decreasing its priority. The same caveats as for
+    /// [`sift_up`](BinaryHeap::sift_up) apply. This takes O(D log(n)) time.
+    ///
+    /// # Panics
+    /// Panics if `index` is out of bounds.
+    #[track_caller]
+    pub fn sift_down(&mut self, index: I) {
+        let i = self.checked_index(index);
+        heapify::<T, D>(self.a.as_mut_slice(), i);
+    }
+
+    #[inline]
+    #[track_caller]
+    fn checked_index(&self, index: I) -> usize {
+        #[cold]
+        #[inline(never)]
+        #[track_caller]
+        fn assert_failed(idx: usize, len: usize) -> ! {
+            panic!("sift index (is {}) should be < len (is {})", idx, len);
+        }
+
+        let i = index.as_usize();
+        if i >= self.len() {
+            assert_failed(i, self.len());
+        }
+        i
+    }
+
+    /// Returns `true` if the heap property holds, i.e. if no element is
+    /// greater than its parent.
+    ///
+    /// This is always the case unless an element's ordering relative to
+    /// others was changed while it was in the heap, which is a logic error,
+    /// or the underlying storage was modified through unsafe code. It is
+    /// primarily useful in debug assertions and tests.
+    ///
+    /// This takes O(n) time.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut heap = coca::collections::InlineHeap::<u32, 8>::new();
+    /// heap.extend([3, 1, 4, 1, 5, 9, 2, 6]);
+    /// assert!(heap.is_valid_heap());
+    /// ```
+    pub fn is_valid_heap(&self) -> bool {
+        let a = self.a.as_slice();
+        (1..a.len()).all(|i| a[parent::<D>(i)] >= a[i])
+    }
+
+    /// Restores the heap property, no matter how it was violated.
+    ///
+    /// This provides a well-defined recovery path after elements' ordering
+    /// was changed while they were in the heap, e.g. through interior
+    /// mutability, as long as their ordering is consistent again by the time
+    /// this is called. See [`is_valid_heap`](BinaryHeap::is_valid_heap) for
+    /// detecting the need for this.
+    ///
+    /// This takes O(n) time.
+    ///
+    /// # Examples
+    /// ```
+    /// use core::cell::Cell;
+    /// let mut heap = coca::collections::InlineHeap::<Cell<u32>, 8>::new();
+    /// heap.extend([3, 1, 4].map(Cell::new));
+    ///
+    /// heap.iter().for_each(|x| x.set(10 - x.get()));
+    /// assert!(!heap.is_valid_heap());
+    ///
+    /// heap.repair();
+    /// assert!(heap.is_valid_heap());
+    /// assert_eq!(heap.pop().map(Cell::into_inner), Some(9));
+    /// ```
+    pub fn repair(&mut self) {
+        build_heap::<T, D>(self.a.as_mut_slice());
+    }
+
+    /// Returns an iterator visiting all values in the underlying vector in
+    /// arbitrary order.
+    ///
+    /// Specifically, values are visited in the order of the underlying
+    /// vector, which stores the heap's tree in level order: the greatest item
+    /// comes first, and the children of the item at index `i` are found at
+    /// indices `D * i + 1` through `D * i + D`. The same holds for
+    /// [`into_iter`](BinaryHeap::into_iter), [`drain`](BinaryHeap::drain) and
+    /// [`into_vec`](BinaryHeap::into_vec). Which layout results from a given
+    /// sequence of operations, however, is not guaranteed to remain the same
+    /// across versions.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut heap = coca::collections::InlineHeap::<u32, 4>::new();
+    /// heap.extend([1, 2, 3, 4]);
+    ///
+    /// let mut iter = heap.iter();
+    /// assert_eq!(iter.next(), Some(&4));
+    /// assert_eq!(iter.count(), 3);
+    /// ```
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.a.iter()
+    }
+
+    /// Returns an iterator visiting all values in the underlying vector,
+    /// along with their positions, in the same order as [`iter`](BinaryHeap::iter).
+    ///
+    /// The positions can be passed to [`update_at`](BinaryHeap::update_at),
+    /// [`sift_up`](BinaryHeap::sift_up) and [`sift_down`](BinaryHeap::sift_down),
+    /// e.g. to implement decrease-key operations. **Any mutation of the heap
+    /// invalidates all previously obtained positions**, including calls to
+    /// these methods, so external bookkeeping must be refreshed afterwards.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut heap = coca::collections::InlineHeap::<u32, 8, u8>::new();
+    /// heap.extend([3, 1, 4, 1, 5]);
+    ///
+    /// let (idx, _) = heap.iter_indexed().find(|&(_, &x)| x == 3).unwrap();
+    /// assert!(heap.update_at(idx, |x| *x = 7));
+    /// assert_eq!(heap.peek(), Some(&7));
+    /// ```
+    pub fn iter_indexed(&self) -> impl Iterator<Item = (I, &T)> {
+        self.a
+            .iter()
+            .enumerate()
+            .map(|(i, x)| (I::from_usize(i), x))
+    }
+
+    /// Returns an iterator visiting all values in the binary heap in heap
+    /// order, without removing them.
+    ///
+    /// # Remarks
+    /// Since the underlying storage can not be reordered, each step selects
+    /// the next element by scanning the entire heap. This requires no scratch
+    /// memory, but makes a full iteration O(n²). Prefer [`.iter()`](BinaryHeap::iter)
+    /// if the order doesn't matter, or [`.into_iter_sorted()`](BinaryHeap::into_iter_sorted)
+    /// if the heap is no longer needed.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut heap = coca::collections::InlineHeap::<u32, 4>::new();
+    /// heap.push(1); heap.push(5); heap.push(3); heap.push(5);
+    ///
+    /// let mut iter = heap.iter_sorted();
+    /// assert_eq!(iter.next(), Some(&5));
+    /// assert_eq!(iter.next(), Some(&5));
+    /// assert_eq!(iter.next(), Some(&3));
+    /// assert_eq!(iter.next(), Some(&1));
+    /// assert_eq!(iter.next(), None);
+    ///
+    /// assert_eq!(heap.len(), 4);
+    /// assert_eq!(heap.peek(), Some(&5));
+    /// ```
+    #[inline]
+    pub fn iter_sorted(&self) -> IterSorted<'_, T> {
+        IterSorted::new(self.a.as_slice())
+    }
+
+    /// Returns an iterator visiting references to the `k` greatest items in
+    /// the binary heap in descending order, or all items if there are fewer.
+    ///
+    /// The heap itself is left untouched. Instead, the iterator tracks the
+    /// candidates for the next item in an auxiliary heap of indices, stored
+    /// inline with room for 64 entries; a full iteration takes
+    /// O(k D log(k)) time. Unlike [`iter_sorted`](BinaryHeap::iter_sorted),
+    /// this does not depend on the size of the heap.
+    ///
+    /// Selections needing more than 64 candidates, i.e. when
+    /// [`top_k_scratch_len(k)`](BinaryHeap::top_k_scratch_len) exceeds 64,
+    /// instead fall back to repeatedly scanning the first `k` levels of the
+    /// heap, which takes O(k min(n, D^k)) time. Use
+    /// [`top_k_in`](BinaryHeap::top_k_in) with a scratch buffer to avoid this.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut heap = coca::collections::InlineHeap::<u32, 8>::new();
+    /// heap.extend([3, 1, 4, 1, 5, 9, 2, 6]);
+    ///
+    /// let mut top = heap.top_k(3);
+    /// assert_eq!(top.next(), Some(&9));
+    /// assert_eq!(top.next(), Some(&6));
+    /// assert_eq!(top.next(), Some(&5));
+    /// assert_eq!(top.next(), None);
+    ///
+    /// assert_eq!(heap.len(), 8);
+    /// assert_eq!(heap.top_k(10).count(), 8);
+    /// ```
+    #[inline]
+    pub fn top_k(&self, k: usize) -> TopK<'_, T, D> {
+        TopK::new(self.a.as_slice(), k)
+    }
+
+    /// Returns the minimum length of a scratch buffer passed to
+    /// [`top_k_in`](BinaryHeap::top_k_in) for selecting `k` items, which is
+    /// `1 + (k - 1) * (D - 1)`, or zero if `k` is zero.
+    ///
+    /// # Examples
+    /// ```
+    /// use coca::collections::binary_heap::{BinaryHeap, QuaternaryHeap};
+    /// use coca::storage::InlineStorage;
+    ///
+    /// assert_eq!(BinaryHeap::<u32, InlineStorage<u32, 8>>::top_k_scratch_len(5), 5);
+    /// assert_eq!(QuaternaryHeap::<u32, InlineStorage<u32, 8>>::top_k_scratch_len(5), 13);
+    /// ```
+    #[inline]
+    pub fn top_k_scratch_len(k: usize) -> usize {
+        top_k_frontier_len::<D>(k)
+    }
+
+    /// Returns an iterator visiting references to the `k` greatest items in
+    /// the binary heap in descending order, or all items if there are fewer,
+    /// using `scratch` to track candidates.
+    ///
+    /// This is equivalent to [`top_k`](BinaryHeap::top_k), but with `k`
+    /// chosen at run time. No memory is allocated, and the same scratch
+    /// buffer can be reused across calls; its contents on entry are ignored.
+    ///
+    /// # Panics
+    /// Panics if `scratch` is shorter than
+    /// [`top_k_scratch_len(k)`](BinaryHeap::top_k_scratch_len), with `k`
+    /// clamped to the heap's length.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut heap = coca::collections::InlineHeap::<u32, 8>::new();
+    /// heap.extend([3, 1, 4, 1, 5, 9, 2, 6]);
+    ///
+    /// let mut scratch = [0; 8];
+    /// for k in 0..=8 {
+    ///     assert!(heap.top_k_in(k, &mut scratch).eq(heap.iter_sorted().take(k)));
+    /// }
+    /// ```
+    #[track_caller]
+    pub fn top_k_in<'s>(&self, k: usize, scratch: &'s mut [usize]) -> TopKIn<'_, 's, T, D> {
+        #[cold]
+        #[inline(never)]
+        #[track_caller]
+        fn assert_failed(len: usize, required: usize) -> ! {
+            panic!(
+                "scratch length (is {}) should be >= required length (is {})",
+                len, required
+            );
+        }
+
+        let a = self.a.as_slice();
+        let remaining = k.min(a.len());
+        let required = top_k_frontier_len::<D>(remaining);
+        if scratch.len() < required {
+            assert_failed(scratch.len(), required);
+        }
+        if let Some(root) = scratch.first_mut() {
+            *root = 0;
+        }
+
+        TopKIn {
+            a,
+            frontier: scratch,
+            frontier_len: remaining.min(1),
+            remaining,
+        }
+    }
+
+    /// Returns a reference to the `k`-th largest item in the binary heap,
+    /// counting from zero, or [`None`] if `k >= self.len()`, using `scratch`
+    /// to track candidates.
+    ///
+    /// This is equivalent to [`kth_largest`](BinaryHeap::kth_largest), but
+    /// stays O(k D log(k)) for any `k`.
+    ///
+    /// `heap.kth_largest_in(0, scratch)` is equivalent to `heap.peek()`.
+    ///
+    /// # Remarks
+    /// The heap itself is left untouched. Instead, the indices of candidate
+    /// items are kept in a binary max-heap within `scratch`, starting from
+    /// the root; each step removes the greatest candidate and adds its
+    /// children, making this O(k * D * log(k)).
+    ///
+    /// # Panics
+    /// Panics if `k < self.len()` and `scratch` is shorter than
+    /// [`top_k_scratch_len(k + 1)`](BinaryHeap::top_k_scratch_len).
+    ///
+    /// # Examples
+    /// ```
+    /// let mut heap = coca::collections::InlineHeap::<u32, 8>::new();
+    /// heap.extend([3, 1, 4, 1, 5, 9, 2, 6]);
+    ///
+    /// let mut scratch = [0; 8];
+    /// assert_eq!(heap.kth_largest_in(0, &mut scratch), Some(&9));
+    /// assert_eq!(heap.kth_largest_in(2, &mut scratch), Some(&5));
+    /// assert_eq!(heap.kth_largest_in(7, &mut scratch), Some(&1));
+    /// assert_eq!(heap.kth_largest_in(8, &mut scratch), None);
+    /// ```
+    #[track_caller]
+    pub fn kth_largest_in(&self, k: usize, scratch: &mut [usize]) -> Option<&T> {
+        if k >= self.len() {
+            return None;
+        }
+
+        self.top_k_in(k + 1, scratch).last()
+    }
+
+    /// Returns a wrapper whose [`Debug`] implementation lists the items in
+    /// descending order, rather than in the arbitrary order of the heap's
+    /// own implementation.
+    ///
+    /// This is useful for deterministic output in tests and logs, regardless
+    /// of the order in which items were pushed. Formatting takes O(n²) time,
+    /// see [`iter_sorted`](BinaryHeap::iter_sorted).
+    ///
+    /// # Examples
+    /// ```
+    /// use core::fmt::Write;
+    ///
+    /// let mut heap = coca::collections::InlineHeap::<u32, 4>::new();
+    /// heap.extend([1, 5, 3]);
+    ///
+    /// let mut s = coca::InlineString::<16>::new();
+    /// write!(s, "{:?}", heap.debug_sorted()).unwrap();
+    /// assert_eq!(s, "[5, 3, 1]");
+    /// ```
+    #[inline]
+    pub fn debug_sorted(&self) -> DebugSorted<'_, T> {
+        DebugSorted {
+            a: self.a.as_slice(),
+        }
+    }
+
+    /// Clears the binary heap, returning an iterator over the removed elements.
+    /// The elements are removed in arbitrary order.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut backing_region = [core::mem::MaybeUninit::<u32>::uninit(); 3];
+    /// let mut heap = coca::collections::SliceHeap::<_>::from(&mut backing_region[..]);
+    /// heap.push(1); heap.push(3);
+    /// assert!(!heap.is_empty());
+    ///
+    /// let mut iter = heap.drain();
+    /// assert!(iter.next().is_some());
+    /// assert!(iter.next().is_some());
+    /// assert!(iter.next().is_none());
+    /// drop(iter);
+    ///
+    /// assert!(heap.is_empty());
+    /// ```
+    #[inline]
+    pub fn drain(&mut self) -> Drain<'_, T, S, I> {
+        self.a.drain(..)
+    }
+
+    /// Returns an iterator which retrieves elements in heap order. The retrieved
+    /// elements are removed from the original heap. The remaining elements will
+    /// be removed on drop in heap order.
+    ///
+    /// # Remarks
+    /// `.drain_sorted()` is O(n log(n)), much slower than [`.drain()`](BinaryHeap::drain).
+    /// The latter is preferable in most cases.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut backing_region = [core::mem::MaybeUninit::<u32>::uninit(); 3];
+    /// let mut heap = coca::collections::SliceHeap::<_>::from(&mut backing_region[..]);
+    /// heap.push(1); heap.push(3); heap.push(5);
+    ///
+    /// let mut iter = heap.drain_sorted();
+    /// assert_eq!(iter.next(), Some(5));
     /// drop(iter);
     /// assert!(heap.is_empty());
     /// ```
     #[inline]
-    pub fn drain_sorted(&mut self) -> DrainSorted<'_, T, S, I> {
+    pub fn drain_sorted(&mut self) -> DrainSorted<'_, T, S, I, D> {
         DrainSorted { heap: self }
     }
 
-    /// Drops all items from the binary heap.
-    #[inline]
-    pub fn clear(&mut self) {
-        self.a.clear();
+    /// Returns an iterator which retrieves elements in heap order. The retrieved
+    /// elements are removed from the original heap. The remaining elements will
+    /// be dropped in arbitrary order when the iterator is dropped.
+    ///
+    /// Unlike [`drain_sorted`](BinaryHeap::drain_sorted), which pops all
+    /// remaining elements one by one when dropped early, this simply clears
+    /// the heap. This is preferable when early termination is common and the
+    /// order in which the remainder is dropped does not matter.
+    ///
+    /// # Remarks
+    /// Each retrieved element takes O(log(n)) time, while dropping the
+    /// iterator takes O(n) time, regardless of how far it was advanced.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut heap = coca::collections::InlineHeap::<u32, 4>::new();
+    /// heap.extend([1, 3, 5, 2]);
+    ///
+    /// let mut iter = heap.drain_sorted_fast();
+    /// assert_eq!(iter.next(), Some(5));
+    /// assert_eq!(iter.next(), Some(3));
+    /// drop(iter);
+    /// assert!(heap.is_empty());
+    /// ```
+    #[inline]
+    pub fn drain_sorted_fast(&mut self) -> DrainSortedFast<'_, T, S, I, D> {
+        DrainSortedFast { heap: self }
+    }
+
+    /// Returns an iterator which retrieves elements in reverse heap order,
+    /// i.e. in ascending order. The retrieved elements are removed from the
+    /// original heap. The remaining elements will be removed on drop in
+    /// ascending order.
+    ///
+    /// # Remarks
+    /// This sorts the heap's contents in place before returning the iterator,
+    /// which is O(n log(n)); the iteration itself is O(1) per element.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut backing_region = [core::mem::MaybeUninit::<u32>::uninit(); 3];
+    /// let mut heap = coca::collections::SliceHeap::<_>::from(&mut backing_region[..]);
+    /// heap.push(3); heap.push(1); heap.push(5);
+    ///
+    /// let mut iter = heap.drain_sorted_rev();
+    /// assert_eq!(iter.next(), Some(1));
+    /// assert_eq!(iter.next(), Some(3));
+    /// drop(iter);
+    /// assert!(heap.is_empty());
+    /// ```
+    pub fn drain_sorted_rev(&mut self) -> DrainSortedRev<'_, T, S, I> {
+        heap_sort::<T, D>(self.a.as_mut_slice());
+        DrainSortedRev {
+            iter: self.a.drain(..),
+        }
+    }
+
+    /// Returns an iterator which removes items from the binary heap in
+    /// descending order, yielding them in batches of up to `N` items each.
+    ///
+    /// Every batch except possibly the last one holds exactly `N` items; if
+    /// `N` is zero, or the heap is empty, no batches are yielded. Items not
+    /// yet retrieved when the iterator is dropped remain in the heap.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut heap = coca::collections::InlineHeap::<u32, 8>::new();
+    /// heap.extend([3, 1, 4, 1, 5, 9, 2, 6]);
+    ///
+    /// let mut pages = heap.sorted_chunks::<3>();
+    /// assert_eq!(pages.next().unwrap(), [9, 6, 5]);
+    /// assert_eq!(pages.next().unwrap(), [4, 3, 2]);
+    /// drop(pages);
+    ///
+    /// assert_eq!(heap.len(), 2);
+    /// assert_eq!(heap.sorted_chunks::<3>().next().unwrap(), [1, 1]);
+    /// assert!(heap.is_empty());
+    /// ```
+    #[inline]
+    pub fn sorted_chunks<const N: usize>(&mut self) -> SortedChunks<'_, T, S, I, N, D> {
+        SortedChunks { heap: self }
+    }
+
+    /// Returns an iterator which removes and yields all items matching the
+    /// predicate `f`, in arbitrary order.
+    ///
+    /// The heap property is restored for the remaining items when the
+    /// iterator is dropped, in O(n) time. Matching items that were not yet
+    /// yielded are dropped at that point.
+    ///
+    /// Note: If the `DrainWhere` value is leaked, the heap may be left in an
+    /// inconsistent state.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut heap = coca::collections::InlineHeap::<u32, 8>::new();
+    /// heap.extend([3, 1, 4, 1, 5, 9, 2, 6]);
+    ///
+    /// let mut evens = coca::collections::InlineVec::<u32, 8>::new();
+    /// evens.extend(heap.drain_where(|x| x % 2 == 0));
+    /// evens.sort_unstable();
+    /// assert_eq!(evens, [2, 4, 6]);
+    ///
+    /// assert_eq!(heap.into_sorted_vec(), &[1, 1, 3, 5, 9][..]);
+    /// ```
+    pub fn drain_where<F: FnMut(&T) -> bool>(&mut self, f: F) -> DrainWhere<'_, T, S, I, F, D> {
+        DrainWhere {
+            heap: self,
+            idx: 0,
+            pred: f,
+        }
+    }
+
+    /// Replaces the contents of `dst` with clones of this heap's items,
+    /// reusing its storage block.
+    ///
+    /// The items keep their positions, so the heap property holds in `dst`
+    /// without restoring it. Returns [`Err`] without modifying `dst` if its
+    /// capacity is insufficient.
+    ///
+    /// # Examples
+    /// ```
+    /// use core::mem::MaybeUninit;
+    /// use coca::collections::SliceHeap;
+    ///
+    /// let mut backing_a = [MaybeUninit::<u32>::uninit(); 4];
+    /// let mut backing_b = [MaybeUninit::<u32>::uninit(); 4];
+    /// let mut a = SliceHeap::<u32>::from(&mut backing_a[..]);
+    /// let mut b = SliceHeap::<u32>::from(&mut backing_b[..]);
+    /// a.extend([3, 1, 4]);
+    /// b.push(9);
+    ///
+    /// assert!(a.clone_into(&mut b).is_ok());
+    /// assert_eq!(a, b);
+    /// assert_eq!(b.pop(), Some(4));
+    /// ```
+    pub fn clone_into<S2, I2>(&self, dst: &mut DaryHeap<T, S2, I2, D>) -> crate::Result<()>
+    where
+        T: Clone,
+        S2: Storage<ArrayLayout<T>>,
+        I2: Capacity,
+    {
+        if self.len() > dst.capacity() {
+            return CapacityError::new();
+        }
+
+        dst.clear();
+        dst.a.extend(self.a.iter().cloned());
+        Ok(())
+    }
+
+    /// Retains only the items specified by the predicate, dropping all
+    /// others.
+    ///
+    /// The predicate is called exactly once for each item, in arbitrary
+    /// order. The heap property is restored once at the end, in O(n) time.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut heap = coca::collections::InlineHeap::<u32, 8>::new();
+    /// heap.extend([3, 1, 4, 1, 5, 9, 2, 6]);
+    ///
+    /// heap.retain(|&x| x % 3 != 0);
+    /// assert_eq!(heap.into_sorted_vec(), &[1, 1, 2, 4, 5][..]);
+    /// ```
+    #[inline]
+    pub fn retain<F: FnMut(&T) -> bool>(&mut self, f: F) {
+        self.retain_counting(f);
+    }
+
+    /// Retains only the items specified by the predicate, dropping all
+    /// others, and returns how many items were kept and removed, in that
+    /// order.
+    ///
+    /// See [`retain`](BinaryHeap::retain) for details.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut heap = coca::collections::InlineHeap::<u32, 8>::new();
+    /// heap.extend([3, 1, 4, 1, 5, 9, 2, 6]);
+    ///
+    /// assert_eq!(heap.retain_counting(|&x| x > 3), (4, 4));
+    /// assert_eq!(heap.len(), 4);
+    /// ```
+    pub fn retain_counting<F: FnMut(&T) -> bool>(&mut self, mut f: F) -> (usize, usize) {
+        let removed = self.drain_where(|x| !f(x)).count();
+        (self.len(), removed)
+    }
+
+    /// Drops all items from the binary heap.
+    ///
+    /// The storage block is retained, so the heap can be refilled up to its
+    /// full capacity afterwards.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.a.clear();
+    }
+
+    /// Drops all items from the binary heap, returning it to its initial
+    /// state with the entire storage block uninitialized.
+    ///
+    /// This behaves like [`clear`](BinaryHeap::clear), but additionally
+    /// asserts in debug builds that the full capacity is available for the
+    /// next fill. Unlike [`drain`](BinaryHeap::drain), the items are dropped
+    /// in place rather than handed to the caller.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut heap = coca::collections::InlineHeap::<u32, 4>::new();
+    /// heap.extend([3, 1, 4, 1]);
+    ///
+    /// heap.reset();
+    /// assert!(heap.is_empty());
+    ///
+    /// heap.extend([5, 9, 2, 6]);
+    /// assert_eq!(heap.peek(), Some(&9));
+    /// ```
+    pub fn reset(&mut self) {
+        let capacity = self.capacity();
+        let spare = self.a.clear_to_spare();
+        debug_assert_eq!(spare.len(), capacity);
+    }
+
+    /// Consumes the `BinaryHeap` and returns the underlying vector in arbitrary order.
+    #[inline]
+    pub fn into_vec(self) -> Vec<T, S, I> {
+        self.a
+    }
+
+    /// Consumes the `BinaryHeap` and returns a vector in sorted (ascending) order.
+    ///
+    /// # Remarks
+    /// This is an in-place heap sort taking O(n log(n)) time in all builds;
+    /// like [`pop`](BinaryHeap::pop), it relies on the heap property without
+    /// verifying it, so no additional O(n) checks are made in debug builds.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut backing_region = [core::mem::MaybeUninit::<u32>::uninit(); 5];
+    /// let mut heap = coca::collections::SliceHeap::<_>::from(&mut backing_region[..]);
+    /// heap.push(1); heap.push(5); heap.push(3); heap.push(2); heap.push(4);
+    /// let vec = heap.into_sorted_vec();
+    /// assert_eq!(vec, &[1, 2, 3, 4, 5][..]);
+    /// ```
+    pub fn into_sorted_vec(self) -> Vec<T, S, I> {
+        let mut result = self.into_vec();
+        heap_sort::<T, D>(result.as_mut_slice());
+        result
+    }
+
+    /// Consumes the `BinaryHeap` and returns a vector in stably sorted
+    /// (ascending) order.
+    ///
+    /// The heap does not track insertion order, so equal items are kept in
+    /// the order they appear in the underlying storage at the time of the
+    /// call, i.e. the level order yielded by [`iter`](BinaryHeap::iter).
+    /// To preserve insertion order instead, include a sequence number in
+    /// each item and compare by it last.
+    ///
+    /// # Remarks
+    /// With the `alloc` feature enabled, this uses the standard library's
+    /// stable sort, which takes O(n log(n)) time and allocates a temporary
+    /// buffer. Otherwise, it falls back to an in-place insertion sort taking
+    /// O(n²) time.
+    ///
+    /// # Examples
+    /// ```
+    /// #[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+    /// struct Task { priority: u8, id: char }
+    ///
+    /// let mut heap = coca::collections::InlineHeap::<Task, 4>::new();
+    /// heap.push(Task { priority: 1, id: 'a' });
+    /// heap.push(Task { priority: 2, id: 'b' });
+    ///
+    /// let order: coca::collections::InlineVec<char, 4> =
+    ///     heap.iter().map(|t| t.id).collect();
+    /// let sorted: coca::collections::InlineVec<char, 4> =
+    ///     heap.into_stably_sorted_vec().iter().map(|t| t.id).collect();
+    /// assert_eq!(order, ['b', 'a']);
+    /// assert_eq!(sorted, ['a', 'b']);
+    /// ```
+    pub fn into_stably_sorted_vec(self) -> Vec<T, S, I> {
+        let mut result = self.into_vec();
+        let a = result.as_mut_slice();
+
+        #[cfg(feature = "alloc")]
+        a.sort();
+
+        #[cfg(not(feature = "alloc"))]
+        for i in 1..a.len() {
+            let mut j = i;
+            while j > 0 && a[j - 1] > a[j] {
+                a.swap(j - 1, j);
+                j -= 1;
+            }
+        }
+
+        result
+    }
+
+    /// Consumes the `BinaryHeap` and returns a vector whose first `k` elements
+    /// are the `k` greatest items in descending order, followed by the
+    /// remaining items in arbitrary order.
+    ///
+    /// If `k` exceeds the heap's length, all items are sorted.
+    ///
+    /// # Remarks
+    /// This takes O(n + k log(n)) time, which is much faster than
+    /// [`.into_sorted_vec()`](BinaryHeap::into_sorted_vec) for `k` much
+    /// smaller than `n`.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut heap = coca::collections::InlineHeap::<u32, 8>::new();
+    /// heap.extend([3, 1, 4, 1, 5, 9, 2, 6]);
+    ///
+    /// let vec = heap.into_partially_sorted_vec(3);
+    /// assert_eq!(vec[..3], [9, 6, 5]);
+    /// assert!(vec[3..].iter().all(|&x| x < 5));
+    /// ```
+    pub fn into_partially_sorted_vec(self, k: usize) -> Vec<T, S, I> {
+        let mut result = self.into_vec();
+        let a = result.as_mut_slice();
+        let len = a.len();
+        for i in (len.saturating_sub(k)..len).rev() {
+            a.swap(0, i);
+            heapify::<T, D>(&mut a[..i], 0);
+        }
+        a.reverse();
+        result
+    }
+
+    /// Consumes the `BinaryHeap`, retains only the elements specified by the
+    /// predicate, and returns them in a vector in sorted (ascending) order.
+    ///
+    /// This is equivalent to, but more efficient than, calling
+    /// [`into_vec`](BinaryHeap::into_vec), [`retain`](Vec::retain), and then
+    /// sorting the result: the survivors are heap sorted in place, without
+    /// any additional memory. The predicate is called exactly once for each
+    /// element, in arbitrary order.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut heap = coca::collections::InlineHeap::<u32, 8>::new();
+    /// heap.extend([3, 1, 4, 1, 5, 9, 2, 6]);
+    ///
+    /// let vec = heap.retain_into_sorted_vec(|&x| x > 2);
+    /// assert_eq!(vec, &[3, 4, 5, 6, 9][..]);
+    /// ```
+    pub fn retain_into_sorted_vec<F: FnMut(&T) -> bool>(self, f: F) -> Vec<T, S, I> {
+        let mut result = self.into_vec();
+        result.retain(f);
+        let a = result.as_mut_slice();
+        build_heap::<T, D>(a);
+        heap_sort::<T, D>(a);
+        result
+    }
+
+    /// Consumes the `BinaryHeap` and returns an iterator which yields elements
+    /// in heap order.
+    ///
+    /// When dropped, the remaining elements will be dropped in heap order.
+    ///
+    /// # Remarks
+    /// `.into_iter_sorted()` is O(n log(n)), much slower than [`.into_iter()`](BinaryHeap::into_iter).
+    /// The latter is preferable in most cases.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut backing_region = [core::mem::MaybeUninit::<u32>::uninit(); 3];
+    /// let mut heap = coca::collections::SliceHeap::<_>::from(&mut backing_region[..]);
+    /// heap.push(1); heap.push(3); heap.push(5);
+    ///
+    /// let mut iter = heap.into_iter_sorted();
+    /// assert_eq!(iter.next(), Some(5));
+    /// assert_eq!(iter.next(), Some(3));
+    /// assert_eq!(iter.next(), Some(1));
+    /// ```
+    pub fn into_iter_sorted(self) -> IntoIterSorted<T, S, I, D> {
+        IntoIterSorted { heap: self }
+    }
+}
+
+/// The result of [`BinaryHeap::insert_bounded`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum InsertOutcome<T> {
+    /// The item was pushed onto the heap, which was not yet full.
+    Inserted,
+    /// The item replaced the smallest item in the full heap, which is
+    /// returned.
+    Replaced(T),
+    /// The heap was full, and the item was not greater than its smallest
+    /// item. The rejected item is returned.
+    Rejected(T),
+}
+
+impl<T: Ord + Pod, const D: usize, S: Storage<ArrayLayout<T>>, I: Capacity> DaryHeap<T, S, I, D> {
+    /// Returns the heap's backing array, in level order, reinterpreted as
+    /// bytes.
+    ///
+    /// The bytes are in the platform's native byte order, so they can only
+    /// be read back on a platform with the same endianness and type layout.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut heap = coca::collections::InlineHeap::<u16, 4>::new();
+    /// heap.extend([0x0102, 0x0304]);
+    ///
+    /// let bytes = heap.as_bytes();
+    /// assert_eq!(bytes.len(), 4);
+    /// if cfg!(target_endian = "little") {
+    ///     assert_eq!(bytes, [0x04, 0x03, 0x02, 0x01]);
+    /// } else {
+    ///     assert_eq!(bytes, [0x03, 0x04, 0x01, 0x02]);
+    /// }
+    /// ```
+    pub fn as_bytes(&self) -> &[u8] {
+        let items = self.a.as_slice();
+        // SAFETY: `T: Pod` guarantees there are no padding bytes, and
+        // `u8` has no alignment requirement
+        unsafe { core::slice::from_raw_parts(items.as_ptr().cast(), core::mem::size_of_val(items)) }
+    }
+
+    /// Copies items from `bytes` into `storage` and establishes the heap
+    /// property, e.g. to restore a heap from the output of
+    /// [`as_bytes`](BinaryHeap::as_bytes).
+    ///
+    /// The bytes are interpreted in the platform's native byte order. They
+    /// need not be aligned, and need not form a valid heap.
+    ///
+    /// # Panics
+    /// Panics if the length of `bytes` is not a multiple of the size of `T`,
+    /// or if `bytes` holds more items than `storage` has room for.
+    ///
+    /// # Examples
+    /// ```
+    /// use coca::collections::InlineHeap;
+    /// use core::mem::MaybeUninit;
+    ///
+    /// let mut heap = InlineHeap::<u32, 8>::new();
+    /// heap.extend([3, 1, 4, 1, 5]);
+    ///
+    /// let restored = InlineHeap::<u32, 8>::from_bytes_heapify(
+    ///     [MaybeUninit::uninit(); 8],
+    ///     heap.as_bytes(),
+    /// );
+    /// assert_eq!(restored, heap);
+    /// ```
+    #[track_caller]
+    pub fn from_bytes_heapify(storage: S, bytes: &[u8]) -> Self {
+        #[cold]
+        #[inline(never)]
+        #[track_caller]
+        fn assert_failed(reason: &str, len: usize, n: usize) -> ! {
+            panic!("bytes length (is {}) should be {} (is {})", len, reason, n);
+        }
+
+        let size = core::mem::size_of::<T>();
+        let count = bytes.len().checked_div(size).unwrap_or(0);
+        if count * size != bytes.len() {
+            assert_failed("a multiple of the item size", bytes.len(), size);
+        }
+
+        let mut a = Vec::from(storage);
+        if count > a.capacity() {
+            assert_failed("<= capacity in bytes", bytes.len(), a.capacity() * size);
+        }
+
+        // SAFETY: the destination has room for `count` items, `T: Pod`
+        // makes any bit pattern a valid value, and copying bytes avoids
+        // alignment requirements on the source
+        unsafe {
+            core::ptr::copy_nonoverlapping(bytes.as_ptr(), a.as_mut_ptr().cast(), bytes.len());
+            a.set_len(I::from_usize(count));
+        }
+
+        build_heap::<T, D>(a.as_mut_slice());
+        DaryHeap { a }
+    }
+}
+
+impl<T: Ord, const D: usize, S: Storage<ArrayLayout<T>>, I: Capacity> IntoIterator
+    for DaryHeap<T, S, I, D>
+{
+    type Item = T;
+    type IntoIter = <Vec<T, S, I> as IntoIterator>::IntoIter;
+
+    /// Consumes the binary heap and returns an iterator yielding its items
+    /// in the order of the underlying vector, i.e. the heap's tree in level
+    /// order, as described for [`iter`](BinaryHeap::iter).
+    ///
+    /// # Examples
+    /// ```
+    /// let mut heap = coca::collections::InlineHeap::<u32, 4>::new();
+    /// heap.extend([1, 2, 3]);
+    ///
+    /// let mut iter = heap.into_iter();
+    /// assert_eq!(iter.next(), Some(3));
+    /// assert_eq!(iter.len(), 2);
+    /// ```
+    fn into_iter(self) -> Self::IntoIter {
+        self.a.into_iter()
+    }
+}
+
+impl<'a, T: Ord, const D: usize, S: Storage<ArrayLayout<T>>, I: Capacity> IntoIterator
+    for &'a DaryHeap<T, S, I, D>
+{
+    type Item = &'a T;
+    type IntoIter = core::slice::Iter<'a, T>;
+
+    /// Returns an iterator visiting all items in the binary heap in the
+    /// order of the underlying vector; see [`iter`](BinaryHeap::iter).
+    ///
+    /// # Examples
+    /// ```
+    /// let mut heap = coca::collections::InlineHeap::<u32, 4>::new();
+    /// heap.extend([1, 2, 3]);
+    ///
+    /// let mut sum = 0;
+    /// for x in &heap {
+    ///     sum += x;
+    /// }
+    /// assert_eq!(sum, 6);
+    /// assert_eq!(heap.len(), 3);
+    /// ```
+    fn into_iter(self) -> Self::IntoIter {
+        self.a.as_slice().iter()
+    }
+}
+
+impl<T1, T2: Ord, const D: usize, S: Storage<ArrayLayout<T2>>, I: Capacity> Extend<T1>
+    for DaryHeap<T2, S, I, D>
+where
+    Vec<T2, S, I>: Extend<T1>,
+{
+    /// Extends the binary heap with the contents of an iterator, restoring
+    /// the heap property once all items have been added.
+    ///
+    /// Like [`Vec`], this supports iterators over references to elements
+    /// implementing [`Clone`], which are cloned into the heap.
+    ///
+    /// # Panics
+    /// Panics if the iterator yields more items than the heap has room for.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut heap = coca::collections::InlineHeap::<u32, 8>::new();
+    /// heap.extend([3, 1, 4]);
+    ///
+    /// let more: &[u32] = &[1, 5, 9, 2];
+    /// heap.extend(more);
+    /// assert_eq!(heap.len(), 7);
+    /// assert_eq!(heap.peek(), Some(&9));
+    /// ```
+    fn extend<T: IntoIterator<Item = T1>>(&mut self, iter: T) {
+        self.a.extend(iter);
+        build_heap::<T2, D>(self.a.as_mut_slice());
+    }
+}
+
+impl<T: Ord, const D: usize, S: Storage<ArrayLayout<T>>, I: Capacity> FromIterator<T>
+    for DaryHeap<T, S, I, D>
+where
+    Vec<T, S, I>: FromIterator<T>,
+{
+    /// Creates a binary heap from an iterator.
+    ///
+    /// # Panics
+    /// Panics if the iterator yields more elements than the binary heap can
+    /// hold. If the lower bound of the iterator's [`size_hint`](Iterator::size_hint)
+    /// exceeds the capacity, this happens before any elements are consumed.
+    #[track_caller]
+    fn from_iter<It: IntoIterator<Item = T>>(iter: It) -> Self {
+        #[cold]
+        #[inline(never)]
+        #[track_caller]
+        fn assert_failed(capacity: usize) -> ! {
+            panic!(
+                "iterator should yield no more than `capacity` (is {}) items",
+                capacity
+            );
+        }
+
+        let iter = iter.into_iter();
+        let mut a: Vec<T, S, I> = core::iter::empty().collect();
+        let cap = a.capacity();
+        if iter.size_hint().0 > cap {
+            assert_failed(cap);
+        }
+
+        for item in iter {
+            if a.try_push(item).is_err() {
+                assert_failed(cap);
+            }
+        }
+
+        Self::from(a)
+    }
+}
+
+/// A draining iterator over the elements of a `BinaryHeap`.
+///
+/// This `struct` is created by [`BinaryHeap::drain_sorted()`].
+/// See its documentation for more.
+pub struct DrainSorted<'a, T: Ord, S: Storage<ArrayLayout<T>>, I: Capacity, const D: usize = 2> {
+    heap: &'a mut DaryHeap<T, S, I, D>,
+}
+
+impl<T: Ord, const D: usize, S: Storage<ArrayLayout<T>>, I: Capacity> Iterator
+    for DrainSorted<'_, T, S, I, D>
+{
+    type Item = T;
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let size = self.heap.len();
+        (size, Some(size))
+    }
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.heap.pop()
+    }
+}
+
+impl<T: Ord, const D: usize, S: Storage<ArrayLayout<T>>, I: Capacity> DoubleEndedIterator
+    for DrainSorted<'_, T, S, I, D>
+{
+    /// Removes and returns the least element remaining in the heap.
+    ///
+    /// Since the least element must be one of the leaves, this scans the
+    /// second half of the heap, which is O(n).
+    fn next_back(&mut self) -> Option<T> {
+        let a = self.heap.a.as_slice();
+        let first_leaf = first_leaf::<D>(a.len());
+        let (offset, _) = a[first_leaf..]
+            .iter()
+            .enumerate()
+            .min_by(|(_, x), (_, y)| x.cmp(y))?;
+        let idx = first_leaf + offset;
+
+        let result = self.heap.a.swap_remove(I::from_usize(idx));
+        let a = self.heap.a.as_mut_slice();
+        if idx < a.len() {
+            sift_up::<T, D>(a, idx);
+        }
+        Some(result)
+    }
+}
+
+impl<T: Ord, const D: usize, S: Storage<ArrayLayout<T>>, I: Capacity> ExactSizeIterator
+    for DrainSorted<'_, T, S, I, D>
+{
+}
+impl<T: Ord, const D: usize, S: Storage<ArrayLayout<T>>, I: Capacity> FusedIterator
+    for DrainSorted<'_, T, S, I, D>
+{
+}
+
+impl<T: Ord, const D: usize, S: Storage<ArrayLayout<T>>, I: Capacity> Drop
+    for DrainSorted<'_, T, S, I, D>
+{
+    fn drop(&mut self) {
+        self.for_each(drop);
+    }
+}
+
+/// A draining iterator over the elements of a `BinaryHeap` that drops any
+/// remaining elements in arbitrary order.
+///
+/// This `struct` is created by [`BinaryHeap::drain_sorted_fast()`].
+/// See its documentation for more.
+pub struct DrainSortedFast<'a, T: Ord, S: Storage<ArrayLayout<T>>, I: Capacity, const D: usize = 2>
+{
+    heap: &'a mut DaryHeap<T, S, I, D>,
+}
+
+impl<T: Ord, const D: usize, S: Storage<ArrayLayout<T>>, I: Capacity> Iterator
+    for DrainSortedFast<'_, T, S, I, D>
+{
+    type Item = T;
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let size = self.heap.len();
+        (size, Some(size))
+    }
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.heap.pop()
+    }
+}
+
+impl<T: Ord, const D: usize, S: Storage<ArrayLayout<T>>, I: Capacity> ExactSizeIterator
+    for DrainSortedFast<'_, T, S, I, D>
+{
+}
+impl<T: Ord, const D: usize, S: Storage<ArrayLayout<T>>, I: Capacity> FusedIterator
+    for DrainSortedFast<'_, T, S, I, D>
+{
+}
+
+impl<T: Ord, const D: usize, S: Storage<ArrayLayout<T>>, I: Capacity> Drop
+    for DrainSortedFast<'_, T, S, I, D>
+{
+    fn drop(&mut self) {
+        self.heap.clear();
+    }
+}
+
+/// A draining iterator over the elements of a `BinaryHeap` in ascending order.
+///
+/// This `struct` is created by [`BinaryHeap::drain_sorted_rev()`].
+/// See its documentation for more.
+pub struct DrainSortedRev<'a, T: Ord, S: Storage<ArrayLayout<T>>, I: Capacity> {
+    iter: Drain<'a, T, S, I>,
+}
+
+impl<T: Ord, S: Storage<ArrayLayout<T>>, I: Capacity> Iterator for DrainSortedRev<'_, T, S, I> {
+    type Item = T;
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+
+    #[inline]
+    fn next(&mut self) -> Option<T> {
+        self.iter.next()
+    }
+}
+
+impl<T: Ord, S: Storage<ArrayLayout<T>>, I: Capacity> DoubleEndedIterator
+    for DrainSortedRev<'_, T, S, I>
+{
+    #[inline]
+    fn next_back(&mut self) -> Option<T> {
+        self.iter.next_back()
+    }
+}
+
+impl<T: Ord, S: Storage<ArrayLayout<T>>, I: Capacity> ExactSizeIterator
+    for DrainSortedRev<'_, T, S, I>
+{
+}
+impl<T: Ord, S: Storage<ArrayLayout<T>>, I: Capacity> FusedIterator
+    for DrainSortedRev<'_, T, S, I>
+{
+}
+
+/// A draining iterator over the elements of a `BinaryHeap` that match a
+/// predicate, in arbitrary order.
+///
+/// This `struct` is created by [`BinaryHeap::drain_where()`].
+/// See its documentation for more.
+pub struct DrainWhere<
+    'a,
+    T: Ord,
+    S: Storage<ArrayLayout<T>>,
+    I: Capacity,
+    F: FnMut(&T) -> bool,
+    const D: usize = 2,
+> {
+    heap: &'a mut DaryHeap<T, S, I, D>,
+    idx: usize,
+    pred: F,
+}
+
+impl<T: Ord + Debug, const D: usize, S: Storage<ArrayLayout<T>>, I: Capacity, F> Debug
+    for DrainWhere<'_, T, S, I, F, D>
+where
+    F: FnMut(&T) -> bool,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DrainWhere")
+            .field("unchecked", &&self.heap.a.as_slice()[self.idx..])
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T: Ord, const D: usize, S: Storage<ArrayLayout<T>>, I: Capacity, F> Iterator
+    for DrainWhere<'_, T, S, I, F, D>
+where
+    F: FnMut(&T) -> bool,
+{
+    type Item = T;
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.heap.len() - self.idx))
+    }
+
+    fn next(&mut self) -> Option<T> {
+        while self.idx < self.heap.len() {
+            if (self.pred)(&self.heap.a.as_slice()[self.idx]) {
+                // the last item takes the removed one's place, so `idx` must
+                // not be advanced here
+                return Some(self.heap.a.swap_remove(I::from_usize(self.idx)));
+            }
+            self.idx += 1;
+        }
+
+        None
+    }
+}
+
+impl<T: Ord, const D: usize, S: Storage<ArrayLayout<T>>, I: Capacity, F> FusedIterator
+    for DrainWhere<'_, T, S, I, F, D>
+where
+    F: FnMut(&T) -> bool,
+{
+}
+
+impl<T: Ord, const D: usize, S: Storage<ArrayLayout<T>>, I: Capacity, F> Drop
+    for DrainWhere<'_, T, S, I, F, D>
+where
+    F: FnMut(&T) -> bool,
+{
+    fn drop(&mut self) {
+        self.for_each(drop);
+        build_heap::<T, D>(self.heap.a.as_mut_slice());
+    }
+}
+
+/// A draining iterator yielding the elements of a `BinaryHeap` in batches,
+/// in descending order.
+///
+/// This `struct` is created by [`BinaryHeap::sorted_chunks()`].
+/// See its documentation for more.
+pub struct SortedChunks<
+    'a,
+    T: Ord,
+    S: Storage<ArrayLayout<T>>,
+    I: Capacity,
+    const N: usize,
+    const D: usize = 2,
+> {
+    heap: &'a mut DaryHeap<T, S, I, D>,
+}
+
+impl<T: Ord + Debug, const D: usize, S: Storage<ArrayLayout<T>>, I: Capacity, const N: usize> Debug
+    for SortedChunks<'_, T, S, I, N, D>
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SortedChunks")
+            .field("heap", &self.heap)
+            .finish()
+    }
+}
+
+impl<T: Ord, const D: usize, S: Storage<ArrayLayout<T>>, I: Capacity, const N: usize> Iterator
+    for SortedChunks<'_, T, S, I, N, D>
+{
+    type Item = InlineVec<T, N>;
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let size = self
+            .heap
+            .len()
+            .checked_sub(1)
+            .and_then(|last| last.checked_div(N))
+            .map_or(0, |full| full + 1);
+        (size, Some(size))
+    }
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if N == 0 || self.heap.is_empty() {
+            return None;
+        }
+
+        let mut chunk = InlineVec::new();
+        self.heap.pop_n(N, &mut chunk);
+        Some(chunk)
+    }
+}
+
+impl<T: Ord, const D: usize, S: Storage<ArrayLayout<T>>, I: Capacity, const N: usize>
+    ExactSizeIterator for SortedChunks<'_, T, S, I, N, D>
+{
+}
+impl<T: Ord, const D: usize, S: Storage<ArrayLayout<T>>, I: Capacity, const N: usize> FusedIterator
+    for SortedChunks<'_, T, S, I, N, D>
+{
+}
+
+/// A consuming iterator that moves out of a `BinaryHeap`.
+///
+/// This `struct` is created by [`BinaryHeap::into_iter_sorted()`].
+/// See its documentation for more.
+#[derive(Debug)]
+pub struct IntoIterSorted<T: Ord, S: Storage<ArrayLayout<T>>, I: Capacity, const D: usize = 2> {
+    heap: DaryHeap<T, S, I, D>,
+}
+
+impl<T: Ord, const D: usize, S: Storage<ArrayLayout<T>>, I: Capacity> Iterator
+    for IntoIterSorted<T, S, I, D>
+{
+    type Item = T;
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let size = self.heap.len();
+        (size, Some(size))
+    }
+
+    #[inline]
+    fn next(&mut self) -> Option<T> {
+        self.heap.pop()
+    }
+}
+
+impl<T: Ord, const D: usize, S: Storage<ArrayLayout<T>>, I: Capacity> ExactSizeIterator
+    for IntoIterSorted<T, S, I, D>
+{
+}
+impl<T: Ord, const D: usize, S: Storage<ArrayLayout<T>>, I: Capacity> FusedIterator
+    for IntoIterSorted<T, S, I, D>
+{
+}
+
+impl<T: Clone + Ord, const D: usize, S: Storage<ArrayLayout<T>>, I: Capacity> Clone
+    for IntoIterSorted<T, S, I, D>
+where
+    DaryHeap<T, S, I, D>: Clone,
+{
+    fn clone(&self) -> Self {
+        self.heap.clone().into_iter_sorted()
+    }
+}
+
+impl<T: Ord, const D: usize, S: Storage<ArrayLayout<T>>, I: Capacity> Drop
+    for IntoIterSorted<T, S, I, D>
+{
+    fn drop(&mut self) {
+        self.for_each(drop);
+    }
+}
+
+/// An iterator over references to the elements of a `BinaryHeap` in heap order.
+///
+/// This `struct` is created by [`BinaryHeap::iter_sorted()`].
+/// See its documentation for more.
+pub struct IterSorted<'a, T: Ord> {
+    a: &'a [T],
+    last: Option<usize>,
+    remaining: usize,
+}
+
+impl<'a, T: Ord> IterSorted<'a, T> {
+    fn new(a: &'a [T]) -> Self {
+        IterSorted {
+            a,
+            last: None,
+            remaining: a.len(),
+        }
+    }
+}
+
+impl<'a, T: Ord> Iterator for IterSorted<'a, T> {
+    type Item = &'a T;
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+
+    fn next(&mut self) -> Option<&'a T> {
+        if self.remaining == 0 {
+            return None;
+        }
+
+        // Repeatedly select the greatest element not yet yielded; equal
+        // elements are yielded in order of their position in storage, so
+        // that each one is visited exactly once.
+        let mut best: Option<usize> = None;
+        for (i, x) in self.a.iter().enumerate() {
+            if let Some(last) = self.last {
+                let already_yielded = match x.cmp(&self.a[last]) {
+                    Ordering::Greater => true,
+                    Ordering::Equal => i <= last,
+                    Ordering::Less => false,
+                };
+
+                if already_yielded {
+                    continue;
+                }
+            }
+
+            // ties are broken in favor of the lower index:
+            match best {
+                Some(b) if *x <= self.a[b] => {}
+                _ => best = Some(i),
+            }
+        }
+
+        self.last = best;
+        self.remaining -= 1;
+        best.map(|i| &self.a[i])
+    }
+}
+
+impl<T: Ord> Clone for IterSorted<'_, T> {
+    fn clone(&self) -> Self {
+        IterSorted {
+            a: self.a,
+            last: self.last,
+            remaining: self.remaining,
+        }
+    }
+}
+
+impl<T: Ord> ExactSizeIterator for IterSorted<'_, T> {}
+impl<T: Ord> FusedIterator for IterSorted<'_, T> {}
+
+// Returns the number of candidate indices a top-k selection over a D-ary
+// heap may need to track at once: after j steps, there are at most
+// 1 + j * (D - 1) candidates, and no children are added after the last step.
+fn top_k_frontier_len<const D: usize>(k: usize) -> usize {
+    match k {
+        0 => 0,
+        _ => (k - 1).saturating_mul(D - 1).saturating_add(1),
+    }
+}
+
+// Advances a top-k selection over the heap `a`, using `frontier[..*len]` as
+// a binary max-heap of candidate indices, ordered by the elements they refer
+// to. Returns the index of the next greatest element, if any remain.
+fn top_k_step<T: Ord, const D: usize>(
+    a: &[T],
+    frontier: &mut [usize],
+    len: &mut usize,
+    remaining: &mut usize,
+) -> Option<usize> {
+    if *remaining == 0 {
+        return None;
+    }
+
+    let f = &mut frontier[..*len];
+    let result = f[0];
+    let last = f.len() - 1;
+    f.swap(0, last);
+
+    let f = &mut f[..last];
+    let mut i = 0;
+    loop {
+        let mut largest = i;
+        let first = first_child::<2>(i);
+        for child in first..first.saturating_add(2) {
+            if child < f.len() && a[f[child]] > a[f[largest]] {
+                largest = child;
+            }
+        }
+        if largest == i {
+            break;
+        }
+        f.swap(i, largest);
+        i = largest;
+    }
+
+    *len = last;
+    *remaining -= 1;
+    if *remaining > 0 {
+        let start = first_child::<D>(result).min(a.len());
+        let end = start.saturating_add(D).min(a.len());
+        for child in start..end {
+            let mut i = *len;
+            frontier[i] = child;
+            *len += 1;
+            while i > 0 && a[frontier[parent::<2>(i)]] < a[frontier[i]] {
+                frontier.swap(i, parent::<2>(i));
+                i = parent::<2>(i);
+            }
+        }
+    }
+
+    Some(result)
+}
+
+/// An iterator over references to the greatest elements of a `BinaryHeap`
+/// in descending order, using a caller-provided scratch buffer.
+///
+/// This `struct` is created by [`BinaryHeap::top_k_in()`].
+/// See its documentation for more.
+pub struct TopKIn<'a, 's, T: Ord, const D: usize = 2> {
+    a: &'a [T],
+    frontier: &'s mut [usize],
+    frontier_len: usize,
+    remaining: usize,
+}
+
+impl<'a, T: Ord, const D: usize> Iterator for TopKIn<'a, '_, T, D> {
+    type Item = &'a T;
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+
+    fn next(&mut self) -> Option<&'a T> {
+        let index = top_k_step::<T, D>(
+            self.a,
+            self.frontier,
+            &mut self.frontier_len,
+            &mut self.remaining,
+        )?;
+        Some(&self.a[index])
+    }
+}
+
+impl<T: Ord, const D: usize> ExactSizeIterator for TopKIn<'_, '_, T, D> {}
+impl<T: Ord, const D: usize> FusedIterator for TopKIn<'_, '_, T, D> {}
+
+/// A wrapper for formatting the elements of a `BinaryHeap` in descending
+/// order.
+///
+/// This `struct` is created by [`BinaryHeap::debug_sorted()`].
+/// See its documentation for more.
+pub struct DebugSorted<'a, T: Ord> {
+    a: &'a [T],
+}
+
+impl<T: Ord + Debug> Debug for DebugSorted<'_, T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(IterSorted::new(self.a)).finish()
+    }
+}
+
+// The number of candidate indices a `TopK` iterator can track inline.
+const TOP_K_INLINE_FRONTIER: usize = 64;
+
+// Returns the length of the prefix of a `D`-ary heap of length `len` that
+// spans its first `levels` levels; since every item is preceded by all of
+// its ancestors, this prefix contains the `levels` greatest items.
+fn top_k_levels_len<const D: usize>(len: usize, levels: usize) -> usize {
+    let mut total = 0usize;
+    let mut level_len = 1usize;
+    for _ in 0..levels {
+        total = total.saturating_add(level_len);
+        if total >= len {
+            return len;
+        }
+        level_len = level_len.saturating_mul(D);
+    }
+    total
+}
+
+/// An iterator over references to the greatest elements of a `BinaryHeap`
+/// in descending order.
+///
+/// This `struct` is created by [`BinaryHeap::top_k()`].
+/// See its documentation for more.
+pub struct TopK<'a, T: Ord, const D: usize = 2> {
+    a: &'a [T],
+    frontier: [usize; TOP_K_INLINE_FRONTIER],
+    frontier_len: usize,
+    remaining: usize,
+    fallback: Option<IterSorted<'a, T>>,
+}
+
+impl<'a, T: Ord, const D: usize> TopK<'a, T, D> {
+    fn new(a: &'a [T], k: usize) -> Self {
+        let remaining = k.min(a.len());
+        let fallback = if top_k_frontier_len::<D>(remaining) > TOP_K_INLINE_FRONTIER {
+            Some(IterSorted::new(
+                &a[..top_k_levels_len::<D>(a.len(), remaining)],
+            ))
+        } else {
+            None
+        };
+
+        TopK {
+            a,
+            frontier: [0; TOP_K_INLINE_FRONTIER],
+            frontier_len: remaining.min(1),
+            remaining,
+            fallback,
+        }
+    }
+}
+
+impl<'a, T: Ord, const D: usize> Iterator for TopK<'a, T, D> {
+    type Item = &'a T;
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+
+    fn next(&mut self) -> Option<&'a T> {
+        if let Some(fallback) = self.fallback.as_mut() {
+            if self.remaining == 0 {
+                return None;
+            }
+            self.remaining -= 1;
+            return fallback.next();
+        }
+
+        let index = top_k_step::<T, D>(
+            self.a,
+            &mut self.frontier,
+            &mut self.frontier_len,
+            &mut self.remaining,
+        )?;
+        Some(&self.a[index])
+    }
+}
+
+impl<T: Ord, const D: usize> Clone for TopK<'_, T, D> {
+    fn clone(&self) -> Self {
+        TopK {
+            a: self.a,
+            frontier: self.frontier,
+            frontier_len: self.frontier_len,
+            remaining: self.remaining,
+            fallback: self.fallback.clone(),
+        }
+    }
+}
+
+impl<T: Ord + Debug, const D: usize> Debug for TopK<'_, T, D> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.clone()).finish()
+    }
+}
+
+impl<T: Ord, const D: usize> ExactSizeIterator for TopK<'_, T, D> {}
+impl<T: Ord, const D: usize> FusedIterator for TopK<'_, T, D> {}
+
+/// Returns an iterator yielding the elements of all the given heaps in
+/// descending order, removing them from their respective heaps as it goes.
+///
+/// This performs a k-way merge, using the slice of heaps itself as a heap
+/// ordered by each heap's greatest element. This requires no scratch memory,
+/// and each step takes O(log(n) + log(k)) time, where `k` is the number of
+/// heaps. The order of the slice is unspecified after calling this function.
+///
+/// Elements not yet yielded when the iterator is dropped remain in their
+/// respective heaps.
+///
+/// # Examples
+/// ```
+/// use coca::collections::{binary_heap::merge_sorted, InlineHeap};
+///
+/// let mut a = InlineHeap::<u32, 4>::new();
+/// let mut b = InlineHeap::<u32, 4>::new();
+/// let mut c = InlineHeap::<u32, 4>::new();
+/// a.extend([1, 5, 9]);
+/// b.extend([2, 6]);
+/// c.extend([3, 4, 7, 8]);
+///
+/// let mut heaps = [&mut a, &mut b, &mut c];
+/// assert!(merge_sorted(&mut heaps).eq((1..=9).rev()));
+/// assert!(a.is_empty() && b.is_empty() && c.is_empty());
+/// ```
+pub fn merge_sorted<'a, 'b, T, S, I, const D: usize>(
+    heaps: &'a mut [&'b mut DaryHeap<T, S, I, D>],
+) -> MergeSorted<'a, 'b, T, S, I, D>
+where
+    T: Ord,
+    S: Storage<ArrayLayout<T>>,
+    I: Capacity,
+{
+    // move all empty heaps to the back, out of consideration:
+    let mut len = 0;
+    for i in 0..heaps.len() {
+        if !heaps[i].is_empty() {
+            heaps.swap(i, len);
+            len += 1;
+        }
+    }
+
+    let mut result = MergeSorted { heaps, len };
+    for i in (0..len / 2).rev() {
+        result.sift_down(i);
+    }
+    result
+}
+
+/// An iterator yielding the elements of several heaps in descending order.
+///
+/// This `struct` is created by the [`merge_sorted`] function. See its
+/// documentation for more.
+pub struct MergeSorted<'a, 'b, T, S, I, const D: usize = 2>
+where
+    T: Ord,
+    S: Storage<ArrayLayout<T>>,
+    I: Capacity,
+{
+    heaps: &'a mut [&'b mut DaryHeap<T, S, I, D>],
+    len: usize,
+}
+
+impl<T: Ord, const D: usize, S: Storage<ArrayLayout<T>>, I: Capacity>
+    MergeSorted<'_, '_, T, S, I, D>
+{
+    // Restores the heap property for the non-empty heaps, assuming only the
+    // heap at index `i` may be out of place.
+    fn sift_down(&mut self, mut i: usize) {
+        loop {
+            let l = first_child::<2>(i);
+            let r = l.saturating_add(1);
+
+            let mut largest = i;
+            if l < self.len && self.heaps[l].peek() > self.heaps[largest].peek() {
+                largest = l;
+            }
+            if r < self.len && self.heaps[r].peek() > self.heaps[largest].peek() {
+                largest = r;
+            }
+
+            if largest == i {
+                return;
+            }
+
+            self.heaps.swap(i, largest);
+            i = largest;
+        }
+    }
+}
+
+impl<T: Ord, const D: usize, S: Storage<ArrayLayout<T>>, I: Capacity> Iterator
+    for MergeSorted<'_, '_, T, S, I, D>
+{
+    type Item = T;
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let size = self.heaps[..self.len].iter().map(|h| h.len()).sum();
+        (size, Some(size))
+    }
+
+    fn next(&mut self) -> Option<T> {
+        if self.len == 0 {
+            return None;
+        }
+
+        let result = self.heaps[0].pop();
+        if self.heaps[0].is_empty() {
+            self.len -= 1;
+            self.heaps.swap(0, self.len);
+        }
+
+        self.sift_down(0);
+        result
+    }
+}
+
+impl<T: Ord, const D: usize, S: Storage<ArrayLayout<T>>, I: Capacity> ExactSizeIterator
+    for MergeSorted<'_, '_, T, S, I, D>
+{
+}
+impl<T: Ord, const D: usize, S: Storage<ArrayLayout<T>>, I: Capacity> FusedIterator
+    for MergeSorted<'_, '_, T, S, I, D>
+{
+}
+
+impl<'a, T: Ord, const D: usize, I: Capacity> DaryHeap<T, ArenaStorage<'a, ArrayLayout<T>>, I, D> {
+    /// Constructs a new, empty `ArenaHeap<T, I>` with the specified capacity,
+    /// backed by memory allocated from the given arena.
+    ///
+    /// # Panics
+    /// Panics if the remaining space in the arena is insufficient. See
+    /// [`try_with_capacity_in`](BinaryHeap::try_with_capacity_in) for a
+    /// checked version that never panics.
+    #[track_caller]
+    pub fn with_capacity_in(capacity: I, arena: &mut Arena<'a>) -> Self {
+        Self::try_with_capacity_in(capacity, arena)
+            .expect("unexpected allocation failure in `with_capacity_in`")
+    }
+
+    /// Constructs a new, empty `ArenaHeap<T, I>` with the specified capacity,
+    /// backed by memory allocated from the given arena.
+    ///
+    /// Returns [`None`] if the remaining space in the arena is insufficient.
+    ///
+    /// # Examples
+    /// ```
+    /// use coca::arena::Arena;
+    /// use coca::collections::ArenaHeap;
+    /// use core::mem::MaybeUninit;
+    ///
+    /// let mut backing_region = [MaybeUninit::uninit(); 192];
+    /// let mut arena = Arena::from(&mut backing_region[..]);
+    ///
+    /// let mut heap = ArenaHeap::<u32, u8>::try_with_capacity_in(32, &mut arena).unwrap();
+    /// heap.extend(0..32);
+    /// assert_eq!(heap.pop(), Some(31));
+    ///
+    /// assert!(ArenaHeap::<u32, u8>::try_with_capacity_in(32, &mut arena).is_none());
+    /// ```
+    pub fn try_with_capacity_in(capacity: I, arena: &mut Arena<'a>) -> Option<Self> {
+        arena.try_with_capacity(capacity.as_usize())
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(docs_rs, doc(cfg(feature = "alloc")))]
+impl<T: Ord, const D: usize, I: Capacity> DaryHeap<T, AllocStorage<ArrayLayout<T>>, I, D> {
+    /// Constructs a new, empty `AllocHeap<T, I>` with the specified capacity.
+    ///
+    /// # Panics
+    /// Panics if the specified capacity cannot be represented by a `usize`.
+    pub fn with_capacity(capacity: I) -> Self {
+        Self::from_vec_unchecked(Vec::with_capacity(capacity))
+    }
+
+    /// Constructs a new `AllocHeap<T, I>` with the specified capacity,
+    /// containing the items yielded by the given iterator.
+    ///
+    /// The heap property is established once after all items have been
+    /// collected, which takes O(n) time. This is faster than inserting the
+    /// items one at a time.
+    ///
+    /// # Panics
+    /// Panics if the specified capacity cannot be represented by a `usize`,
+    /// or if the iterator yields more than `capacity` items. If the lower
+    /// bound of the iterator's [`size_hint`](Iterator::size_hint) exceeds
+    /// `capacity`, this happens before any allocation takes place.
+    ///
+    /// # Examples
+    /// ```
+    /// use coca::collections::AllocHeap;
+    ///
+    /// let mut heap = AllocHeap::<u32>::from_iter_in(8, [3, 1, 4, 1, 5]);
+    /// assert_eq!(heap.capacity(), 8);
+    /// assert_eq!(heap.len(), 5);
+    /// assert_eq!(heap.pop(), Some(5));
+    /// ```
+    #[track_caller]
+    pub fn from_iter_in<It: IntoIterator<Item = T>>(capacity: I, iter: It) -> Self {
+        #[cold]
+        #[inline(never)]
+        #[track_caller]
+        fn assert_failed(capacity: usize) -> ! {
+            panic!(
+                "iterator should yield no more than `capacity` (is {}) items",
+                capacity
+            );
+        }
+
+        let iter = iter.into_iter();
+        let cap = capacity.as_usize();
+        if iter.size_hint().0 > cap {
+            assert_failed(cap);
+        }
+
+        let mut vec = Vec::with_capacity(capacity);
+        for item in iter {
+            if vec.try_push(item).is_err() {
+                assert_failed(cap);
+            }
+        }
+
+        Self::from(vec)
+    }
+
+    /// Ensures the heap can hold at least `at_least` elements in total,
+    /// reallocating the underlying storage block if necessary.
+    ///
+    /// If the heap's capacity is less than `at_least`, it is increased to
+    /// exactly `at_least`, and the heap's contents are moved to the new
+    /// allocation. Otherwise, the heap is returned unchanged.
+    ///
+    /// # Panics
+    /// Panics if `at_least` cannot be represented by a `usize`, or if
+    /// allocation fails.
+    ///
+    /// # Examples
+    /// ```
+    /// use coca::collections::AllocHeap;
+    ///
+    /// let heap = AllocHeap::<u32>::with_capacity(4).with_min_capacity(16);
+    /// assert_eq!(heap.capacity(), 16);
+    ///
+    /// let heap = heap.with_min_capacity(8);
+    /// assert_eq!(heap.capacity(), 16);
+    /// ```
+    #[must_use]
+    pub fn with_min_capacity(mut self, at_least: I) -> Self {
+        if self.capacity() >= at_least.as_usize() {
+            return self;
+        }
+
+        let mut result = Self::with_capacity(at_least);
+        result.a.extend(self.a.drain(..));
+        result
+    }
+
+    /// Constructs a new, empty `AllocHeap<T, I>` with the specified capacity,
+    /// returning an error instead of panicking if the allocation fails.
+    ///
+    /// # Examples
+    /// ```
+    /// use coca::collections::AllocHeap;
+    /// use coca::storage::TryReserveError;
+    ///
+    /// let heap = AllocHeap::<u64>::try_with_capacity(16).unwrap();
+    /// assert_eq!(heap.capacity(), 16);
+    ///
+    /// let result = AllocHeap::<u64>::try_with_capacity(usize::MAX);
+    /// assert_eq!(result.err(), Some(TryReserveError::CapacityOverflow));
+    /// ```
+    pub fn try_with_capacity(capacity: I) -> Result<Self, TryReserveError> {
+        let cap = capacity.as_usize();
+        if capacity != I::from_usize(cap) {
+            return Err(TryReserveError::CapacityOverflow);
+        }
+
+        let buf = AllocStorage::try_with_capacity(cap)?;
+        Ok(Self::from(buf))
+    }
+
+    /// Shrinks the capacity of the binary heap with a lower bound, reallocating
+    /// the underlying storage block.
+    ///
+    /// The capacity will remain at least as large as both the length and
+    /// the supplied value. If the current capacity is less than the lower
+    /// limit, this is a no-op.
+    ///
+    /// # Panics
+    /// Panics if reallocation fails.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut heap = coca::collections::AllocHeap::<u32>::with_capacity(10);
+    /// heap.extend([3, 1, 4, 1, 5]);
+    /// heap.pop(); heap.pop();
+    ///
+    /// heap.shrink_to(4);
+    /// assert_eq!(heap.capacity(), 4);
+    /// assert_eq!(heap.pop(), Some(3));
+    /// ```
+    #[inline]
+    pub fn shrink_to(&mut self, min_capacity: I) {
+        self.a.shrink_to(min_capacity);
+    }
+
+    /// Shrinks the capacity of the binary heap as much as possible, reallocating
+    /// the underlying storage block.
+    ///
+    /// # Panics
+    /// Panics if reallocation fails.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut heap = coca::collections::AllocHeap::<u32>::with_capacity(10);
+    /// heap.extend([3, 1, 4, 1, 5]);
+    ///
+    /// heap.shrink_to_fit();
+    /// assert_eq!(heap.capacity(), 5);
+    /// ```
+    #[inline]
+    pub fn shrink_to_fit(&mut self) {
+        self.a.shrink_to_fit();
+    }
+
+    /// Checks that at least `additional` more items can be pushed onto the
+    /// binary heap, returning [`Err`] otherwise.
+    ///
+    /// This never reallocates, since the capacity of an `AllocHeap` is fixed
+    /// after construction. See [`Vec::try_reserve`] for more.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut heap = coca::collections::AllocHeap::<u32>::with_capacity(4);
+    /// heap.extend([3, 1]);
+    ///
+    /// assert!(heap.try_reserve(2).is_ok());
+    /// assert!(heap.try_reserve(3).is_err());
+    /// assert_eq!(heap.capacity(), 4);
+    /// ```
+    #[inline]
+    pub fn try_reserve(&mut self, additional: I) -> crate::Result<()> {
+        self.a.try_reserve(additional)
+    }
+
+    /// Asserts that at least `additional` more items can be pushed onto the
+    /// binary heap.
+    ///
+    /// This never reallocates; see [`try_reserve`](BinaryHeap::try_reserve).
+    ///
+    /// # Panics
+    /// Panics if the remaining capacity is less than `additional`.
+    #[inline]
+    #[track_caller]
+    pub fn reserve(&mut self, additional: I) {
+        self.a.reserve(additional);
+    }
+
+    /// Asserts that at least `additional` more items can be pushed onto the
+    /// binary heap.
+    ///
+    /// Since this never reallocates, it is equivalent to
+    /// [`reserve`](BinaryHeap::reserve).
+    ///
+    /// # Panics
+    /// Panics if the remaining capacity is less than `additional`.
+    #[inline]
+    #[track_caller]
+    pub fn reserve_exact(&mut self, additional: I) {
+        self.a.reserve_exact(additional);
+    }
+
+    /// Consumes the `AllocHeap` and returns a boxed slice in sorted (ascending) order.
+    ///
+    /// This sorts the heap's contents in place and reuses its allocation,
+    /// shrunk to fit, without copying any elements.
+    ///
+    /// # Panics
+    /// Panics if reallocation fails.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut heap = coca::collections::AllocHeap::<u32>::with_capacity(10);
+    /// heap.extend([3, 1, 4, 1, 5]);
+    ///
+    /// let slice = heap.into_sorted_boxed_slice();
+    /// assert_eq!(&*slice, &[1, 1, 3, 4, 5]);
+    /// ```
+    pub fn into_sorted_boxed_slice(self) -> alloc::boxed::Box<[T]> {
+        self.into_sorted_vec().into_boxed_slice()
+    }
+
+    /// Moves all elements out of the heap into a vector in sorted (ascending)
+    /// order, leaving the heap empty.
+    ///
+    /// The returned vector takes over the heap's allocation, so no elements
+    /// are copied, and the heap is left with a capacity of zero, just like
+    /// one constructed by [`Default::default`]; this never allocates. It is
+    /// equivalent to, but more convenient than, calling
+    /// [`into_sorted_vec`](BinaryHeap::into_sorted_vec) on the result of
+    /// [`core::mem::take`].
+    ///
+    /// Use [`drain_sorted_rev`](BinaryHeap::drain_sorted_rev) instead to
+    /// retain the allocation for refilling the heap; it yields the elements
+    /// in the same order and works with any storage type. Use
+    /// [`drain`](BinaryHeap::drain) if the order doesn't matter.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut heap = coca::collections::AllocHeap::<u32>::with_capacity(8);
+    /// heap.extend([3, 1, 4, 1, 5]);
+    ///
+    /// let vec = heap.take();
+    /// assert_eq!(vec, &[1, 1, 3, 4, 5][..]);
+    /// assert_eq!(vec.capacity(), 8);
+    ///
+    /// assert!(heap.is_empty());
+    /// assert_eq!(heap.capacity(), 0);
+    /// ```
+    pub fn take(&mut self) -> Vec<T, AllocStorage<ArrayLayout<T>>, I> {
+        core::mem::take(self).into_sorted_vec()
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(docs_rs, doc(cfg(feature = "alloc")))]
+impl<T: Ord, const D: usize, I: Capacity> Default
+    for DaryHeap<T, AllocStorage<ArrayLayout<T>>, I, D>
+{
+    /// Constructs a new, empty `AllocHeap<T, I>` with a capacity of zero,
+    /// without allocating.
+    ///
+    /// Since the capacity of an `AllocHeap` is fixed, any attempt to push
+    /// an item will fail. This is mostly useful for satisfying trait bounds,
+    /// e.g. in `#[derive(Default)]`; the heap should be replaced with one
+    /// constructed by [`with_capacity`](BinaryHeap::with_capacity) before use.
+    fn default() -> Self {
+        Self::with_capacity(I::from_usize(0))
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(docs_rs, doc(cfg(feature = "alloc")))]
+impl<T: Clone + Ord, const D: usize, I: Capacity> Clone
+    for DaryHeap<T, AllocStorage<ArrayLayout<T>>, I, D>
+{
+    fn clone(&self) -> Self {
+        DaryHeap { a: self.a.clone() }
+    }
+}
+
+impl<T: Ord, const D: usize, I: Capacity, const C: usize> DaryHeap<T, [MaybeUninit<T>; C], I, D> {
+    /// The number of elements a heap backed by an inline array can hold,
+    /// always equal to [`capacity()`](BinaryHeap::capacity).
+    ///
+    /// Unlike the method, this can be used in `const` contexts.
+    ///
+    /// # Examples
+    /// ```
+    /// use coca::collections::InlineHeap;
+    ///
+    /// type Heap = InlineHeap<u32, 16>;
+    /// const _: () = assert!(Heap::CAPACITY >= 4);
+    /// assert_eq!(Heap::new().capacity(), Heap::CAPACITY);
+    /// ```
+    pub const CAPACITY: usize = C;
+
+    /// Constructs a new, empty `BinaryHeap` backed by an inline array.
+    ///
+    /// # Panics
+    /// Panics if `C` cannot be represented as a value of type `I`.
+    ///
+    /// # Examples
+    /// ```
+    /// let heap = coca::collections::InlineHeap::<char, 4>::new();
+    /// assert_eq!(heap.capacity(), 4);
+    /// assert!(heap.is_empty());
+    /// ```
+    pub fn new() -> Self {
+        Self::from_vec_unchecked(Vec::new())
+    }
+
+    /// Constructs a new, full `BinaryHeap` backed by an inline array, from
+    /// the results of calling `f` with each index in ascending order.
+    ///
+    /// The heap property is established afterwards, in O(n) time. If `f`
+    /// panics, all items produced up to that point are dropped.
+    ///
+    /// # Panics
+    /// Panics if `C` cannot be represented as a value of type `I`.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut heap = coca::collections::InlineHeap::<usize, 4>::from_fn(|i| i * i);
+    /// assert!(heap.is_full());
+    /// assert_eq!(heap.pop(), Some(9));
+    /// assert_eq!(heap.pop(), Some(4));
+    /// ```
+    pub fn from_fn<F: FnMut(usize) -> T>(f: F) -> Self {
+        Self::from(Vec::from_fn(f))
+    }
+
+    /// Consumes the binary heap and returns its items as an array, in
+    /// arbitrary order, if it is full. Otherwise, returns the heap unchanged.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut heap = coca::collections::InlineHeap::<u32, 4>::new();
+    /// heap.extend([3, 1, 4]);
+    ///
+    /// let mut heap = heap.try_into_array().unwrap_err();
+    /// heap.push(1);
+    ///
+    /// let mut array = heap.try_into_array().unwrap();
+    /// array.sort_unstable();
+    /// assert_eq!(array, [1, 1, 3, 4]);
+    /// ```
+    pub fn try_into_array(self) -> Result<[T; C], Self> {
+        if self.len() < C {
+            return Err(self);
+        }
+
+        let vec = core::mem::ManuallyDrop::new(self.a);
+        // SAFETY: the vector is full, so all `C` elements are initialized,
+        // and it won't drop them since it's wrapped in `ManuallyDrop`.
+        Ok(unsafe { vec.as_ptr().cast::<[T; C]>().read() })
+    }
+}
+
+#[cfg(feature = "unstable")]
+macro_rules! impl_new_const {
+    ($($idx:ty),*) => {$(
+        #[cfg_attr(docs_rs, doc(cfg(feature = "unstable")))]
+        impl<T: Ord, const D: usize, const C: usize> DaryHeap<T, [MaybeUninit<T>; C], $idx, D> {
+            /// Constructs a new, empty `BinaryHeap` backed by an inline array,
+            /// in a `const` context.
+            ///
+            /// Unlike [`new`](BinaryHeap::new), this is a `const fn`, so it
+            /// can be used to initialize a `const` or `static` item. Since
+            /// trait methods can't be called in `const` contexts, it is only
+            /// available with the built-in index types. It also requires the
+            /// `unstable` feature, as trait bounds on `const fn`s are not
+            /// supported by the minimum supported Rust version.
+            ///
+            /// # Panics
+            /// Panics if `C` cannot be represented as a value of the index
+            /// type. In a `const` context, this is a compile-time error.
+            ///
+            /// # Examples
+            /// ```
+            /// use coca::collections::InlineHeap;
+            ///
+            /// const EMPTY: InlineHeap<u32, 16> = InlineHeap::<u32, 16>::new_const();
+            /// let mut heap = EMPTY;
+            /// heap.push(1);
+            ///
+            /// assert_eq!(heap.capacity(), 16);
+            /// assert_eq!(EMPTY.len(), 0);
+            /// ```
+            pub const fn new_const() -> Self {
+                #[allow(clippy::let_unit_value)]
+                let () = Self::ARITY_IS_VALID;
+                DaryHeap {
+                    a: Vec::<T, [MaybeUninit<T>; C], $idx>::new_const(),
+                }
+            }
+        }
+    )*};
+}
+
+#[cfg(feature = "unstable")]
+impl_new_const!(u8, u16, u32, u64, usize);
+
+impl<T: Ord, const D: usize, I: Capacity, const C: usize> Default
+    for DaryHeap<T, [MaybeUninit<T>; C], I, D>
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Clone + Ord, const D: usize, I: Capacity, const C: usize> Clone
+    for DaryHeap<T, [MaybeUninit<T>; C], I, D>
+{
+    fn clone(&self) -> Self {
+        DaryHeap { a: self.a.clone() }
+    }
+}
+
+/// Extension methods for collecting iterators into binary heaps.
+///
+/// This is implemented for all iterators, and exported from the crate root.
+pub trait IteratorExt: Iterator + Sized {
+    /// Transforms the iterator into a binary heap of type `H`, e.g. an
+    /// [`InlineHeap`](crate::collections::InlineHeap).
+    ///
+    /// This is equivalent to [`Iterator::collect`], but lets the storage
+    /// type be inferred from a partially specified heap type.
+    ///
+    /// # Panics
+    /// Panics if the iterator yields more items than the heap can hold.
+    ///
+    /// # Examples
+    /// ```
+    /// use coca::IteratorExt;
+    /// use coca::collections::InlineHeap;
+    ///
+    /// let mut heap = (1..=10).collect_heap::<InlineHeap<_, 16>>();
+    /// assert_eq!(heap.len(), 10);
+    /// assert_eq!(heap.pop(), Some(10));
+    /// ```
+    #[inline]
+    #[track_caller]
+    fn collect_heap<H: FromIterator<Self::Item>>(self) -> H {
+        self.collect()
+    }
+
+    /// Transforms the iterator into a binary heap backed by memory allocated
+    /// from the given arena, with capacity for exactly the iterator's
+    /// remaining items.
+    ///
+    /// # Panics
+    /// Panics if the remaining space in the arena is insufficient, or if the
+    /// iterator's length cannot be represented by the index type `I`. See
+    /// [`try_collect_heap_in`](IteratorExt::try_collect_heap_in) for a
+    /// checked version.
+    ///
+    /// # Examples
+    /// ```
+    /// use coca::IteratorExt;
+    /// use coca::arena::Arena;
+    /// use core::mem::MaybeUninit;
+    ///
+    /// let mut backing_region = [MaybeUninit::uninit(); 256];
+    /// let mut arena = Arena::from(&mut backing_region[..]);
+    ///
+    /// let mut heap = [3, 1, 4, 1, 5].iter().copied().collect_heap_in::<u8>(&mut arena);
+    /// assert!(heap.is_full());
+    /// assert_eq!(heap.pop(), Some(5));
+    /// ```
+    #[track_caller]
+    fn collect_heap_in<'a, I: Capacity>(self, arena: &mut Arena<'a>) -> ArenaHeap<'a, Self::Item, I>
+    where
+        Self: ExactSizeIterator,
+        Self::Item: Ord,
+    {
+        self.try_collect_heap_in(arena)
+            .expect("unexpected allocation failure in `collect_heap_in`")
+    }
+
+    /// Transforms the iterator into a binary heap backed by memory allocated
+    /// from the given arena, with capacity for exactly the iterator's
+    /// remaining items.
+    ///
+    /// Returns [`None`] if the remaining space in the arena is insufficient.
+    ///
+    /// # Panics
+    /// Panics if the iterator's length cannot be represented by the index
+    /// type `I`.
+    fn try_collect_heap_in<'a, I: Capacity>(
+        self,
+        arena: &mut Arena<'a>,
+    ) -> Option<ArenaHeap<'a, Self::Item, I>>
+    where
+        Self: ExactSizeIterator,
+        Self::Item: Ord,
+    {
+        let capacity = self.len();
+        arena.try_collect_with_capacity(self, capacity)
+    }
+
+    /// Transforms the iterator into a binary heap backed by a heap-allocated
+    /// slice, with capacity for exactly the iterator's remaining items.
+    ///
+    /// # Panics
+    /// Panics if the iterator's length cannot be represented by the index
+    /// type `I`, or if the iterator yields more items than it reported.
+    ///
+    /// # Examples
+    /// ```
+    /// use coca::IteratorExt;
+    ///
+    /// let mut heap = (1..11).map(|x| x % 4).collect_alloc_heap::<usize>();
+    /// assert_eq!(heap.capacity(), 10);
+    /// assert_eq!(heap.pop(), Some(3));
+    /// ```
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(docs_rs, doc(cfg(feature = "alloc")))]
+    #[track_caller]
+    fn collect_alloc_heap<I: Capacity>(self) -> crate::collections::AllocHeap<Self::Item, I>
+    where
+        Self: ExactSizeIterator,
+        Self::Item: Ord,
+    {
+        let capacity = self.len();
+        if capacity > I::MAX_REPRESENTABLE {
+            buffer_too_large_for_index_type::<I>(capacity);
+        }
+
+        crate::collections::AllocHeap::from_iter_in(I::from_usize(capacity), self)
+    }
+}
+
+impl<It: Iterator> IteratorExt for It {}
+
+/// Declares a [`SliceHeap`](crate::collections::SliceHeap) backed by an
+/// array of `MaybeUninit` on the stack.
+///
+/// Expands to two `let` statements in the calling scope: one binding a hidden
+/// backing array of the given length, and one binding the heap itself with
+/// the given name. The heap is declared as mutable, and the index type can
+/// optionally be specified after the item type.
+///
+/// Prefer [`InlineHeap`](crate::collections::InlineHeap) when the heap is
+/// to be moved or returned; this macro is for cases where the storage should
+/// be borrowed, e.g. so that its lifetime is tied to the current scope.
+///
+/// # Examples
+/// ```
+/// # #![forbid(unsafe_code)]
+/// coca::heap_on_stack!(heap: u32; 8);
+/// heap.extend([3, 1, 4, 1, 5]);
+/// assert_eq!(heap.capacity(), 8);
+/// assert_eq!(heap.peek(), Some(&5));
+///
+/// coca::heap_on_stack!(names: &str, u8; 4);
+/// names.push("coca");
+/// assert_eq!(names.len_index(), 1u8);
+/// ```
+#[macro_export]
+macro_rules! heap_on_stack {
+    ($name:ident : $t:ty ; $n:expr) => {
+        $crate::heap_on_stack!($name : $t, usize ; $n)
+    };
+    ($name:ident : $t:ty, $idx:ty ; $n:expr) => {
+        let mut backing_region = $crate::storage::uninit_inline_storage::<$t, { $n }>();
+        #[allow(unused_mut)]
+        let mut $name =
+            $crate::collections::SliceHeap::<$t, $idx>::from(&mut backing_region[..]);
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::collections::SliceHeap;
+
+    #[test]
+    fn tree_traversal_utilities() {
+        assert_eq!(first_child::<2>(0), 1);
+        assert_eq!(parent::<2>(1), 0);
+        assert_eq!(parent::<2>(2), 0);
+
+        fn check<const D: usize>() {
+            for i in 0..=1000 {
+                let first = first_child::<D>(i);
+                for c in first..first + D {
+                    assert_eq!(parent::<D>(c), i);
+                    for cc in first_child::<D>(c)..first_child::<D>(c) + D {
+                        assert_eq!(parent::<D>(parent::<D>(cc)), i);
+                    }
+                }
+                assert_eq!(first_child::<D>(i + 1), first + D);
+            }
+
+            assert_eq!(first_leaf::<D>(0), 0);
+            assert_eq!(first_leaf::<D>(1), 0);
+            for len in 2..=1000 {
+                let leaf = first_leaf::<D>(len);
+                assert!(first_child::<D>(leaf) >= len);
+                assert!(first_child::<D>(leaf - 1) < len);
+            }
+        }
+
+        check::<2>();
+        check::<3>();
+        check::<4>();
+        check::<7>();
+    }
+
+    #[test]
+    fn push_and_pop_randomized_inputs() {
+        use rand::{rngs::SmallRng, RngCore, SeedableRng};
+
+        let mut backing_region = [core::mem::MaybeUninit::<u32>::uninit(); 32];
+        let mut heap = crate::collections::SliceHeap::<_>::from(&mut backing_region[..]);
+
+        let mut rng = SmallRng::from_seed(crate::test_utils::RNG_SEED);
+
+        let mut newest = 0;
+        for _ in 0..32 {
+            newest = rng.next_u32();
+            heap.push(newest);
+        }
+
+        let mut prev = u32::max_value();
+        for _ in 0..1000 {
+            let x = heap.pop().unwrap();
+            assert!(x <= prev || x == newest);
+            prev = x;
+
+            newest = rng.next_u32();
+            heap.push(newest);
+        }
+    }
+
+    #[test]
+    fn dary_heaps_agree_with_binary_heap() {
+        use crate::collections::InlineHeap;
+        use rand::{rngs::SmallRng, Rng, SeedableRng};
+
+        fn run<const D: usize>(seed: u64) {
+            let mut rng = SmallRng::seed_from_u64(seed);
+            let mut reference = InlineHeap::<u16, 64>::new();
+            let mut dary = DaryHeap::<u16, [MaybeUninit<u16>; 64], usize, D>::new();
+
+            for _ in 0..2000 {
+                match rng.gen_range(0..8) {
+                    0..=3 if !reference.is_full() => {
+                        let x = rng.gen_range(0..100);
+                        reference.push(x);
+                        dary.push(x);
+                    }
+                    4 if !reference.is_empty() => {
+                        // equal values are interchangeable, so updating any
+                        // occurrence in the reference heap is equivalent
+                        let idx = rng.gen_range(0..dary.len());
+                        let old = dary.iter().nth(idx).copied().unwrap();
+                        let ref_idx = reference.iter().position(|&x| x == old).unwrap();
+
+                        let new = rng.gen_range(0..100);
+                        assert!(dary.update_at(idx, |x| *x = new));
+                        assert!(reference.update_at(ref_idx, |x| *x = new));
+                    }
+                    5 => {
+                        let k = rng.gen_range(0..8);
+                        assert_eq!(dary.kth_largest(k), reference.kth_largest(k));
+                    }
+                    6 => assert!(dary.iter_sorted().eq(reference.iter_sorted())),
+                    _ => assert_eq!(dary.pop(), reference.pop()),
+                }
+
+                assert!(dary.is_valid_heap());
+                assert_eq!(dary.peek(), reference.peek());
+                assert_eq!(dary.len(), reference.len());
+            }
+
+            assert!(dary
+                .into_sorted_vec()
+                .iter()
+                .eq(reference.into_sorted_vec().iter()));
+        }
+
+        for seed in 0..8 {
+            run::<2>(seed);
+            run::<3>(seed);
+            run::<4>(seed);
+            run::<5>(seed);
+        }
+    }
+
+    #[test]
+    fn retain_into_sorted_vec_keeps_none_some_or_all() {
+        use crate::collections::InlineHeap;
+
+        let mut heap = InlineHeap::<u32, 8, u8>::new();
+        heap.extend([3, 1, 4, 1, 5, 9, 2, 6]);
+
+        let none = heap.clone().retain_into_sorted_vec(|_| false);
+        assert!(none.is_empty());
+        assert_eq!(none.capacity(), 8);
+
+        let some = heap.clone().retain_into_sorted_vec(|&x| x % 2 == 1);
+        assert_eq!(some, &[1, 1, 3, 5, 9][..]);
+
+        let all = heap.retain_into_sorted_vec(|_| true);
+        assert_eq!(all, &[1, 1, 2, 3, 4, 5, 6, 9][..]);
+    }
+
+    #[test]
+    fn bulk_mut_restores_heap_property_on_drop() {
+        use crate::collections::{InlineHeap, InlineVec};
+        use rand::{rngs::SmallRng, Rng, SeedableRng};
+
+        let mut rng = SmallRng::from_seed(crate::test_utils::RNG_SEED);
+        let mut heap = InlineHeap::<u32, 32>::new();
+        heap.extend((0..16).map(|_| rng.gen_range(0..1000)));
+
+        for _ in 0..100 {
+            {
+                let mut vec = heap.bulk_mut();
+                for _ in 0..rng.gen_range(1..8) {
+                    match rng.gen_range(0..5) {
+                        0 if !vec.is_full() => vec.push(rng.gen_range(0..1000)),
+                        1 if !vec.is_empty() => {
+                            let idx = rng.gen_range(0..vec.len());
+                            vec.swap_remove(idx);
+                        }
+                        2 if !vec.is_empty() => {
+                            let idx = rng.gen_range(0..vec.len());
+                            vec[idx] = rng.gen_range(0..1000);
+                        }
+                        3 => vec.sort_unstable(),
+                        _ => vec.as_mut_slice().reverse(),
+                    }
+                }
+            }
+
+            assert!(heap.is_valid_heap());
+        }
+
+        let mut expected: InlineVec<u32, 32> = heap.iter().copied().collect();
+        expected.sort_unstable();
+        assert_eq!(heap.into_sorted_vec(), expected);
+    }
+
+    #[test]
+    #[cfg(feature = "unstable")]
+    fn new_const_in_static_and_const_items() {
+        use crate::collections::InlineHeap;
+
+        static EMPTY_STATIC: InlineHeap<u32, 16> = InlineHeap::<u32, 16>::new_const();
+        const EMPTY_CONST: QuaternaryHeap<u32, InlineStorage<u32, 16>, u8> =
+            QuaternaryHeap::<u32, InlineStorage<u32, 16>, u8>::new_const();
+
+        assert_eq!(EMPTY_STATIC.len(), 0);
+        assert_eq!(EMPTY_STATIC.capacity(), 16);
+
+        let mut heap = EMPTY_CONST;
+        assert!(heap.is_empty());
+        assert_eq!(heap.capacity(), 16);
+        heap.extend([3, 1, 4, 1, 5]);
+        assert_eq!(heap.pop(), Some(5));
+        assert!(EMPTY_CONST.is_empty());
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn from_iter_in_below_and_at_capacity() {
+        use crate::collections::AllocHeap;
+
+        let heap = AllocHeap::<u32, u8>::from_iter_in(16, core::iter::empty());
+        assert!(heap.is_empty());
+        assert_eq!(heap.capacity(), 16);
+
+        let heap = AllocHeap::<u32, u8>::from_iter_in(16, (0..10).map(|x| x * 7 % 10));
+        assert_eq!(heap.capacity(), 16);
+        assert!(heap.is_valid_heap());
+        assert_eq!(heap.into_sorted_vec(), &[0, 1, 2, 3, 4, 5, 6, 7, 8, 9][..]);
+
+        let heap = AllocHeap::<u32, u8>::from_iter_in(10, (0..10).rev());
+        assert_eq!(heap.len(), 10);
+        assert_eq!(heap.peek(), Some(&9));
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    #[should_panic(expected = "iterator should yield no more than `capacity` (is 4) items")]
+    fn from_iter_in_above_capacity() {
+        let _ = crate::collections::AllocHeap::<u32>::from_iter_in(4, 0..5);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    #[should_panic(expected = "iterator should yield no more than `capacity` (is 4) items")]
+    fn from_iter_in_above_capacity_without_size_hint() {
+        let _ = crate::collections::AllocHeap::<u32>::from_iter_in(4, (0..5).filter(|_| true));
+    }
+
+    #[test]
+    #[should_panic(expected = "iterator should yield no more than `capacity` (is 4) items")]
+    fn from_iter_above_capacity() {
+        struct Loud(core::ops::Range<u32>);
+        impl Iterator for Loud {
+            type Item = u32;
+            fn next(&mut self) -> Option<u32> {
+                panic!("no items should be consumed")
+            }
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                self.0.size_hint()
+            }
+        }
+
+        let _: crate::collections::InlineHeap<u32, 4> = Loud(0..5).collect();
+    }
+
+    #[test]
+    #[should_panic(expected = "iterator should yield no more than `capacity` (is 4) items")]
+    fn from_iter_above_capacity_without_size_hint() {
+        let _: crate::collections::InlineHeap<u32, 4> = (0..5).filter(|_| true).collect();
+    }
+
+    #[test]
+    fn from_iter_with_loose_upper_bound() {
+        let heap: crate::collections::InlineHeap<u32, 4> =
+            (0..100).filter(|x| x % 25 == 0).collect();
+        assert_eq!(heap.into_sorted_vec(), [0, 25, 50, 75]);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn take_leaves_heap_empty() {
+        use crate::collections::AllocHeap;
+
+        let mut heap = AllocHeap::<u32, u16>::with_capacity(16);
+        heap.extend([3, 1, 4, 1, 5, 9, 2, 6]);
+
+        let vec = heap.take();
+        assert_eq!(vec, &[1, 1, 2, 3, 4, 5, 6, 9][..]);
+        assert_eq!(vec.capacity(), 16);
+        assert!(heap.is_empty());
+        assert_eq!(heap.capacity(), 0);
+        assert_eq!(heap.try_push(7), Err(CapacityError::with_value(7)));
+
+        let mut empty = AllocHeap::<u32, u16>::default();
+        assert!(empty.take().is_empty());
+        assert_eq!(empty.capacity(), 0);
+    }
+
+    #[test]
+    fn reset_drops_items_and_keeps_capacity() {
+        use crate::test_utils::DropCounter;
+
+        let drop_count = DropCounter::new();
+        let mut heap = crate::collections::InlineHeap::<_, 8>::new();
+        for i in 0..5 {
+            heap.push(drop_count.new_droppable(i));
+        }
+
+        heap.reset();
+        assert!(heap.is_empty());
+        assert_eq!(drop_count.dropped(), 5);
+
+        for i in 0..8 {
+            heap.push(drop_count.new_droppable(i));
+        }
+        assert_eq!(heap.peek().map(|d| d.value), Some(7));
+    }
+
+    #[test]
+    fn from_iter_saturating_drops_excess_items_once() {
+        use crate::test_utils::DropCounter;
+
+        let kept = DropCounter::new();
+        let discarded = DropCounter::new();
+        let items = (0..4)
+            .map(|i| kept.new_droppable(i))
+            .chain((4..10).map(|i| discarded.new_droppable(i)));
+
+        let mut backing_region = [
+            MaybeUninit::uninit(),
+            MaybeUninit::uninit(),
+            MaybeUninit::uninit(),
+            MaybeUninit::uninit(),
+        ];
+        let mut heap = SliceHeap::<_, u8>::from_iter_saturating(&mut backing_region[..], items);
+
+        assert_eq!(heap.len(), heap.capacity());
+        assert!(heap.is_valid_heap());
+        assert_eq!(kept.dropped(), 0);
+        assert_eq!(discarded.dropped(), 6);
+
+        assert_eq!(heap.pop().map(|x| x.value), Some(3));
+        assert_eq!(kept.dropped(), 1);
+        drop(heap);
+        assert_eq!(kept.dropped(), 4);
+        assert_eq!(discarded.dropped(), 6);
+
+        let mut backing_region = [MaybeUninit::<u32>::uninit(); 4];
+        let heap = SliceHeap::<_, u8>::from_iter_saturating(&mut backing_region[..], [2, 1]);
+        assert_eq!(heap.len(), 2);
+        assert_eq!(heap.peek(), Some(&2));
+    }
+
+    #[test]
+    fn merge_sorted_matches_sorted_concatenation() {
+        use crate::collections::{InlineHeap, InlineVec};
+        use rand::{rngs::SmallRng, Rng, SeedableRng};
+
+        let mut rng = SmallRng::from_seed(crate::test_utils::RNG_SEED);
+        for _ in 0..50 {
+            let mut a = InlineHeap::<u32, 16>::new();
+            let mut b = InlineHeap::<u32, 16>::new();
+            let mut c = InlineHeap::<u32, 16>::new();
+            let mut expected = InlineVec::<u32, 48>::new();
+            for heap in [&mut a, &mut b, &mut c] {
+                for _ in 0..rng.gen_range(0..=16) {
+                    let x = rng.gen_range(0..50);
+                    heap.push(x);
+                    expected.push(x);
+                }
+            }
+            expected.sort_unstable();
+
+            let mut heaps = [&mut a, &mut b, &mut c];
+            let merged = merge_sorted(&mut heaps);
+            assert_eq!(merged.len(), expected.len());
+            assert!(merged.eq(expected.iter().rev().copied()));
+            assert!(a.is_empty() && b.is_empty() && c.is_empty());
+        }
+    }
+
+    #[test]
+    fn merge_sorted_leaves_remaining_elements() {
+        use crate::collections::InlineHeap;
+
+        let mut a = InlineHeap::<u32, 4>::new();
+        let mut b = InlineHeap::<u32, 4>::new();
+        a.extend([1, 4]);
+        b.extend([2, 3]);
+
+        let mut heaps = [&mut a, &mut b];
+        let mut merged = merge_sorted(&mut heaps);
+        assert_eq!(merged.next(), Some(4));
+        assert_eq!(merged.next(), Some(3));
+        drop(merged);
+
+        assert_eq!(a.len() + b.len(), 2);
+        assert_eq!(a.peek(), Some(&1));
+        assert_eq!(b.peek(), Some(&2));
+
+        let mut empty: [&mut InlineHeap<u32, 4>; 0] = [];
+        assert_eq!(merge_sorted(&mut empty).next(), None);
+    }
+
+    #[test]
+    fn assert_capacity_satisfied() {
+        let mut backing_region = [MaybeUninit::<u32>::uninit(); 8];
+        let mut heap = SliceHeap::<_, u8>::from(&mut backing_region[..]);
+        heap.assert_capacity(0);
+        heap.assert_capacity(8);
+
+        heap.extend([1, 2, 3, 4, 5, 6, 7, 8]);
+        heap.assert_capacity(8);
+    }
+
+    #[test]
+    #[should_panic(expected = "binary heap capacity (is 8) should be >= 9")]
+    fn assert_capacity_unsatisfied() {
+        let mut backing_region = [MaybeUninit::<u32>::uninit(); 8];
+        let heap = SliceHeap::<_, u8>::from(&mut backing_region[..]);
+        heap.assert_capacity(9);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn with_min_capacity_grows_only_when_necessary() {
+        use crate::collections::AllocHeap;
+
+        let mut heap = AllocHeap::<u32, u16>::with_capacity(4);
+        heap.extend([3, 1, 4]);
+
+        let mut heap = heap.with_min_capacity(2);
+        assert_eq!(heap.capacity(), 4);
+
+        heap = heap.with_min_capacity(10);
+        heap.assert_capacity(10);
+        assert_eq!(heap.capacity(), 10);
+        assert!(heap.is_valid_heap());
+        assert_eq!(heap.into_sorted_vec(), &[1, 3, 4][..]);
+    }
+
+    #[test]
+    fn with_capacity_in_shares_one_arena() {
+        use crate::collections::ArenaHeap;
+
+        let mut backing_region = [MaybeUninit::uninit(); 256];
+        let mut arena = Arena::from(&mut backing_region[..]);
+
+        let mut a = ArenaHeap::<u64, u8>::with_capacity_in(8, &mut arena);
+        let mut b = QuaternaryHeap::<u64, _, u8>::try_with_capacity_in(8, &mut arena).unwrap();
+        assert_eq!(a.capacity(), 8);
+        assert_eq!(b.capacity(), 8);
+
+        a.extend([3, 1, 4, 1, 5]);
+        b.extend([9, 2, 6]);
+        assert_eq!(a.peek(), Some(&5));
+        assert_eq!(b.peek(), Some(&9));
+
+        // the remaining space fits at most 16 more elements
+        let mut remaining = 0;
+        while let Some(heap) = ArenaHeap::<u64, u8>::try_with_capacity_in(1, &mut arena) {
+            assert_eq!(heap.capacity(), 1);
+            remaining += 1;
+            assert!(remaining <= 16);
+        }
+
+        assert!(ArenaHeap::<u64, u8>::try_with_capacity_in(1, &mut arena).is_none());
+        assert_eq!(b.pop(), Some(9));
+        assert!(a.into_sorted_vec().iter().eq(&[1, 1, 3, 4, 5]));
+    }
+
+    #[test]
+    #[should_panic(expected = "unexpected allocation failure in `with_capacity_in`")]
+    fn with_capacity_in_panics_when_arena_is_exhausted() {
+        use crate::collections::ArenaHeap;
+
+        let mut backing_region = [MaybeUninit::uninit(); 64];
+        let mut arena = Arena::from(&mut backing_region[..]);
+        let _heap = ArenaHeap::<u64, u8>::with_capacity_in(16, &mut arena);
+    }
+
+    #[test]
+    fn default_heaps_have_zero_capacity() {
+        let mut heap = SliceHeap::<u32, u8>::default();
+        assert_eq!(heap.capacity(), 0);
+        assert!(heap.is_empty() && heap.is_full());
+        assert_eq!(heap.try_push(1), Err(CapacityError::with_value(1)));
+
+        #[cfg(feature = "alloc")]
+        {
+            #[derive(Default)]
+            struct Holder {
+                heap: crate::collections::AllocHeap<u32>,
+            }
+
+            let mut holder = Holder::default();
+            assert_eq!(holder.heap.capacity(), 0);
+            assert_eq!(holder.heap.try_push(1), Err(CapacityError::with_value(1)));
+
+            holder.heap = crate::collections::AllocHeap::with_capacity(4);
+            holder.heap.push(1);
+            assert_eq!(holder.heap.peek(), Some(&1));
+        }
+    }
+
+    #[test]
+    fn sift_up_and_down_fix_single_violations() {
+        use crate::collections::InlineHeap;
+        use rand::{rngs::SmallRng, Rng, SeedableRng};
+
+        let mut rng = SmallRng::from_seed(crate::test_utils::RNG_SEED);
+        let mut heap = InlineHeap::<u32, 64>::new();
+        heap.extend((0..64).map(|_| rng.gen_range(100..200)));
+
+        for _ in 0..200 {
+            let idx = rng.gen_range(0..heap.len());
+
+            // an increased element may only need to move up
+            heap.a[idx] += rng.gen_range(0..100);
+            heap.sift_up(idx);
+            assert!(heap.is_valid_heap());
+
+            // a decreased element may only need to move down
+            let idx = rng.gen_range(0..heap.len());
+            heap.a[idx] = heap.a[idx].saturating_sub(rng.gen_range(0..100));
+            heap.sift_down(idx);
+            assert!(heap.is_valid_heap());
+
+            heap.pop();
+            heap.push(rng.gen_range(100..200));
+        }
+
+        let root = heap.a[0];
+        heap.a[63] = root + 1;
+        heap.sift_up(63);
+        assert_eq!(heap.peek(), Some(&(root + 1)));
+
+        heap.a[0] = 0;
+        heap.sift_down(0);
+        assert!(heap.is_valid_heap());
+        assert_eq!(heap.iter_sorted().last(), Some(&0));
+    }
+
+    #[test]
+    #[should_panic(expected = "sift index (is 3) should be < len (is 3)")]
+    fn sift_out_of_bounds() {
+        let mut heap = crate::collections::InlineHeap::<u32, 8>::new();
+        heap.extend([1, 2, 3]);
+        heap.sift_down(3);
+    }
+
+    #[test]
+    fn from_full_slice_heapifies_in_place() {
+        let mut data = [3, 1, 4, 1, 5, 9];
+        let mut heap = unsafe { SliceHeap::<_, u8>::from_full_slice(&mut data[..]) };
+        assert_eq!(heap.len(), 6);
+        assert!(heap.is_full());
+        assert!(heap.is_valid_heap());
+
+        let mut popped = [0; 6];
+        for slot in popped.iter_mut() {
+            *slot = heap.pop().unwrap();
+        }
+        assert_eq!(popped, [9, 5, 4, 3, 1, 1]);
+        assert!(heap.is_empty());
+
+        heap.push(2);
+        assert_eq!(heap.peek(), Some(&2));
+    }
+
+    #[test]
+    fn eq_and_hash_ignore_insertion_order() {
+        use crate::collections::{InlineHeap, InlineVec};
+
+        fn hash_of<T: Hash>(x: &T) -> u64 {
+            let mut hasher = rustc_hash::FxHasher::default();
+            x.hash(&mut hasher);
+            hasher.finish()
+        }
+
+        let mut a = InlineHeap::<u32, 8>::new();
+        a.extend([3, 1, 4, 1, 5, 9, 2, 6]);
+
+        let mut b = InlineHeap::<u32, 8>::new();
+        for x in [6, 2, 9, 5, 1, 4, 1, 3] {
+            b.push(x);
+        }
+
+        assert_ne!(a.a, b.a);
+        assert_eq!(a, b);
+        assert_eq!(hash_of(&a), hash_of(&b));
+
+        b.pop();
+        b.push(8);
+        assert_ne!(a, b);
+        assert_ne!(hash_of(&a), hash_of(&b));
+
+        let before = a.a.clone();
+        let sorted: InlineVec<u32, 8> = a.iter_sorted().copied().collect();
+        assert_eq!(sorted, [9, 6, 5, 4, 3, 2, 1, 1]);
+        assert_eq!(a.a, before);
+
+        let mut backing = [MaybeUninit::<u32>::uninit(); 8];
+        let mut c = crate::collections::SliceHeap::<u32>::from(&mut backing[..]);
+        c.extend([1, 1, 2, 3, 4, 5, 6, 9]);
+        assert_eq!(c, a);
+        assert_eq!(hash_of(&c), hash_of(&a));
+    }
+
+    #[test]
+    #[cfg(all(feature = "alloc", target_pointer_width = "64"))]
+    #[cfg_attr(miri, ignore)]
+    fn try_with_capacity_reports_allocation_failure() {
+        // Layout is valid, but no allocator can hope to satisfy this request:
+        let capacity = (isize::MAX as usize) / 16;
+        let result = crate::collections::AllocHeap::<u64>::try_with_capacity(capacity);
+        assert!(matches!(result, Err(TryReserveError::AllocError { .. })));
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn shrinking_preserves_contents() {
+        let mut heap = crate::collections::AllocHeap::<u32>::with_capacity(64);
+        heap.extend((0..64).map(|x| (x * 37) % 64));
+
+        for _ in 0..32 {
+            heap.pop();
+        }
+
+        heap.shrink_to_fit();
+        assert_eq!(heap.capacity(), 32);
+        for expected in (0..32).rev() {
+            assert_eq!(heap.pop(), Some(expected));
+        }
+
+        heap.shrink_to_fit();
+        assert_eq!(heap.capacity(), 0);
+        assert!(heap.try_push(0).is_err());
+    }
+
+    #[test]
+    fn heap_validity_check() {
+        let mut heap = crate::collections::InlineHeap::<u32, 16>::new();
+        assert!(heap.is_valid_heap());
+
+        heap.extend(0..16);
+        assert!(heap.is_valid_heap());
+
+        heap.a.swap(0, 15);
+        assert!(!heap.is_valid_heap());
+
+        heap.a.swap(0, 15);
+        assert!(heap.is_valid_heap());
+        *heap.a.last_mut().unwrap() = 100;
+        assert!(!heap.is_valid_heap());
+    }
+
+    #[test]
+    fn update_at_randomized_inputs() {
+        use rand::{rngs::SmallRng, RngCore, SeedableRng};
+
+        let mut heap = crate::collections::InlineHeap::<u32, 64>::new();
+        let mut rng = SmallRng::from_seed(crate::test_utils::RNG_SEED);
+        heap.extend((0..64).map(|_| rng.next_u32() % 1000));
+
+        for _ in 0..1000 {
+            let idx = rng.next_u32() as usize % heap.len();
+            let new_value = rng.next_u32() % 1000;
+            assert!(heap.update_at(idx, |x| *x = new_value));
+            assert!(heap.is_valid_heap());
+        }
+
+        assert!(!heap.update_at(64, |_| unreachable!()));
+    }
+
+    #[test]
+    fn kth_largest_matches_sorted_reference() {
+        use crate::collections::binary_heap::QuaternaryHeap;
+        use rand::{rngs::SmallRng, RngCore, SeedableRng};
+
+        let mut heap = crate::collections::InlineHeap::<u32, 64>::new();
+        let mut heap4 =
+            QuaternaryHeap::<u32, [MaybeUninit<u32>; 64], u8>::from([MaybeUninit::uninit(); 64]);
+        let mut rng = SmallRng::from_seed(crate::test_utils::RNG_SEED);
+        for _ in 0..64 {
+            let x = rng.next_u32() % 100;
+            heap.push(x);
+            heap4.push(x);
+        }
+
+        let mut sorted = heap.a.clone();
+        sorted.sort_unstable_by(|a, b| b.cmp(a));
+
+        for k in 0..64 {
+            assert_eq!(heap.kth_largest(k), Some(&sorted[k]));
+            assert_eq!(heap4.kth_largest(k), Some(&sorted[k]));
+        }
+        assert_eq!(heap.kth_largest(63), sorted.last());
+        assert_eq!(heap.kth_largest(64), None);
+        assert_eq!(heap4.kth_largest(usize::MAX), None);
+    }
+
+    #[test]
+    fn kth_largest_in_matches_sorted_reference() {
+        use rand::{rngs::SmallRng, RngCore, SeedableRng};
+
+        let mut heap = crate::collections::InlineHeap::<u32, 64>::new();
+        let mut rng = SmallRng::from_seed(crate::test_utils::RNG_SEED);
+        heap.extend((0..64).map(|_| rng.next_u32() % 32));
+
+        let mut sorted = heap.a.clone();
+        sorted.sort_unstable_by(|a, b| b.cmp(a));
+
+        let mut scratch = [0; 64];
+        for k in 0..64 {
+            assert_eq!(heap.kth_largest_in(k, &mut scratch), Some(&sorted[k]));
+        }
+        assert_eq!(heap.kth_largest_in(64, &mut scratch), None);
+    }
+
+    #[test]
+    fn pop_n_stops_at_heap_or_destination_limits() {
+        use crate::collections::{InlineHeap, InlineVec};
+
+        let mut heap = InlineHeap::<u32, 8>::new();
+        heap.extend([3, 1, 4, 1, 5, 9, 2, 6]);
+        let mut dst = InlineVec::<u32, 16, u8>::new();
+
+        assert_eq!(heap.pop_n(0, &mut dst), 0);
+        assert!(dst.is_empty());
+        assert_eq!(heap.len(), 8);
+
+        assert_eq!(heap.pop_n(3, &mut dst), 3);
+        assert_eq!(dst, [9, 6, 5]);
+        assert!(heap.is_valid_heap());
+
+        assert_eq!(heap.pop_n(100, &mut dst), 5);
+        assert_eq!(dst, [9, 6, 5, 4, 3, 2, 1, 1]);
+        assert!(heap.is_empty());
+        assert_eq!(heap.pop_n(1, &mut dst), 0);
+
+        heap.extend(0..8);
+        let mut small = InlineVec::<u32, 2>::new();
+        assert_eq!(heap.pop_n(5, &mut small), 2);
+        assert_eq!(small, [7, 6]);
+        assert_eq!(heap.len(), 6);
+        assert_eq!(heap.peek(), Some(&5));
+    }
+
+    #[test]
+    fn try_into_array_moves_items_out_only_when_full() {
+        use crate::collections::InlineHeap;
+        use crate::test_utils::*;
+
+        let drop_count = DropCounter::new();
+        let mut heap = InlineHeap::<Droppable<u32>, 4, u8>::new();
+        for i in 0..3 {
+            heap.push(drop_count.new_droppable(i));
+        }
+
+        let mut heap = heap.try_into_array().unwrap_err();
+        assert_eq!(heap.len(), 3);
+        assert_eq!(drop_count.dropped(), 0);
+
+        heap.push(drop_count.new_droppable(3));
+        let array = heap.try_into_array().unwrap();
+        assert_eq!(drop_count.dropped(), 0);
+
+        let mut values = [0; 4];
+        for (v, d) in values.iter_mut().zip(&array) {
+            *v = d.value;
+        }
+        values.sort_unstable();
+        assert_eq!(values, [0, 1, 2, 3]);
+
+        drop(array);
+        assert_eq!(drop_count.dropped(), 4);
+    }
+
+    #[test]
+    fn push_all_uses_fewer_comparisons_than_repeated_push() {
+        use crate::collections::InlineHeap;
+        use core::cell::Cell;
+        use rand::{rngs::SmallRng, Rng, SeedableRng};
+
+        #[derive(Debug)]
+        struct Counted<'a>(u32, &'a Cell<usize>);
+        impl PartialEq for Counted<'_> {
+            fn eq(&self, other: &Self) -> bool {
+                self.cmp(other) == Ordering::Equal
+            }
+        }
+        impl Eq for Counted<'_> {}
+        impl PartialOrd for Counted<'_> {
+            fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+        impl Ord for Counted<'_> {
+            fn cmp(&self, other: &Self) -> Ordering {
+                self.1.set(self.1.get() + 1);
+                self.0.cmp(&other.0)
+            }
+        }
+
+        let mut rng = SmallRng::from_seed(crate::test_utils::RNG_SEED);
+        let mut values = [0u32; 256];
+        rng.fill(&mut values[..]);
+
+        let bulk_count = Cell::new(0);
+        let mut bulk = InlineHeap::<Counted, 256>::new();
+        bulk.push_all(values.iter().map(|&x| Counted(x, &bulk_count)));
+        let bulk_comparisons = bulk_count.get();
+        assert!(bulk.is_valid_heap());
+
+        let single_count = Cell::new(0);
+        let mut single = InlineHeap::<Counted, 256>::new();
+        for &x in &values {
+            single.push(Counted(x, &single_count));
+        }
+
+        let mut sorted = values;
+        sorted.sort_unstable();
+        let bulk_sorted = bulk.into_sorted_vec();
+        assert!(bulk_sorted.iter().map(|c| c.0).eq(sorted.iter().copied()));
+
+        // building a heap bottom-up takes fewer than 2n comparisons
+        assert!(bulk_comparisons < 2 * values.len());
+        assert!(bulk_comparisons < single_count.get());
+    }
+
+    #[test]
+    fn try_push_all_fills_to_capacity_on_overflow() {
+        let mut heap = crate::collections::InlineHeap::<u32, 8>::new();
+        heap.push(10);
+        assert!(heap.try_push_all(0..7).is_ok());
+        assert!(heap.is_full());
+        assert_eq!(heap.peek(), Some(&10));
+
+        heap.clear();
+        let rest = heap.try_push_all(0..10).unwrap_err();
+        assert!(rest.eq(8..10));
+        assert!(heap.is_valid_heap());
+        assert_eq!(heap.peek(), Some(&7));
+    }
+
+    #[test]
+    #[should_panic(expected = "binary heap is already at capacity")]
+    fn push_all_panics_on_overflow() {
+        let mut heap = crate::collections::InlineHeap::<u32, 4>::new();
+        heap.push_all(0..5);
+    }
+
+    #[test]
+    fn peek_mut_or_insert_with_empty_and_non_empty() {
+        let mut heap = crate::collections::InlineHeap::<u32, 4>::new();
+        {
+            let top = heap.peek_mut_or_insert_with(|| 7);
+            assert_eq!(*top, 7);
+        }
+        assert_eq!(heap.len(), 1);
+
+        heap.extend([3, 5]);
+        {
+            let mut top = heap.peek_mut_or_insert_with(|| unreachable!());
+            assert_eq!(*top, 7);
+            *top = 0;
+        }
+        assert_eq!(heap.len(), 3);
+        assert!(heap.is_valid_heap());
+        assert_eq!(heap.peek(), Some(&5));
+
+        let top = heap.peek_mut_or_insert_with(|| unreachable!());
+        assert_eq!(PeekMut::pop(top), 5);
+        assert_eq!(heap.into_sorted_vec(), &[0, 3][..]);
+    }
+
+    #[test]
+    #[should_panic(expected = "binary heap is already at capacity")]
+    fn peek_mut_or_insert_with_zero_capacity() {
+        let mut heap = crate::collections::InlineHeap::<u32, 0>::new();
+        let _ = heap.peek_mut_or_insert_with(|| 0);
+    }
+
+    #[test]
+    fn drain_where_all_none_or_some() {
+        use crate::collections::{InlineHeap, InlineVec};
+        use crate::test_utils::*;
+
+        let mut heap = InlineHeap::<u32, 16>::new();
+        heap.extend(0..16);
+        assert_eq!(heap.drain_where(|_| false).count(), 0);
+        assert_eq!(heap.len(), 16);
+        assert!(heap.is_valid_heap());
+
+        let mut drained: InlineVec<u32, 16> = heap.drain_where(|x| x % 3 == 0).collect();
+        drained.sort_unstable();
+        assert_eq!(drained, [0, 3, 6, 9, 12, 15]);
+        assert!(heap.is_valid_heap());
+        assert!(heap.iter().all(|x| x % 3 != 0));
+        assert_eq!(heap.len(), 10);
+
+        assert_eq!(heap.drain_where(|_| true).count(), 10);
+        assert!(heap.is_empty());
+
+        // dropping early still removes all matches and restores the heap
+        let drop_count = DropCounter::new();
+        let mut heap = InlineHeap::<Droppable<u32>, 16>::new();
+        for i in 0..16 {
+            heap.push(drop_count.new_droppable(i));
+        }
+
+        let mut iter = heap.drain_where(|d| d.value >= 8);
+        let first = iter.next().unwrap();
+        assert!(first.value >= 8);
+        drop(iter);
+        assert_eq!(drop_count.dropped(), 7);
+        drop(first);
+        assert_eq!(drop_count.dropped(), 8);
+
+        assert_eq!(heap.len(), 8);
+        assert!(heap.is_valid_heap());
+        assert_eq!(heap.peek().map(|d| d.value), Some(7));
+    }
+
+    #[test]
+    fn sorted_chunks_in_pages_of_three() {
+        use crate::collections::InlineHeap;
+
+        let mut heap = InlineHeap::<u32, 10>::new();
+        heap.extend([7, 2, 9, 4, 0, 5, 8, 1, 6, 3]);
+
+        let mut pages = heap.sorted_chunks::<3>();
+        assert_eq!(pages.len(), 4);
+        assert_eq!(pages.next().unwrap(), [9, 8, 7]);
+        assert_eq!(pages.next().unwrap(), [6, 5, 4]);
+        assert_eq!(pages.next().unwrap(), [3, 2, 1]);
+        assert_eq!(pages.len(), 1);
+        assert_eq!(pages.next().unwrap(), [0]);
+        assert!(pages.next().is_none());
+        assert!(pages.next().is_none());
+        drop(pages);
+        assert!(heap.is_empty());
+
+        assert!(heap.sorted_chunks::<3>().next().is_none());
+
+        heap.extend([1, 2, 3]);
+        let mut none = heap.sorted_chunks::<0>();
+        assert_eq!(none.len(), 0);
+        assert!(none.next().is_none());
+        drop(none);
+        assert_eq!(heap.len(), 3);
+    }
+
+    #[test]
+    fn partially_sorted_vec_has_k_largest_prefix() {
+        use crate::collections::{InlineHeap, InlineVec};
+        use rand::{rngs::SmallRng, Rng, SeedableRng};
+
+        let mut rng = SmallRng::from_seed(crate::test_utils::RNG_SEED);
+        for &k in &[0, 1, 5, 31, 32, 100] {
+            let mut heap = InlineHeap::<u32, 32>::new();
+            while !heap.is_full() {
+                heap.push(rng.gen_range(0..20));
+            }
+
+            let mut sorted = InlineVec::<u32, 32>::new();
+            sorted.extend(heap.iter().copied());
+            sorted.sort_unstable_by(|a, b| b.cmp(a));
+
+            let mut vec = heap.into_partially_sorted_vec(k);
+            assert_eq!(vec.len(), 32);
+
+            let k = k.min(32);
+            assert_eq!(vec[..k], sorted[..k]);
+
+            vec.sort_unstable_by(|a, b| b.cmp(a));
+            assert_eq!(vec, sorted);
+        }
+    }
+
+    #[test]
+    fn append_sorted_desc_restores_heap() {
+        use crate::collections::InlineVec;
+        use rand::{rngs::SmallRng, Rng, SeedableRng};
+
+        fn check<const D: usize>(rng: &mut SmallRng) {
+            for old_len in 0..20 {
+                for new_len in 0..(40 - old_len) {
+                    let mut backing_region = [core::mem::MaybeUninit::<u32>::uninit(); 40];
+                    let mut heap = DaryHeap::<u32, _, usize, D>::from(&mut backing_region[..]);
+                    let mut all = InlineVec::<u32, 40>::new();
+
+                    for _ in 0..old_len {
+                        let x = rng.gen_range(0..50);
+                        heap.push(x);
+                        all.push(x);
+                    }
+
+                    let mut sorted = InlineVec::<u32, 40>::new();
+                    sorted.extend((0..new_len).map(|_| rng.gen_range(0..50)));
+                    sorted.sort_unstable_by(|a, b| b.cmp(a));
+                    all.extend_from_slice(&sorted);
+
+                    heap.append_sorted_desc(&sorted);
+                    assert!(heap.is_valid_heap());
+
+                    all.sort_unstable_by(|a, b| b.cmp(a));
+                    assert!(heap.drain_sorted().eq(all.iter().copied()));
+                }
+            }
+        }
+
+        let mut rng = SmallRng::from_seed(crate::test_utils::RNG_SEED);
+        check::<2>(&mut rng);
+        check::<3>(&mut rng);
+        check::<4>(&mut rng);
+    }
+
+    #[test]
+    #[should_panic(expected = "slice length (is 3) should be <= remaining capacity (is 2)")]
+    fn append_sorted_desc_beyond_capacity() {
+        let mut backing_region = [core::mem::MaybeUninit::<u32>::uninit(); 4];
+        let mut heap = SliceHeap::<u32>::from(&mut backing_region[..]);
+        heap.extend([1, 2]);
+        heap.append_sorted_desc(&[5, 4, 3]);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "slice should be sorted in descending order")]
+    fn append_sorted_desc_rejects_unsorted_input() {
+        let mut backing_region = [core::mem::MaybeUninit::<u32>::uninit(); 4];
+        let mut heap = SliceHeap::<u32>::from(&mut backing_region[..]);
+        heap.append_sorted_desc(&[1, 2, 3]);
+    }
+
+    #[test]
+    fn heap_on_stack_declares_independent_heaps() {
+        crate::heap_on_stack!(a: u32; 4);
+        crate::heap_on_stack!(b: u32, u8; 6);
+        a.extend([1, 2, 3]);
+        b.extend([6, 5, 4]);
+
+        assert_eq!(a.capacity(), 4);
+        assert_eq!(b.capacity(), 6);
+        assert_eq!(a.into_sorted_vec(), &[1, 2, 3][..]);
+        assert_eq!(b.into_sorted_vec(), &[4, 5, 6][..]);
+    }
+
+    #[test]
+    fn into_iter_yields_level_order() {
+        use crate::collections::InlineHeap;
+
+        let mut heap = InlineHeap::<u32, 8>::new();
+        for x in [3, 1, 4, 1, 5, 9, 2, 6] {
+            heap.push(x);
+        }
+
+        let expected = [9, 6, 5, 4, 1, 3, 2, 1];
+        assert!(heap.iter().eq(expected.iter()));
+        assert!(heap.into_iter().eq(expected.iter().copied()));
+    }
+
+    #[test]
+    fn min_matches_full_scan() {
+        use rand::{rngs::SmallRng, Rng, SeedableRng};
+
+        fn check<const D: usize>(rng: &mut SmallRng) {
+            let mut backing_region = [core::mem::MaybeUninit::<u32>::uninit(); 64];
+            let mut heap = DaryHeap::<u32, _, usize, D>::from(&mut backing_region[..]);
+            assert_eq!(heap.min(), None);
+
+            while !heap.is_full() {
+                heap.push(rng.gen_range(0..1000));
+                assert_eq!(heap.min(), heap.iter().min());
+            }
+
+            heap.clear();
+            heap.push(7);
+            assert_eq!(heap.min(), Some(&7));
+        }
+
+        let mut rng = SmallRng::from_seed(crate::test_utils::RNG_SEED);
+        check::<2>(&mut rng);
+        check::<3>(&mut rng);
+        check::<4>(&mut rng);
+    }
+
+    #[test]
+    fn into_sorted_vec_matches_sorting_the_items() {
+        use crate::collections::InlineHeap;
+        use rand::{rngs::SmallRng, Rng, SeedableRng};
+
+        let mut rng = SmallRng::from_seed(crate::test_utils::RNG_SEED);
+        for len in 0..=32 {
+            let mut heap = InlineHeap::<u32, 32>::new();
+            for _ in 0..len {
+                heap.push(rng.gen_range(0..100));
+            }
+
+            let mut expected = heap.clone().into_vec();
+            expected.sort_unstable();
+            assert_eq!(heap.into_sorted_vec(), expected);
+        }
+    }
+
+    #[test]
+    fn push_unique_rejects_equal_items() {
+        use crate::collections::InlineHeap;
+        use crate::test_utils::Tagged;
+
+        let mut heap = InlineHeap::<Tagged, 8>::new();
+        let mut inserted = 0;
+        for (tag, &key) in [5, 3, 5, 8, 1, 3, 8, 2].iter().enumerate() {
+            let tag = tag as u32;
+            if heap.push_unique(Tagged { key, tag }) {
+                inserted += 1;
+            }
+            assert!(heap.is_valid_heap());
+        }
+
+        assert_eq!(inserted, 5);
+        assert_eq!(heap.len(), 5);
+
+        let sorted = heap.into_sorted_vec();
+        let tags = [4, 7, 1, 0, 3];
+        for (item, &tag) in sorted.iter().zip(tags.iter()) {
+            assert_eq!(item.tag, tag);
+        }
+    }
+
+    #[test]
+    fn clone_into_reuses_destination_storage() {
+        use core::mem::MaybeUninit;
+
+        let mut backing_src = [MaybeUninit::uninit(); 6];
+        let mut backing_dst = [MaybeUninit::uninit(); 6];
+        let mut backing_small = [MaybeUninit::uninit(); 3];
+
+        let mut src = SliceHeap::<u32>::from(&mut backing_src[..]);
+        let mut dst = SliceHeap::<u32, u8>::from(&mut backing_dst[..]);
+        let mut small = SliceHeap::<u32>::from(&mut backing_small[..]);
+
+        src.extend([5, 2, 8, 1, 9]);
+        dst.extend([10, 0]);
+        small.push(7);
+
+        assert!(src.clone_into(&mut dst).is_ok());
+        assert!(dst.is_valid_heap());
+        assert!(src.iter().eq(dst.iter()));
+
+        assert_eq!(src.clone_into(&mut small), Err(CapacityError::default()));
+        assert_eq!(small.len(), 1);
+        assert_eq!(small.peek(), Some(&7));
+    }
+
+    #[test]
+    fn extend_heap_ordered_round_trip() {
+        use crate::collections::{InlineHeap, InlineVec};
+
+        let mut heap = InlineHeap::<u32, 16>::new();
+        heap.extend([3, 1, 4, 1, 5, 9, 2, 6, 5, 3]);
+
+        let mut serialized = InlineVec::<u32, 16>::new();
+        serialized.extend(heap.iter().copied());
+
+        let mut restored = InlineHeap::<u32, 16>::new();
+        restored.extend_heap_ordered(&serialized[..4]);
+        restored.extend_heap_ordered(&serialized[4..]);
+        assert!(restored.iter().eq(heap.iter()));
+        assert_eq!(restored.into_sorted_vec(), heap.into_sorted_vec());
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "appended items should preserve the heap property")]
+    fn extend_heap_ordered_rejects_invalid_order() {
+        let mut backing_region = [core::mem::MaybeUninit::<u32>::uninit(); 4];
+        let mut heap = SliceHeap::<u32>::from(&mut backing_region[..]);
+        heap.push(5);
+        heap.extend_heap_ordered(&[3, 7]);
+    }
+
+    #[test]
+    fn depth_and_level_slices() {
+        use crate::collections::InlineHeap;
+
+        let mut heap = InlineHeap::<u32, 10>::new();
+        assert_eq!(heap.depth(), 0);
+        assert!(heap.level_slice(0).is_empty());
+
+        heap.push(0);
+        assert_eq!(heap.depth(), 1);
+        assert_eq!(heap.level_slice(0), &[0]);
+        assert!(heap.level_slice(1).is_empty());
+
+        heap.extend(1..7);
+        assert_eq!(heap.depth(), 3);
+        let widths = [1, 2, 4, 0];
+        for (level, &width) in widths.iter().enumerate() {
+            assert_eq!(heap.level_slice(level).len(), width);
+        }
+
+        heap.extend(7..10);
+        assert_eq!(heap.depth(), 4);
+        let a = heap.a.as_slice();
+        assert_eq!(heap.level_slice(0), &a[..1]);
+        assert_eq!(heap.level_slice(1), &a[1..3]);
+        assert_eq!(heap.level_slice(2), &a[3..7]);
+        assert_eq!(heap.level_slice(3), &a[7..]);
+        assert!(heap.level_slice(4).is_empty());
+        assert!(heap.level_slice(usize::MAX).is_empty());
+
+        let mut backing_region = [core::mem::MaybeUninit::<u32>::uninit(); 10];
+        let mut ternary = DaryHeap::<u32, _, usize, 3>::from(&mut backing_region[..]);
+        ternary.extend(0..10);
+        assert_eq!(ternary.depth(), 3);
+        let a = ternary.a.as_slice();
+        assert_eq!(ternary.level_slice(1), &a[1..4]);
+        assert_eq!(ternary.level_slice(2), &a[4..]);
+    }
+
+    #[test]
+    fn retain_counting_matches_lengths() {
+        use crate::test_utils::*;
+        use rand::{rngs::SmallRng, Rng, SeedableRng};
+
+        let mut rng = SmallRng::from_seed(crate::test_utils::RNG_SEED);
+        let drop_count = DropCounter::new();
+        let mut heap = crate::collections::InlineHeap::<Droppable<'_, u32>, 32>::new();
+        while !heap.is_full() {
+            heap.push(drop_count.new_droppable(rng.gen_range(0..100)));
+        }
+
+        for threshold in [10, 40, 75, 101] {
+            let before = heap.len();
+            let dropped_before = drop_count.dropped();
+            let mut calls = 0;
+            let (kept, removed) = heap.retain_counting(|d| {
+                calls += 1;
+                d.value >= threshold
+            });
+
+            assert_eq!(calls, before);
+            assert_eq!(kept, heap.len());
+            assert_eq!(kept + removed, before);
+            assert_eq!(drop_count.dropped() - dropped_before, removed);
+            assert!(heap.iter().all(|d| d.value >= threshold));
+            assert!(heap.is_valid_heap());
+        }
+
+        assert!(heap.is_empty());
+        assert_eq!(drop_count.dropped(), 32);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn alloc_heap_of_owned_strings() {
+        use crate::collections::AllocHeap;
+        use alloc::string::{String, ToString};
+
+        let mut heap = AllocHeap::<String>::with_capacity(4);
+        for s in ["pear", "apple", "quince", "fig"] {
+            heap.push(s.to_string());
+        }
+
+        let mut clone = heap.clone();
+        assert_eq!(clone, heap);
+
+        for expected in ["quince", "pear", "fig", "apple"] {
+            assert_eq!(heap.pop().as_deref(), Some(expected));
+        }
+        assert!(heap.is_empty());
+
+        assert_eq!(clone.len(), 4);
+        assert_eq!(clone.pop().as_deref(), Some("quince"));
+    }
+
+    #[test]
+    fn repair_after_mutation_through_refcell() {
+        use crate::collections::InlineHeap;
+        use core::cell::RefCell;
+        use rand::{rngs::SmallRng, Rng, SeedableRng};
+
+        let mut rng = SmallRng::from_seed(crate::test_utils::RNG_SEED);
+        let mut heap = InlineHeap::<RefCell<u32>, 32>::new();
+        heap.extend((0..32).map(RefCell::new));
+
+        for x in heap.iter() {
+            *x.borrow_mut() = rng.gen_range(0..100);
+        }
+        if heap.is_valid_heap() {
+            *heap.iter().last().unwrap().borrow_mut() = 100;
+        }
+        assert!(!heap.is_valid_heap());
+
+        heap.repair();
+        assert!(heap.is_valid_heap());
+
+        let mut last = u32::MAX;
+        while let Some(x) = heap.pop() {
+            let x = x.into_inner();
+            assert!(x <= last);
+            last = x;
+        }
+    }
+
+    #[test]
+    fn inline_capacity_in_const_context() {
+        use crate::collections::InlineHeap;
+
+        const CAP: usize = InlineHeap::<u8, 12, u8>::CAPACITY;
+        const _: () = assert!(CAP == 12);
+        let buf = [0u8; InlineHeap::<u8, 5>::CAPACITY];
+        assert_eq!(buf.len(), 5);
+
+        let heap = DaryHeap::<u8, [MaybeUninit<u8>; 7], u16, 3>::new();
+        assert_eq!(
+            heap.capacity(),
+            DaryHeap::<u8, [MaybeUninit<u8>; 7], u16, 3>::CAPACITY
+        );
+    }
+
+    #[test]
+    fn count_ge_matches_naive_count() {
+        use crate::collections::InlineVec;
+        use rand::{rngs::SmallRng, Rng, SeedableRng};
+
+        fn check<const D: usize>(rng: &mut SmallRng) {
+            for len in 0..=64 {
+                let mut heap = DaryHeap::<u32, _, usize, D>::from(InlineVec::<u32, 64>::new());
+                heap.extend((0..len).map(|_| rng.gen_range(0..32)));
+                for threshold in 0..=32 {
+                    let naive = heap.iter().filter(|&&x| x >= threshold).count();
+                    assert_eq!(heap.count_ge(&threshold), naive);
+                }
+            }
+        }
+
+        let mut rng = SmallRng::from_seed(crate::test_utils::RNG_SEED);
+        check::<2>(&mut rng);
+        check::<3>(&mut rng);
+        check::<4>(&mut rng);
+    }
+
+    #[test]
+    fn drain_sorted_from_both_ends() {
+        use crate::collections::InlineVec;
+        use rand::{rngs::SmallRng, RngCore, SeedableRng};
+
+        let mut rng = SmallRng::from_seed(crate::test_utils::RNG_SEED);
+        let mut heap = crate::collections::InlineHeap::<u32, 64>::new();
+        heap.extend((0..64).map(|_| rng.next_u32() % 32));
+
+        let mut expected = heap.a.clone();
+        expected.sort_unstable();
+
+        let mut front = InlineVec::<u32, 64>::new();
+        let mut back = InlineVec::<u32, 64>::new();
+        let mut iter = heap.drain_sorted();
+        while iter.len() > 0 {
+            let remaining = iter.len();
+            if rng.next_u32() % 2 == 0 {
+                front.push(iter.next().unwrap());
+            } else {
+                back.push(iter.next_back().unwrap());
+            }
+            assert_eq!(iter.len(), remaining - 1);
+            assert!(iter.heap.is_valid_heap());
+        }
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next_back(), None);
+        drop(iter);
+
+        let reconstructed: InlineVec<u32, 64> =
+            back.iter().chain(front.iter().rev()).copied().collect();
+        assert_eq!(reconstructed, expected);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn into_sorted_boxed_slice_fits_exactly() {
+        use rand::{rngs::SmallRng, RngCore, SeedableRng};
+
+        let mut rng = SmallRng::from_seed(crate::test_utils::RNG_SEED);
+        let mut heap = crate::collections::AllocHeap::<u32>::with_capacity(100);
+        heap.extend((0..73).map(|_| rng.next_u32()));
+
+        let slice = heap.into_sorted_boxed_slice();
+        assert_eq!(slice.len(), 73);
+        assert!(slice.windows(2).all(|w| w[0] <= w[1]));
+
+        let empty = crate::collections::AllocHeap::<u32>::with_capacity(8);
+        assert!(empty.into_sorted_boxed_slice().is_empty());
     }
 
-    /// Consumes the `BinaryHeap` and returns the underlying vector in arbitrary order.
-    #[inline]
-    pub fn into_vec(self) -> Vec<T, S, I> {
-        self.a
+    #[test]
+    fn pop_if_greater_then_push_replaces_or_ignores() {
+        let mut heap = crate::collections::InlineHeap::<u32, 4>::new();
+        assert_eq!(heap.pop_if_greater_then_push(1), None);
+        assert!(heap.is_empty());
+
+        heap.extend([10, 40, 20, 30]);
+        assert_eq!(heap.pop_if_greater_then_push(40), None);
+        assert_eq!(heap.pop_if_greater_then_push(50), None);
+        assert_eq!(heap.len(), 4);
+        assert_eq!(heap.peek(), Some(&40));
+
+        assert_eq!(heap.pop_if_greater_then_push(25), Some(40));
+        assert_eq!(heap.pop_if_greater_then_push(5), Some(30));
+        assert!(heap.is_valid_heap());
+        assert_eq!(heap.into_sorted_vec(), [5, 10, 20, 25]);
     }
 
-    /// Consumes the `BinaryHeap` and returns a vector in sorted (ascending) order.
-    ///
-    /// # Examples
-    /// ```
-    /// let mut backing_region = [core::mem::MaybeUninit::<u32>::uninit(); 5];
-    /// let mut heap = coca::collections::SliceHeap::<_>::from(&mut backing_region[..]);
-    /// heap.push(1); heap.push(5); heap.push(3); heap.push(2); heap.push(4);
-    /// let vec = heap.into_sorted_vec();
-    /// assert_eq!(vec, &[1, 2, 3, 4, 5][..]);
-    /// ```
-    pub fn into_sorted_vec(self) -> Vec<T, S, I> {
-        let mut result = self.into_vec();
-        let a = result.as_mut_slice();
-        for i in (1..a.len()).rev() {
-            a.swap(0, i);
-            heapify(&mut a[..i], 0);
-        }
-        result
+    #[test]
+    fn bytes_round_trip() {
+        use crate::collections::InlineHeap;
+        use rand::{rngs::SmallRng, RngCore, SeedableRng};
+
+        let mut rng = SmallRng::from_seed(crate::test_utils::RNG_SEED);
+        let mut heap = InlineHeap::<u32, 32, u8>::new();
+        heap.extend((0..27).map(|_| rng.next_u32()));
+
+        let bytes = heap.as_bytes();
+        assert_eq!(bytes.len(), 27 * 4);
+        let first = u32::from_ne_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+        assert_eq!(Some(&first), heap.peek());
+
+        let mut unaligned = [0u8; 27 * 4 + 1];
+        unaligned[1..].copy_from_slice(bytes);
+        let restored = InlineHeap::<u32, 32, u8>::from_bytes_heapify(
+            [MaybeUninit::uninit(); 32],
+            &unaligned[1..],
+        );
+        assert_eq!(restored.as_bytes(), heap.as_bytes());
+
+        let shuffled: InlineVec<u8, 12> =
+            [1u32, 3, 2].iter().flat_map(|x| x.to_ne_bytes()).collect();
+        let heap = InlineHeap::<u32, 4>::from_bytes_heapify([MaybeUninit::uninit(); 4], &shuffled);
+        assert!(heap.is_valid_heap());
+        assert_eq!(heap.into_sorted_vec(), [1, 2, 3]);
     }
 
-    /// Consumes the `BinaryHeap` and returns an iterator which yields elements
-    /// in heap order.
-    ///
-    /// When dropped, the remaining elements will be dropped in heap order.
-    ///
-    /// # Remarks
-    /// `.into_iter_sorted()` is O(n log(n)), much slower than [`.into_iter()`](BinaryHeap::into_iter).
-    /// The latter is preferable in most cases.
-    ///
-    /// # Examples
-    /// ```
-    /// let mut backing_region = [core::mem::MaybeUninit::<u32>::uninit(); 3];
-    /// let mut heap = coca::collections::SliceHeap::<_>::from(&mut backing_region[..]);
-    /// heap.push(1); heap.push(3); heap.push(5);
-    ///
-    /// let mut iter = heap.into_iter_sorted();
-    /// assert_eq!(iter.next(), Some(5));
-    /// assert_eq!(iter.next(), Some(3));
-    /// assert_eq!(iter.next(), Some(1));
-    /// ```
-    pub fn into_iter_sorted(self) -> IntoIterSorted<T, S, I> {
-        IntoIterSorted { heap: self }
+    #[test]
+    #[should_panic(expected = "bytes length (is 7) should be a multiple of the item size (is 4)")]
+    fn from_bytes_heapify_rejects_partial_items() {
+        let _ = crate::collections::InlineHeap::<u32, 4>::from_bytes_heapify(
+            [MaybeUninit::uninit(); 4],
+            &[0; 7],
+        );
     }
-}
 
-impl<T: Ord, S: Storage<ArrayLayout<T>>, I: Capacity> IntoIterator for BinaryHeap<T, S, I> {
-    type Item = T;
-    type IntoIter = <Vec<T, S, I> as IntoIterator>::IntoIter;
-    fn into_iter(self) -> Self::IntoIter {
-        self.a.into_iter()
+    #[test]
+    #[should_panic(expected = "bytes length (is 20) should be <= capacity in bytes (is 16)")]
+    fn from_bytes_heapify_rejects_excess_items() {
+        let _ = crate::collections::InlineHeap::<u32, 4>::from_bytes_heapify(
+            [MaybeUninit::uninit(); 4],
+            &[0; 20],
+        );
     }
-}
 
-impl<T1, T2: Ord, S: Storage<ArrayLayout<T2>>, I: Capacity> Extend<T1> for BinaryHeap<T2, S, I>
-where
-    Vec<T2, S, I>: Extend<T1>,
-{
-    fn extend<T: IntoIterator<Item = T1>>(&mut self, iter: T) {
-        self.a.extend(iter);
-        for i in (0..(self.a.len() / 2)).rev() {
-            heapify(self.a.as_mut_slice(), i);
+    #[test]
+    fn push_with_large_items() {
+        let mut heap = crate::collections::InlineHeap::<[u8; 1024], 8, u8>::new();
+        for i in [3u8, 7, 1, 5, 2, 8, 6, 4] {
+            heap.push_with(|| [i; 1024]).unwrap();
+        }
+        assert!(heap.push_with(|| [0; 1024]).is_err());
+        assert!(heap.is_valid_heap());
+
+        for i in (1..=8).rev() {
+            let item = heap.pop().unwrap();
+            assert!(item.iter().all(|&x| x == i));
         }
     }
-}
 
-impl<T: Ord, S: Storage<ArrayLayout<T>>, I: Capacity> FromIterator<T> for BinaryHeap<T, S, I>
-where
-    Vec<T, S, I>: FromIterator<T>,
-{
-    /// Creates a binary heap from an iterator.
-    ///
-    /// # Panics
-    /// Panics if the iterator yields more elements than the binary heap can hold.
-    fn from_iter<It: IntoIterator<Item = T>>(iter: It) -> Self {
-        let a = Vec::<T, S, I>::from_iter(iter);
-        Self::from(a)
+    #[test]
+    #[cfg(feature = "std")]
+    fn push_with_leaves_heap_unchanged_on_panic() {
+        let mut heap = crate::collections::InlineHeap::<u32, 4>::new();
+        heap.push(1);
+
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            let _ = heap.push_with(|| panic!("constructor failed"));
+        }));
+        assert!(result.is_err());
+        assert_eq!(heap.len(), 1);
+        assert_eq!(heap.pop(), Some(1));
     }
-}
 
-/// A draining iterator over the elements of a `BinaryHeap`.
-///
-/// This `struct` is created by [`BinaryHeap::drain_sorted()`].
-/// See its documentation for more.
-pub struct DrainSorted<'a, T: Ord, S: Storage<ArrayLayout<T>>, I: Capacity> {
-    heap: &'a mut BinaryHeap<T, S, I>,
-}
+    #[test]
+    fn from_iter_keeping_smallest_of_shuffled_range() {
+        use rand::{rngs::SmallRng, seq::SliceRandom, SeedableRng};
 
-impl<T: Ord, S: Storage<ArrayLayout<T>>, I: Capacity> Iterator for DrainSorted<'_, T, S, I> {
-    type Item = T;
+        let mut rng = SmallRng::from_seed(crate::test_utils::RNG_SEED);
+        let mut items = [0u32; 10000];
+        for (i, x) in items.iter_mut().enumerate() {
+            *x = i as u32;
+        }
+        items.shuffle(&mut rng);
 
-    fn size_hint(&self) -> (usize, Option<usize>) {
-        let size = self.len();
-        (size, Some(size))
-    }
+        let mut buf = [MaybeUninit::<u32>::uninit(); 5];
+        let heap =
+            SliceHeap::<u32>::from_iter_keeping_smallest(&mut buf[..], items.iter().copied());
+        assert!(heap.is_valid_heap());
+        assert_eq!(heap.into_sorted_vec(), &[0, 1, 2, 3, 4][..]);
 
-    fn next(&mut self) -> Option<Self::Item> {
-        self.heap.pop()
+        let mut buf = [MaybeUninit::<u32>::uninit(); 5];
+        let heap = SliceHeap::<u32>::from_iter_keeping_smallest(&mut buf[..], [4, 2]);
+        assert_eq!(heap.into_sorted_vec(), &[2, 4][..]);
     }
-}
 
-impl<T: Ord, S: Storage<ArrayLayout<T>>, I: Capacity> ExactSizeIterator
-    for DrainSorted<'_, T, S, I>
-{
-}
-impl<T: Ord, S: Storage<ArrayLayout<T>>, I: Capacity> FusedIterator for DrainSorted<'_, T, S, I> {}
+    #[test]
+    fn insert_bounded_outcomes() {
+        let mut heap = crate::collections::InlineHeap::<u32, 5>::new();
+        for x in [50, 10, 40, 20] {
+            assert_eq!(heap.insert_bounded(x), InsertOutcome::Inserted);
+        }
+        assert_eq!(heap.insert_bounded(5), InsertOutcome::Inserted);
+        assert!(heap.is_full());
 
-impl<T: Ord, S: Storage<ArrayLayout<T>>, I: Capacity> Drop for DrainSorted<'_, T, S, I> {
-    fn drop(&mut self) {
-        self.for_each(drop);
+        assert_eq!(heap.insert_bounded(5), InsertOutcome::Rejected(5));
+        assert_eq!(heap.insert_bounded(1), InsertOutcome::Rejected(1));
+        assert_eq!(heap.insert_bounded(60), InsertOutcome::Replaced(5));
+        assert_eq!(heap.insert_bounded(15), InsertOutcome::Replaced(10));
+        assert!(heap.is_valid_heap());
+        assert_eq!(heap.into_sorted_vec(), [15, 20, 40, 50, 60]);
+
+        let mut heap = crate::collections::InlineHeap::<u32, 0>::new();
+        assert_eq!(heap.insert_bounded(1), InsertOutcome::Rejected(1));
     }
-}
 
-/// A consuming iterator that moves out of a `BinaryHeap`.
-///
-/// This `struct` is created by [`BinaryHeap::into_iter_sorted()`].
-/// See its documentation for more.
-#[derive(Debug)]
-pub struct IntoIterSorted<T: Ord, S: Storage<ArrayLayout<T>>, I: Capacity> {
-    heap: BinaryHeap<T, S, I>,
-}
+    #[test]
+    fn top_k_matches_sorted_prefix() {
+        use crate::collections::binary_heap::QuaternaryHeap;
+        use rand::{rngs::SmallRng, Rng, SeedableRng};
 
-impl<T: Ord, S: Storage<ArrayLayout<T>>, I: Capacity> Iterator for IntoIterSorted<T, S, I> {
-    type Item = T;
+        let mut rng = SmallRng::from_seed(crate::test_utils::RNG_SEED);
+        let mut heap = crate::collections::InlineHeap::<u32, 64, u8>::new();
+        let mut heap4 =
+            QuaternaryHeap::<u32, [MaybeUninit<u32>; 64], u8>::from([MaybeUninit::uninit(); 64]);
+        for _ in 0..50 {
+            let x = rng.gen_range(0..20);
+            heap.push(x);
+            heap4.push(x);
+        }
 
-    #[inline]
-    fn size_hint(&self) -> (usize, Option<usize>) {
-        let size = self.heap.len();
-        (size, Some(size))
-    }
+        let before = heap.clone();
+        for k in 0..=60 {
+            assert!(heap.top_k(k).eq(heap.iter_sorted().take(k)));
+            assert!(heap4.top_k(k).eq(heap.iter_sorted().take(k)));
+            assert_eq!(heap4.top_k(k).len(), k.min(50));
+        }
+        assert!(heap.top_k(usize::MAX).eq(heap.iter_sorted()));
+        assert_eq!(heap.top_k(usize::MAX).len(), 50);
+        assert_eq!(heap.a, before.a);
 
-    #[inline]
-    fn next(&mut self) -> Option<T> {
-        self.heap.pop()
+        let empty = crate::collections::InlineHeap::<u32, 4>::new();
+        assert_eq!(empty.top_k(3).next(), None);
     }
-}
 
-impl<T: Ord, S: Storage<ArrayLayout<T>>, I: Capacity> ExactSizeIterator
-    for IntoIterSorted<T, S, I>
-{
-}
-impl<T: Ord, S: Storage<ArrayLayout<T>>, I: Capacity> FusedIterator for IntoIterSorted<T, S, I> {}
+    #[test]
+    fn debug_sorted_is_independent_of_push_order() {
+        use core::fmt::Write;
+        type Heap = crate::collections::InlineHeap<u32, 8>;
 
-impl<T: Clone + Ord, S: Storage<ArrayLayout<T>>, I: Capacity> Clone for IntoIterSorted<T, S, I>
-where
-    BinaryHeap<T, S, I>: Clone,
-{
-    fn clone(&self) -> Self {
-        self.heap.clone().into_iter_sorted()
-    }
-}
+        let mut fst = Heap::new();
+        fst.extend([4, 1, 3, 1, 5]);
+        let mut snd = Heap::new();
+        snd.extend([1, 1, 3, 4, 5]);
 
-impl<T: Ord, S: Storage<ArrayLayout<T>>, I: Capacity> Drop for IntoIterSorted<T, S, I> {
-    fn drop(&mut self) {
-        self.for_each(drop);
+        let mut s1 = crate::InlineString::<64>::new();
+        let mut s2 = crate::InlineString::<64>::new();
+        write!(s1, "{:?}", fst.debug_sorted()).unwrap();
+        write!(s2, "{:?}", snd.debug_sorted()).unwrap();
+        assert_eq!(s1, "[5, 4, 3, 1, 1]");
+        assert_eq!(s1, s2);
+
+        s1.clear();
+        write!(s1, "{:#?}", fst.debug_sorted()).unwrap();
+        assert_eq!(s1, "[\n    5,\n    4,\n    3,\n    1,\n    1,\n]");
     }
-}
 
-#[cfg(feature = "alloc")]
-#[cfg_attr(docs_rs, doc(cfg(feature = "alloc")))]
-impl<T: Ord, I: Capacity> crate::collections::AllocHeap<T, I> {
-    /// Constructs a new, empty `AllocHeap<T, I>` with the specified capacity.
-    ///
-    /// # Panics
-    /// Panics if the specified capacity cannot be represented by a `usize`.
-    pub fn with_capacity(capacity: I) -> Self {
-        BinaryHeap {
-            a: Vec::with_capacity(capacity),
+    #[test]
+    fn from_array_heapifies_and_drops() {
+        use crate::test_utils::*;
+
+        let mut heap = crate::collections::InlineHeap::<u32, 5, u8>::from([3, 1, 4, 1, 5]);
+        assert!(heap.is_valid_heap());
+        for x in [5, 4, 3, 1, 1] {
+            assert_eq!(heap.pop(), Some(x));
         }
+        assert!(heap.is_empty());
+
+        let counter = DropCounter::new();
+        let items = [
+            counter.new_droppable(2),
+            counter.new_droppable(7),
+            counter.new_droppable(5),
+        ];
+        let heap = crate::collections::InlineHeap::<_, 3>::from(items);
+        assert_eq!(heap.peek().map(|x| x.value), Some(7));
+        assert_eq!(counter.dropped(), 0);
+        drop(heap);
+        assert_eq!(counter.dropped(), 3);
     }
-}
 
-#[cfg(feature = "alloc")]
-#[cfg_attr(docs_rs, doc(cfg(feature = "alloc")))]
-impl<T: Clone + Ord, I: Capacity> Clone for crate::collections::AllocHeap<T, I> {
-    fn clone(&self) -> Self {
-        BinaryHeap { a: self.a.clone() }
+    #[test]
+    fn byte_sizes_of_array_items() {
+        let mut heap = crate::collections::InlineHeap::<[u8; 24], 10, u8>::new();
+        assert_eq!(heap.capacity_bytes(), 240);
+        assert_eq!(heap.len_bytes(), 0);
+
+        heap.extend([[1; 24], [2; 24], [3; 24]]);
+        assert_eq!(heap.len_bytes(), 72);
+
+        let heap = crate::collections::InlineHeap::<(), 10>::new();
+        assert_eq!(heap.capacity_bytes(), 0);
     }
-}
 
-impl<T: Ord, I: Capacity, const C: usize> BinaryHeap<T, [MaybeUninit<T>; C], I> {
-    /// Constructs a new, empty `BinaryHeap` backed by an inline array.
-    ///
-    /// # Panics
-    /// Panics if `C` cannot be represented as a value of type `I`.
-    ///
-    /// # Examples
-    /// ```
-    /// let heap = coca::collections::InlineHeap::<char, 4>::new();
-    /// assert_eq!(heap.capacity(), 4);
-    /// assert!(heap.is_empty());
-    /// ```
-    pub fn new() -> Self {
-        let a = Vec::new();
-        BinaryHeap { a }
+    #[test]
+    fn into_stably_sorted_vec_keeps_storage_order_of_ties() {
+        use crate::test_utils::Tagged;
+        use rand::{rngs::SmallRng, Rng, SeedableRng};
+
+        let mut rng = SmallRng::from_seed(crate::test_utils::RNG_SEED);
+        let mut heap = crate::collections::InlineHeap::<Tagged, 64, u8>::new();
+        for tag in 0..64 {
+            let key = rng.gen_range(0..5);
+            heap.push(Tagged { key, tag });
+        }
+
+        let mut expected = InlineVec::<u32, 64>::new();
+        for key in 0..5 {
+            expected.extend(heap.iter().filter(|t| t.key == key).map(|t| t.tag));
+        }
+
+        let sorted = heap.into_stably_sorted_vec();
+        assert!(sorted.windows(2).all(|w| w[0].key <= w[1].key));
+        assert!(sorted.iter().map(|t| t.tag).eq(expected.iter().copied()));
     }
-}
 
-impl<T: Ord, I: Capacity, const C: usize> Default for BinaryHeap<T, [MaybeUninit<T>; C], I> {
-    fn default() -> Self {
-        Self::new()
+    #[test]
+    fn swap_remove_max_then_repair() {
+        use rand::{rngs::SmallRng, Rng, SeedableRng};
+
+        let mut rng = SmallRng::from_seed(crate::test_utils::RNG_SEED);
+        let mut heap = crate::collections::InlineHeap::<u32, 32, u8>::new();
+        heap.extend((0..32).map(|_| rng.gen_range(0..1000)));
+        let mut expected = heap.clone().into_sorted_vec();
+
+        assert_eq!(heap.swap_remove_max(), expected.pop());
+        assert_eq!(heap.len(), 31);
+        heap.repair();
+        assert!(heap.is_valid_heap());
+        assert!(heap.into_sorted_vec().iter().eq(expected.iter()));
+
+        let mut empty = crate::collections::InlineHeap::<u32, 4>::new();
+        assert_eq!(empty.swap_remove_max(), None);
     }
-}
 
-impl<T: Clone + Ord, I: Capacity, const C: usize> Clone for BinaryHeap<T, [MaybeUninit<T>; C], I> {
-    fn clone(&self) -> Self {
-        BinaryHeap { a: self.a.clone() }
+    #[test]
+    fn iter_indexed_positions_work_with_update_at() {
+        let mut heap = crate::collections::InlineHeap::<u32, 16, u8>::new();
+        heap.extend([30, 10, 40, 10, 50, 90, 20, 60]);
+
+        let pairs: InlineVec<(u8, u32), 16> = heap.iter_indexed().map(|(i, &x)| (i, x)).collect();
+        assert!(pairs.iter().map(|&(_, x)| x).eq(heap.iter().copied()));
+        assert!(pairs.iter().map(|&(i, _)| i).eq(0..8));
+
+        for target in [10, 20, 30] {
+            let (idx, _) = heap.iter_indexed().find(|&(_, &x)| x == target).unwrap();
+            assert!(heap.update_at(idx, |x| *x += 100));
+            assert!(heap.is_valid_heap());
+        }
+        assert_eq!(heap.into_sorted_vec(), [10, 40, 50, 60, 90, 110, 120, 130]);
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    #[test]
+    fn navigation_does_not_overflow() {
+        assert_eq!(first_child::<2>(usize::MAX / 2 + 1), usize::MAX);
+        assert_eq!(first_child::<4>(usize::MAX / 3), usize::MAX);
+        assert_eq!(first_leaf::<2>(usize::MAX), usize::MAX / 2);
+        assert_eq!(parent::<3>(usize::MAX), (usize::MAX - 1) / 3);
+    }
 
     #[test]
-    fn tree_traversal_utilities() {
-        assert_eq!(left(0), 1);
-        assert_eq!(right(0), 2);
-        assert_eq!(parent(1), 0);
-        assert_eq!(parent(2), 0);
+    fn full_u16_indexed_heap() {
+        // Index arithmetic is done in `usize`, so this only covers converting
+        // lengths and positions to and from the index type at its maximum.
+        use rand::{rngs::SmallRng, Rng, SeedableRng};
 
-        for i in 1..=1000 {
-            let l = left(i);
-            let r = right(i);
-            assert_eq!(l + 1, r);
-            assert_eq!(parent(l), i);
-            assert_eq!(parent(r), i);
+        let mut rng = SmallRng::from_seed(crate::test_utils::RNG_SEED);
+        let mut heap = crate::collections::InlineHeap::<u8, 65535, u16>::new();
+        heap.extend((0..65535).map(|_| rng.gen::<u8>()));
+        assert!(heap.is_full());
+        assert!(heap.is_valid_heap());
+        assert_eq!(heap.len_index(), u16::MAX);
 
-            let ll = left(l);
-            let lr = right(l);
-            let rl = left(r);
-            let rr = right(r);
+        let (last, _) = heap.iter_indexed().last().unwrap();
+        assert!(heap.update_at(last, |x| *x = 255));
+        assert_eq!(heap.pop(), Some(255));
+        heap.push(0);
+        assert!(heap.is_valid_heap());
 
-            assert_eq!(ll + 1, lr);
-            assert_eq!(rl + 1, rr);
-            assert_eq!(parent(parent(ll)), i);
-            assert_eq!(parent(parent(lr)), i);
-            assert_eq!(parent(parent(rl)), i);
-            assert_eq!(parent(parent(rr)), i);
+        let mut prev = 255;
+        while let Some(x) = heap.pop() {
+            assert!(x <= prev);
+            prev = x;
         }
     }
 
     #[test]
-    fn push_and_pop_randomized_inputs() {
-        use rand::{rngs::SmallRng, RngCore, SeedableRng};
-
-        let mut backing_region = [core::mem::MaybeUninit::<u32>::uninit(); 32];
-        let mut heap = crate::collections::SliceHeap::<_>::from(&mut backing_region[..]);
+    fn scratch_is_reusable_across_selections() {
+        use crate::collections::binary_heap::QuaternaryHeap;
+        use rand::{rngs::SmallRng, Rng, SeedableRng};
 
         let mut rng = SmallRng::from_seed(crate::test_utils::RNG_SEED);
+        let mut heap = crate::collections::InlineHeap::<u32, 64, u8>::new();
+        let mut heap4 =
+            QuaternaryHeap::<u32, [MaybeUninit<u32>; 64], u8>::from([MaybeUninit::uninit(); 64]);
+        for _ in 0..50 {
+            let x = rng.gen_range(0..30);
+            heap.push(x);
+            heap4.push(x);
+        }
 
-        let mut newest = 0;
-        for _ in 0..32 {
-            newest = rng.next_u32();
-            heap.push(newest);
+        let mut scratch = [usize::MAX; 200];
+        for k in [0, 1, 7, 3, 50, 64, 12] {
+            assert!(heap
+                .top_k_in(k, &mut scratch)
+                .eq(heap.iter_sorted().take(k)));
+            assert!(heap4
+                .top_k_in(k, &mut scratch)
+                .eq(heap.iter_sorted().take(k)));
+        }
+        for k in 0..=50 {
+            assert_eq!(heap.kth_largest_in(k, &mut scratch), heap.kth_largest(k));
+            assert_eq!(heap4.kth_largest_in(k, &mut scratch), heap.kth_largest(k));
         }
 
-        let mut prev = u32::max_value();
-        for _ in 0..1000 {
-            let x = heap.pop().unwrap();
-            assert!(x <= prev || x == newest);
-            prev = x;
+        let exact = QuaternaryHeap::<u32, [MaybeUninit<u32>; 64], u8>::top_k_scratch_len(50);
+        assert_eq!(heap4.top_k_in(50, &mut scratch[..exact]).count(), 50);
+    }
 
-            newest = rng.next_u32();
-            heap.push(newest);
+    #[test]
+    #[should_panic(expected = "scratch length (is 4) should be >= required length (is 5)")]
+    fn top_k_in_rejects_short_scratch() {
+        let mut heap = crate::collections::InlineHeap::<u32, 8>::new();
+        heap.extend(0..8);
+        let _ = heap.top_k_in(5, &mut [0; 4]);
+    }
+
+    #[test]
+    fn drain_sorted_fast_drops_remainder_on_early_drop() {
+        use crate::test_utils::{DropLog, Logged};
+
+        let log = DropLog::default();
+        let mut heap = crate::collections::InlineHeap::<Logged, 8>::new();
+        for value in [4, 8, 1, 6, 3, 7, 2, 5] {
+            heap.push(Logged::new(value, &log));
         }
+
+        let mut iter = heap.drain_sorted_fast();
+        assert_eq!(iter.len(), 8);
+        assert_eq!(iter.next().map(|x| x.value), Some(8));
+        assert_eq!(iter.next().map(|x| x.value), Some(7));
+        assert_eq!(*log.borrow(), [8, 7]);
+
+        drop(iter);
+        assert!(heap.is_empty());
+        assert_eq!(heap.capacity(), 8);
+        drop(heap);
+
+        let mut dropped = log.into_inner();
+        assert_eq!(dropped.len(), 8);
+        dropped.sort_unstable();
+        assert_eq!(dropped, [1, 2, 3, 4, 5, 6, 7, 8]);
+    }
+
+    #[test]
+    fn try_from_iter_under_exact_and_over_capacity() {
+        let mut buf = [MaybeUninit::<u32>::uninit(); 8];
+        let heap = SliceHeap::<u32>::try_from_iter(&mut buf[..], [5, 2, 7]).unwrap();
+        assert_eq!(heap.len(), 3);
+        assert!(heap.is_valid_heap());
+
+        let mut buf = [MaybeUninit::<u32>::uninit(); 8];
+        let heap = SliceHeap::<u32>::try_from_iter(&mut buf[..], 0..8).unwrap();
+        assert!(heap.is_full());
+        assert!(heap.is_valid_heap());
+
+        let mut buf = [MaybeUninit::<u32>::uninit(); 8];
+        let (heap, rest) = SliceHeap::<u32>::try_from_iter(&mut buf[..], 0..11).unwrap_err();
+        assert!(heap.is_full());
+        assert!(heap.is_valid_heap());
+        assert!(heap.into_sorted_vec().iter().copied().eq(0..8));
+        assert!(rest.eq(8..11));
     }
 
     #[test]
@@ -641,7 +5678,7 @@ mod tests {
             }
         }
 
-        let mut backing_array = [MaybeUninit::<usize>::uninit(); 16];
+        let mut backing_array = [MaybeUninit::<usize>::uninit(); 24];
         let drop_log = RefCell::new(crate::collections::SliceVec::<_>::from(
             &mut backing_array[..],
         ));
@@ -681,18 +5718,34 @@ mod tests {
             });
         }
 
+        let mut drain_iter = heap.drain_sorted_rev();
+        assert_eq!(drain_iter.next().unwrap().value, 1);
+        assert_eq!(drain_iter.next().unwrap().value, 2);
+        assert_eq!(drop_log.borrow().len(), 10);
+
+        drop(drain_iter);
+        assert_eq!(drop_log.borrow().len(), 16);
+        assert_eq!(heap.len(), 0);
+
+        for i in 1..=8 {
+            heap.push(Droppable {
+                value: i,
+                log: &drop_log,
+            });
+        }
+
         let mut into_iter = heap.into_iter_sorted();
         assert_eq!(into_iter.next().unwrap().value, 8);
         assert_eq!(into_iter.next().unwrap().value, 7);
         assert_eq!(into_iter.next().unwrap().value, 6);
-        assert_eq!(drop_log.borrow().len(), 11);
+        assert_eq!(drop_log.borrow().len(), 19);
 
         drop(into_iter);
-        assert_eq!(drop_log.borrow().len(), 16);
+        assert_eq!(drop_log.borrow().len(), 24);
 
         assert_eq!(
             drop_log.borrow().as_slice(),
-            &[8, 7, 6, 5, 4, 3, 2, 1, 8, 7, 6, 5, 4, 3, 2, 1]
+            &[8, 7, 6, 5, 4, 3, 2, 1, 1, 2, 3, 4, 5, 6, 7, 8, 8, 7, 6, 5, 4, 3, 2, 1]
         );
     }
 }
@@ -14,6 +14,7 @@ use crate::storage::{
     buffer_too_large_for_index_type, mut_ptr_at_index, normalize_range, ptr_at_index, ArrayLayout,
     Capacity, Storage,
 };
+use crate::CapacityError;
 
 /// A double-ended queue implemented with a ring buffer.
 ///
@@ -46,7 +47,7 @@ impl<T, S: Storage<ArrayLayout<T>>, I: Capacity> From<S> for Deque<T, S, I> {
     /// This may panic if the index type I cannot represent `buf.capacity()`.
     fn from(buf: S) -> Self {
         if buf.capacity() > I::MAX_REPRESENTABLE {
-            buffer_too_large_for_index_type::<I>();
+            buffer_too_large_for_index_type::<I>(buf.capacity());
         }
 
         Deque {
@@ -282,8 +283,8 @@ impl<T, S: Storage<ArrayLayout<T>>, I: Capacity> Deque<T, S, I> {
         Some(result)
     }
 
-    /// Prepends an element to the front of the `Deque`, returning `Err(value)`
-    /// if it is already full.
+    /// Prepends an element to the front of the `Deque`, returning it back in a
+    /// [`CapacityError`] if the `Deque` is already full.
     ///
     /// # Examples
     /// ```
@@ -292,11 +293,11 @@ impl<T, S: Storage<ArrayLayout<T>>, I: Capacity> Deque<T, S, I> {
     /// assert!(deque.try_push_front(1).is_ok());
     /// assert!(deque.try_push_front(2).is_ok());
     /// assert!(deque.try_push_front(3).is_ok());
-    /// assert_eq!(deque.try_push_front(4), Err(4));
+    /// assert_eq!(deque.try_push_front(4).unwrap_err().into_inner(), 4);
     /// ```
-    pub fn try_push_front(&mut self, value: T) -> Result<(), T> {
+    pub fn try_push_front(&mut self, value: T) -> Result<(), CapacityError<T>> {
         if self.is_full() {
-            return Err(value);
+            return Err(CapacityError::with_value(value));
         }
 
         let idx = (self.front.as_usize() + self.capacity() - 1) % self.capacity();
@@ -342,8 +343,8 @@ impl<T, S: Storage<ArrayLayout<T>>, I: Capacity> Deque<T, S, I> {
         result
     }
 
-    /// Appends an element to the back of the `Deque`, returning `Err(value)`
-    /// if it is already full.
+    /// Appends an element to the back of the `Deque`, returning it back in a
+    /// [`CapacityError`] if the `Deque` is already full.
     ///
     /// # Examples
     /// ```
@@ -352,11 +353,11 @@ impl<T, S: Storage<ArrayLayout<T>>, I: Capacity> Deque<T, S, I> {
     /// assert!(deque.try_push_back(1).is_ok());
     /// assert!(deque.try_push_back(2).is_ok());
     /// assert!(deque.try_push_back(3).is_ok());
-    /// assert_eq!(deque.try_push_back(4), Err(4));
+    /// assert_eq!(deque.try_push_back(4).unwrap_err().into_inner(), 4);
     /// ```
-    pub fn try_push_back(&mut self, value: T) -> Result<(), T> {
+    pub fn try_push_back(&mut self, value: T) -> Result<(), CapacityError<T>> {
         if self.is_full() {
-            return Err(value);
+            return Err(CapacityError::with_value(value));
         }
 
         let end = self.physical_index_unchecked(self.len);
@@ -552,9 +553,9 @@ impl<T, S: Storage<ArrayLayout<T>>, I: Capacity> Deque<T, S, I> {
     /// assert!(deque.try_insert(1, 'd').is_ok());
     /// assert_eq!(deque, &['a', 'd', 'b', 'c']);
     /// ```
-    pub fn try_insert(&mut self, index: I, value: T) -> Result<(), T> {
+    pub fn try_insert(&mut self, index: I, value: T) -> Result<(), CapacityError<T>> {
         if self.is_full() {
-            return Err(value);
+            return Err(CapacityError::with_value(value));
         }
 
         let index = index.as_usize();
@@ -1883,7 +1884,7 @@ impl<T, I: Capacity> crate::collections::AllocDeque<T, I> {
     pub fn with_capacity(capacity: I) -> Self {
         let cap = capacity.as_usize();
         if capacity != I::from_usize(cap) {
-            buffer_too_large_for_index_type::<I>();
+            crate::storage::capacity_too_large_for_usize(capacity);
         }
 
         Deque {
@@ -1920,7 +1921,7 @@ impl<T, I: Capacity, const C: usize> Deque<T, [core::mem::MaybeUninit<T>; C], I>
     #[inline]
     pub fn new() -> Self {
         if C > I::MAX_REPRESENTABLE {
-            buffer_too_large_for_index_type::<I>();
+            buffer_too_large_for_index_type::<I>(C);
         }
 
         Deque {
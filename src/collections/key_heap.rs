@@ -0,0 +1,372 @@
+//! A fixed-capacity priority queue of references, ordered by cached keys.
+//!
+//! Each reference is stored together with a key computed once on insertion,
+//! so that comparisons never have to re-derive it from the referenced item.
+//! This is useful when the ordering key is an expensive projection, and avoids
+//! wrapping each item in a newtype with a custom `Ord` implementation.
+
+use core::cmp::Ordering;
+use core::fmt::{self, Debug, Formatter};
+use core::mem::MaybeUninit;
+
+use crate::collections::binary_heap::BinaryHeap;
+use crate::storage::{ArrayLayout, Capacity, Storage};
+use crate::CapacityError;
+
+/// A reference stored in a [`KeyHeap`], together with its cached key.
+///
+/// Entries are compared by their keys only.
+pub struct KeyEntry<'a, T: ?Sized, K> {
+    key: K,
+    item: &'a T,
+}
+
+impl<'a, T: ?Sized, K> KeyEntry<'a, T, K> {
+    /// Returns the referenced item.
+    #[inline]
+    pub fn item(&self) -> &'a T {
+        self.item
+    }
+
+    /// Returns a reference to the cached key.
+    #[inline]
+    pub fn key(&self) -> &K {
+        &self.key
+    }
+}
+
+impl<T: ?Sized, K: Clone> Clone for KeyEntry<'_, T, K> {
+    fn clone(&self) -> Self {
+        KeyEntry {
+            key: self.key.clone(),
+            item: self.item,
+        }
+    }
+}
+
+impl<T: ?Sized + Debug, K: Debug> Debug for KeyEntry<'_, T, K> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("KeyEntry")
+            .field("key", &self.key)
+            .field("item", &self.item)
+            .finish()
+    }
+}
+
+impl<T: ?Sized, K: PartialEq> PartialEq for KeyEntry<'_, T, K> {
+    fn eq(&self, other: &Self) -> bool {
+        self.key == other.key
+    }
+}
+
+impl<T: ?Sized, K: Eq> Eq for KeyEntry<'_, T, K> {}
+
+impl<T: ?Sized, K: PartialOrd> PartialOrd for KeyEntry<'_, T, K> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.key.partial_cmp(&other.key)
+    }
+}
+
+impl<T: ?Sized, K: Ord> Ord for KeyEntry<'_, T, K> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.key.cmp(&other.key)
+    }
+}
+
+/// A fixed-capacity priority queue of references to items of type `T`,
+/// ordered by keys of type `K`.
+///
+/// Generic over the storage buffer type `S` and the index type `I`.
+///
+/// See the [module-level documentation](crate::collections::key_heap) for more.
+///
+/// # Examples
+/// ```
+/// let words = ["apple", "fig", "banana", "kiwi"];
+///
+/// let mut heap = coca::collections::InlineKeyHeap::<str, usize, 4>::new();
+/// for w in &words {
+///     heap.push_with_key(w, w.len());
+/// }
+///
+/// assert_eq!(heap.pop(), Some("banana"));
+/// assert_eq!(heap.pop(), Some("apple"));
+/// assert_eq!(heap.pop(), Some("kiwi"));
+/// assert_eq!(heap.pop(), Some("fig"));
+/// assert_eq!(heap.pop(), None);
+/// ```
+pub struct KeyHeap<'a, T: ?Sized, K, S, I = usize>
+where
+    K: Ord,
+    S: Storage<ArrayLayout<KeyEntry<'a, T, K>>>,
+    I: Capacity,
+{
+    heap: BinaryHeap<KeyEntry<'a, T, K>, S, I>,
+}
+
+impl<'a, T: ?Sized, K, S, I> From<S> for KeyHeap<'a, T, K, S, I>
+where
+    K: Ord,
+    S: Storage<ArrayLayout<KeyEntry<'a, T, K>>>,
+    I: Capacity,
+{
+    /// Converts a contiguous block of memory into an empty `KeyHeap`.
+    fn from(buf: S) -> Self {
+        KeyHeap {
+            heap: BinaryHeap::from(buf),
+        }
+    }
+}
+
+impl<'a, T: ?Sized, K, S, I> KeyHeap<'a, T, K, S, I>
+where
+    K: Ord,
+    S: Storage<ArrayLayout<KeyEntry<'a, T, K>>>,
+    I: Capacity,
+{
+    /// Returns the number of references the heap can hold.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.heap.capacity()
+    }
+
+    /// Returns the number of references in the heap.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    /// Returns `true` if the heap contains no references.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+
+    /// Returns `true` if the heap is at capacity.
+    #[inline]
+    pub fn is_full(&self) -> bool {
+        self.heap.is_full()
+    }
+
+    /// Returns the item with the greatest key, or [`None`] if the heap is empty.
+    #[inline]
+    pub fn peek(&self) -> Option<&'a T> {
+        self.heap.peek().map(KeyEntry::item)
+    }
+
+    /// Returns the item with the greatest key along with a reference to that
+    /// key, or [`None`] if the heap is empty.
+    #[inline]
+    pub fn peek_with_key(&self) -> Option<(&'a T, &K)> {
+        self.heap.peek().map(|e| (e.item, &e.key))
+    }
+
+    /// Pushes a reference onto the heap, ordered by the given key.
+    ///
+    /// # Panics
+    /// Panics if the heap is already at capacity. See
+    /// [`try_push_with_key`](KeyHeap::try_push_with_key) for a checked
+    /// version that never panics.
+    #[track_caller]
+    pub fn push_with_key(&mut self, item: &'a T, key: K) {
+        #[cold]
+        #[inline(never)]
+        #[track_caller]
+        fn assert_failed() -> ! {
+            panic!("key heap is already at capacity")
+        }
+
+        if self.try_push_with_key(item, key).is_err() {
+            assert_failed();
+        }
+    }
+
+    /// Pushes a reference onto the heap, ordered by the given key, returning
+    /// both back in a [`CapacityError`] if the heap is already full.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut heap = coca::collections::InlineKeyHeap::<u32, i32, 1>::new();
+    /// assert!(heap.try_push_with_key(&7, -7).is_ok());
+    ///
+    /// let err = heap.try_push_with_key(&3, -3).unwrap_err();
+    /// assert_eq!(err.into_inner(), (&3, -3));
+    /// ```
+    pub fn try_push_with_key(
+        &mut self,
+        item: &'a T,
+        key: K,
+    ) -> Result<(), CapacityError<(&'a T, K)>> {
+        self.heap.try_push(KeyEntry { key, item }).map_err(|e| {
+            let KeyEntry { key, item } = e.into_inner();
+            CapacityError::with_value((item, key))
+        })
+    }
+
+    /// Removes the item with the greatest key from the heap and returns it,
+    /// or [`None`] if the heap is empty.
+    #[inline]
+    pub fn pop(&mut self) -> Option<&'a T> {
+        self.heap.pop().map(|e| e.item)
+    }
+
+    /// Removes the item with the greatest key from the heap and returns it
+    /// together with that key, or [`None`] if the heap is empty.
+    #[inline]
+    pub fn pop_with_key(&mut self) -> Option<(&'a T, K)> {
+        self.heap.pop().map(|e| (e.item, e.key))
+    }
+
+    /// Returns an iterator visiting all entries in the heap in arbitrary order.
+    pub fn iter(&self) -> impl Iterator<Item = &KeyEntry<'a, T, K>> {
+        self.heap.iter()
+    }
+
+    /// Drops all references from the heap.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.heap.clear();
+    }
+}
+
+impl<'a, T: ?Sized, K, S, I> Extend<(&'a T, K)> for KeyHeap<'a, T, K, S, I>
+where
+    K: Ord,
+    S: Storage<ArrayLayout<KeyEntry<'a, T, K>>>,
+    I: Capacity,
+{
+    /// Pushes all references with their keys yielded by an iterator onto the heap.
+    ///
+    /// # Panics
+    /// Panics if the iterator yields more items than the heap has room for.
+    fn extend<It: IntoIterator<Item = (&'a T, K)>>(&mut self, iter: It) {
+        iter.into_iter()
+            .for_each(|(item, key)| self.push_with_key(item, key));
+    }
+}
+
+impl<'a, T: ?Sized + Debug, K: Debug, S, I> Debug for KeyHeap<'a, T, K, S, I>
+where
+    K: Ord,
+    S: Storage<ArrayLayout<KeyEntry<'a, T, K>>>,
+    I: Capacity,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.heap.iter()).finish()
+    }
+}
+
+impl<'a, T: ?Sized, K: Ord, I: Capacity, const C: usize>
+    KeyHeap<'a, T, K, [MaybeUninit<KeyEntry<'a, T, K>>; C], I>
+{
+    /// Constructs a new, empty `KeyHeap` backed by an inline array.
+    ///
+    /// # Panics
+    /// Panics if `C` cannot be represented as a value of type `I`.
+    pub fn new() -> Self {
+        KeyHeap {
+            heap: BinaryHeap::new(),
+        }
+    }
+}
+
+impl<'a, T: ?Sized, K: Ord, I: Capacity, const C: usize> Default
+    for KeyHeap<'a, T, K, [MaybeUninit<KeyEntry<'a, T, K>>; C], I>
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::collections::InlineKeyHeap;
+
+    #[test]
+    fn strings_by_length() {
+        let words = ["a", "abcd", "ab", "abcde", "abc", "xy"];
+
+        let mut heap = InlineKeyHeap::<&str, usize, 6, u8>::new();
+        heap.extend(words.iter().map(|w| (w, w.len())));
+        assert!(heap.is_full());
+        assert_eq!(heap.peek_with_key(), Some((&"abcde", &5)));
+
+        let mut lengths = [0; 6];
+        for l in lengths.iter_mut() {
+            let (w, key) = heap.pop_with_key().unwrap();
+            assert_eq!(w.len(), key);
+            *l = key;
+        }
+        assert_eq!(lengths, [5, 4, 3, 2, 2, 1]);
+        assert!(heap.pop().is_none());
+    }
+
+    #[test]
+    fn cached_keys_are_compared_but_never_cloned() {
+        use core::cell::Cell;
+        use core::cmp::Ordering;
+
+        struct CountingKey<'c> {
+            value: u32,
+            clones: &'c Cell<usize>,
+            comparisons: &'c Cell<usize>,
+        }
+
+        impl Clone for CountingKey<'_> {
+            fn clone(&self) -> Self {
+                self.clones.set(self.clones.get() + 1);
+                CountingKey { ..*self }
+            }
+        }
+
+        impl PartialEq for CountingKey<'_> {
+            fn eq(&self, other: &Self) -> bool {
+                self.cmp(other) == Ordering::Equal
+            }
+        }
+
+        impl Eq for CountingKey<'_> {}
+
+        impl PartialOrd for CountingKey<'_> {
+            fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+
+        impl Ord for CountingKey<'_> {
+            fn cmp(&self, other: &Self) -> Ordering {
+                self.comparisons.set(self.comparisons.get() + 1);
+                self.value.cmp(&other.value)
+            }
+        }
+
+        let clones = Cell::new(0);
+        let comparisons = Cell::new(0);
+        let mut items = [0; 32];
+        for (x, i) in items.iter_mut().zip(0u32..) {
+            *x = i * 37 % 32;
+        }
+
+        let mut heap = InlineKeyHeap::<u32, CountingKey<'_>, 32>::new();
+        heap.extend(items.iter().map(|x| {
+            let key = CountingKey {
+                value: x % 10,
+                clones: &clones,
+                comparisons: &comparisons,
+            };
+            (x, key)
+        }));
+        assert!(comparisons.get() > 0);
+
+        let mut last = u32::MAX;
+        let mut popped = 0;
+        while let Some((x, key)) = heap.pop_with_key() {
+            assert_eq!(key.value, x % 10);
+            assert!(key.value <= last);
+            last = key.value;
+            popped += 1;
+        }
+        assert_eq!(popped, items.len());
+        assert_eq!(clones.get(), 0);
+    }
+}
@@ -8,6 +8,7 @@ use core::marker::PhantomData;
 use core::mem::MaybeUninit;
 
 use crate::storage::{Capacity, LayoutSpec, Storage};
+use crate::CapacityError;
 
 use self::Entry::{Occupied, Vacant};
 
@@ -347,7 +348,8 @@ impl<K, V, S: Storage<ListMapLayout<K, V>>, I: Capacity> ListMap<K, V, S, I> {
 
     /// Gets the given key's corresponding [`Entry`] in the map for in-place manipulation.
     ///
-    /// Returns [`Err(key)`] if the map is full and does not contain the given key.
+    /// Returns a [`CapacityError`] containing `key` if the map is full and
+    /// does not contain the given key.
     ///
     /// # Examples
     /// ```
@@ -366,7 +368,7 @@ impl<K, V, S: Storage<ListMapLayout<K, V>>, I: Capacity> ListMap<K, V, S, I> {
     /// assert_eq!(letters.get(&'o'), Some(&2));
     /// assert_eq!(letters.get(&'u'), None);
     /// ```
-    pub fn try_entry(&mut self, key: K) -> Result<Entry<'_, K, V, S, I>, K>
+    pub fn try_entry(&mut self, key: K) -> Result<Entry<'_, K, V, S, I>, CapacityError<K>>
     where
         K: Eq,
     {
@@ -377,7 +379,7 @@ impl<K, V, S: Storage<ListMapLayout<K, V>>, I: Capacity> ListMap<K, V, S, I> {
                 map: self,
             }))
         } else if self.is_full() {
-            Err(key)
+            Err(CapacityError::with_value(key))
         } else {
             Ok(Vacant(VacantEntry { key, map: self }))
         }
@@ -518,7 +520,8 @@ impl<K, V, S: Storage<ListMapLayout<K, V>>, I: Capacity> ListMap<K, V, S, I> {
     /// Inserts a key-value pair into the map.
     ///
     /// If the map did not have this key present, `Ok(None)` is returned if the
-    /// key-value pair is inserted, or [`Err((key, value))`] if the map is full.
+    /// key-value pair is inserted, or a [`CapacityError`] containing
+    /// `(key, value)` if the map is full.
     ///
     /// If the map did have this key present, the value is updated, and the
     /// old value is returned. The key is not updated though; this matters for
@@ -536,16 +539,16 @@ impl<K, V, S: Storage<ListMapLayout<K, V>>, I: Capacity> ListMap<K, V, S, I> {
     /// map.insert("c", 19);
     /// map.insert("d", 8);
     /// assert_eq!(map.is_full(), true);
-    /// assert_eq!(map.try_insert("e", 0), Err(("e", 0)));
+    /// assert_eq!(map.try_insert("e", 0).unwrap_err().into_inner(), ("e", 0));
     /// ```
-    pub fn try_insert(&mut self, key: K, value: V) -> Result<Option<V>, (K, V)>
+    pub fn try_insert(&mut self, key: K, value: V) -> Result<Option<V>, CapacityError<(K, V)>>
     where
         K: Eq,
     {
         if let Some((idx, _)) = self.lookup(&key) {
             return Ok(Some(core::mem::replace(&mut self.values_mut()[idx], value)));
         } else if self.is_full() {
-            return Err((key, value));
+            return Err(CapacityError::with_value((key, value)));
         }
 
         let idx = self.len();
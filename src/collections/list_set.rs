@@ -7,6 +7,7 @@ use core::slice::Iter;
 
 use crate::collections::vec::{Drain, Vec};
 use crate::storage::{ArrayLayout, Capacity, InlineStorage, Storage};
+use crate::CapacityError;
 
 /// A set implemented with a vector, using a linear scan to find a given value.
 ///
@@ -448,8 +449,8 @@ impl<T: Eq, S: Storage<ArrayLayout<T>>, I: Capacity> ListSet<T, S, I> {
     ///
     /// Returns `Ok(false)` if the set already contained a value equal to
     /// the given value. Otherwise, returns `Ok(true)` if the given value
-    /// was successfully inserted, or `Err(value)` if the remaining space
-    /// is insufficient.
+    /// was successfully inserted, or a [`CapacityError`] containing `value`
+    /// if the remaining space is insufficient.
     ///
     /// # Examples
     /// ```
@@ -463,10 +464,10 @@ impl<T: Eq, S: Storage<ArrayLayout<T>>, I: Capacity> ListSet<T, S, I> {
     ///
     /// assert_eq!(set.try_insert(3), Ok(true));
     /// assert_eq!(set.try_insert(4), Ok(true));
-    /// assert_eq!(set.try_insert(5), Err(5));
+    /// assert_eq!(set.try_insert(5).unwrap_err().into_inner(), 5);
     /// assert_eq!(set.len(), 4);
     /// ```
-    pub fn try_insert(&mut self, value: T) -> Result<bool, T> {
+    pub fn try_insert(&mut self, value: T) -> Result<bool, CapacityError<T>> {
         if self.contains(&value) {
             return Ok(false);
         }
@@ -499,8 +500,8 @@ impl<T: Eq, S: Storage<ArrayLayout<T>>, I: Capacity> ListSet<T, S, I> {
     /// Inserts a value into the set without checking if it was already part of
     /// the set.
     ///
-    /// Returns a reference to the inserted value, or `Err(value)` if the set
-    /// is already full.
+    /// Returns a reference to the inserted value, or a [`CapacityError`]
+    /// containing `value` if the set is already full.
     ///
     /// It is a logic error to insert a duplicate element, and the behavior
     /// of the resulting set is unspecified, though it is guaranteed to be
@@ -510,9 +511,9 @@ impl<T: Eq, S: Storage<ArrayLayout<T>>, I: Capacity> ListSet<T, S, I> {
     /// because it does not perform a lookup before insertion. This is useful
     /// during initial population of the set, e.g. when constructing a set from
     /// another set, which guarantees unique values.
-    pub fn try_insert_unique_unchecked(&mut self, value: T) -> Result<&T, T> {
+    pub fn try_insert_unique_unchecked(&mut self, value: T) -> Result<&T, CapacityError<T>> {
         if self.vec.is_full() {
-            Err(value)
+            Err(CapacityError::with_value(value))
         } else {
             self.vec.push(value);
             self.vec.last().ok_or_else(|| unreachable!())
@@ -538,8 +539,8 @@ impl<T: Eq, S: Storage<ArrayLayout<T>>, I: Capacity> ListSet<T, S, I> {
     /// being identical.
     ///
     /// Returns the replaced value, or `Ok(None)` if the value was successfully
-    /// inserted without replacing any other element, or `Err(value)` if the
-    /// set was already full.
+    /// inserted without replacing any other element, or a [`CapacityError`]
+    /// containing `value` if the set was already full.
     ///
     /// # Examples
     /// ```
@@ -567,7 +568,7 @@ impl<T: Eq, S: Storage<ArrayLayout<T>>, I: Capacity> ListSet<T, S, I> {
     ///
     /// assert!(set.try_replace(Foo(4, 10)).is_err());
     /// ```
-    pub fn try_replace(&mut self, value: T) -> Result<Option<T>, T> {
+    pub fn try_replace(&mut self, value: T) -> Result<Option<T>, CapacityError<T>> {
         if let Some((idx, _)) = self
             .vec
             .iter()
@@ -576,7 +577,7 @@ impl<T: Eq, S: Storage<ArrayLayout<T>>, I: Capacity> ListSet<T, S, I> {
         {
             Ok(Some(self.vec.replace(I::from_usize(idx), value)))
         } else if self.is_full() {
-            Err(value)
+            Err(CapacityError::with_value(value))
         } else {
             self.vec.push(value);
             Ok(None)
@@ -3,10 +3,15 @@
 pub mod binary_heap;
 pub mod cache;
 pub mod deque;
+pub mod key_heap;
 pub mod list_map;
 pub mod list_set;
 pub mod option_group;
 pub mod pool;
+pub mod running_median;
+#[cfg(feature = "alloc")]
+#[cfg_attr(docs_rs, doc(cfg(feature = "alloc")))]
+pub mod small_heap;
 pub mod vec;
 
 use crate::storage::{ArenaStorage, ArrayLayout, InlineStorage, SliceStorage};
@@ -14,12 +19,14 @@ use crate::storage::{ArenaStorage, ArrayLayout, InlineStorage, SliceStorage};
 use binary_heap::BinaryHeap;
 use cache::{CacheTable, LruCache2, UnitCache};
 use deque::Deque;
+use key_heap::{KeyEntry, KeyHeap};
 use list_map::{ListMap, ListMapLayout};
 use list_set::ListSet;
 use option_group::OptionGroup;
 use pool::direct::{DirectPool, DirectPoolLayout};
 use pool::packed::{PackedPool, PackedPoolLayout};
 use pool::DefaultHandle;
+use running_median::RunningMedian;
 use vec::Vec;
 
 /// A binary heap using a mutable slice for storage.
@@ -62,6 +69,32 @@ pub type ArenaHeap<'a, T, I = usize> = BinaryHeap<T, ArenaStorage<'a, ArrayLayou
 /// ```
 pub type InlineHeap<T, const C: usize, I = usize> = BinaryHeap<T, InlineStorage<T, C>, I>;
 
+/// A priority queue of references ordered by cached keys, using an inline
+/// array for storage.
+///
+/// # Examples
+/// ```
+/// let mut heap = coca::collections::InlineKeyHeap::<str, usize, 2>::new();
+/// heap.push_with_key("hello", 5);
+/// heap.push_with_key("hi", 2);
+/// assert_eq!(heap.pop(), Some("hello"));
+/// ```
+pub type InlineKeyHeap<'a, T, K, const C: usize, I = usize> =
+    KeyHeap<'a, T, K, InlineStorage<KeyEntry<'a, T, K>, C>, I>;
+
+/// A streaming median tracker using two inline arrays for storage, able to
+/// track up to `2 * C` values.
+///
+/// # Examples
+/// ```
+/// let mut median = coca::collections::InlineRunningMedian::<u32, 2, u8>::new();
+/// median.extend([4, 2, 3]);
+/// assert_eq!(median.median(), Some(&3));
+/// assert_eq!(median.capacity(), 4);
+/// ```
+pub type InlineRunningMedian<T, const C: usize, I = usize> =
+    RunningMedian<T, InlineStorage<T, C>, InlineStorage<core::cmp::Reverse<T>, C>, I>;
+
 #[cfg(feature = "alloc")]
 #[cfg_attr(docs_rs, doc(cfg(feature = "alloc")))]
 /// A binary heap using a heap-allocated slice for storage.
@@ -299,7 +332,7 @@ pub type SliceDeque<'a, T, I = usize> = Deque<T, SliceStorage<'a, T>, I>;
 /// deque.push_back('d');
 ///
 /// assert_eq!(deque, &['a', 'b', 'c', 'd']);
-/// assert_eq!(deque.try_push_back('e'), Err('e'));
+/// assert_eq!(deque.try_push_back('e').unwrap_err().into_inner(), 'e');
 /// # Some(())
 /// # }
 /// # assert!(test().is_some());
@@ -322,7 +355,7 @@ pub type ArenaDeque<'a, T, I = usize> = Deque<T, ArenaStorage<'a, ArrayLayout<T>
 /// deque.push_back('d');
 ///
 /// assert_eq!(deque, &['a', 'b', 'c', 'd']);
-/// assert_eq!(deque.try_push_back('e'), Err('e'));
+/// assert_eq!(deque.try_push_back('e').unwrap_err().into_inner(), 'e');
 /// ```
 pub type AllocDeque<T, I = usize> = Deque<T, crate::storage::AllocStorage<ArrayLayout<T>>, I>;
 
@@ -461,7 +494,7 @@ pub type DirectArenaPool<'src, T, H = DefaultHandle> =
 /// pool.insert(2);
 /// pool.insert(3);
 /// pool.insert(4);
-/// assert_eq!(pool.try_insert(5), Err(5));
+/// assert_eq!(pool.try_insert(5).unwrap_err().into_inner(), 5);
 /// ```
 #[cfg(feature = "alloc")]
 #[cfg_attr(docs_rs, doc(cfg(feature = "alloc")))]
@@ -12,6 +12,7 @@ use core::ops::{Index, IndexMut};
 
 use super::{buffer_too_large_for_handle_type, DebugEntry, DefaultHandle, Handle};
 use crate::storage::{Capacity, LayoutSpec, Storage};
+use crate::CapacityError;
 
 union Slot<T, I: Capacity> {
     item: ManuallyDrop<T>,
@@ -247,12 +248,13 @@ impl<T, S: Storage<DirectPoolLayout<T, H>>, H: Handle> DirectPool<T, S, H> {
 
     /// Inserts a value into the pool, returning a unique handle to access it.
     ///
-    /// Returns `Err(value)` if the pool is already at capacity.
+    /// Returns a [`CapacityError`] containing `value` if the pool is already
+    /// at capacity.
     ///
     /// # Examples
     /// ```
     /// # use coca::collections::{pool::DefaultHandle, DirectArenaPool};
-    /// # fn test() -> Result<(), u128> {
+    /// # fn test() -> Result<(), coca::CapacityError<u128>> {
     /// # let mut backing = [core::mem::MaybeUninit::uninit(); 1024];
     /// # let mut arena = coca::arena::Arena::from(&mut backing[..]);
     /// let mut pool: DirectArenaPool<u128, DefaultHandle> = arena.with_capacity(8);
@@ -262,10 +264,10 @@ impl<T, S: Storage<DirectPoolLayout<T, H>>, H: Handle> DirectPool<T, S, H> {
     /// # }
     /// # assert!(test().is_ok());
     /// ```
-    pub fn try_insert(&mut self, value: T) -> Result<H, T> {
+    pub fn try_insert(&mut self, value: T) -> Result<H, CapacityError<T>> {
         let insert_position = self.next_free_slot.as_usize();
         if insert_position == Self::FREE_LIST_SENTINEL {
-            return Err(value);
+            return Err(CapacityError::with_value(value));
         }
 
         self.len = H::Index::from_usize(self.len() + 1);
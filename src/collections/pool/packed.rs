@@ -13,6 +13,7 @@ use core::ops::{Index, IndexMut};
 
 use super::{buffer_too_large_for_handle_type, DebugEntry, DefaultHandle, Handle};
 use crate::storage::{Capacity, LayoutSpec, Storage};
+use crate::CapacityError;
 
 /// The [`LayoutSpec`] for a [`PackedPool`].
 pub struct PackedPoolLayout<T, H>(PhantomData<(T, H)>);
@@ -434,7 +435,8 @@ impl<T, S: Storage<PackedPoolLayout<T, H>>, H: Handle> PackedPool<T, S, H> {
 
     /// Inserts a value into the pool, returning a unique handle to access it.
     ///
-    /// Returns `Err(value)` if the pool is already at capacity.
+    /// Returns a [`CapacityError`] containing `value` if the pool is already
+    /// at capacity.
     ///
     /// # Examples
     /// ```
@@ -443,10 +445,10 @@ impl<T, S: Storage<PackedPoolLayout<T, H>>, H: Handle> PackedPool<T, S, H> {
     /// let h = pool.try_insert(42).expect("failed to insert into an empty pool?!");
     /// assert_eq!(pool.get(h), Some(&42));
     /// ```
-    pub fn try_insert(&mut self, value: T) -> Result<H, T> {
+    pub fn try_insert(&mut self, value: T) -> Result<H, CapacityError<T>> {
         let insert_position = self.next_free_slot.as_usize();
         if insert_position == Self::FREE_LIST_SENTINEL {
-            return Err(value);
+            return Err(CapacityError::with_value(value));
         }
 
         let packed_insert_position = self.len;
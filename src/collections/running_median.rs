@@ -0,0 +1,332 @@
+//! A streaming median tracker built on two binary heaps.
+//!
+//! The lower half of all values seen so far is kept in a max-heap, the upper
+//! half in a min-heap, so that the median is always found at the top of one
+//! of them. Inserting a value takes O(log(n)) time, and retrieving the median
+//! takes O(1) time.
+
+use core::cmp::Reverse;
+use core::fmt::{self, Debug, Formatter};
+use core::mem::MaybeUninit;
+
+use crate::collections::binary_heap::BinaryHeap;
+use crate::storage::{ArrayLayout, Capacity, Storage};
+use crate::CapacityError;
+
+/// A data structure for tracking the median of a stream of values.
+///
+/// Generic over the storage buffer types `L` and `U` of the lower and upper
+/// halves, and the index type `I`.
+///
+/// See the [module-level documentation](crate::collections::running_median)
+/// for more.
+///
+/// # Examples
+/// ```
+/// use coca::collections::InlineRunningMedian;
+///
+/// let mut median = InlineRunningMedian::<u32, 4>::new();
+/// assert_eq!(median.median(), None);
+///
+/// median.push(5);
+/// assert_eq!(median.median(), Some(&5));
+/// median.push(1);
+/// assert_eq!(median.median_pair(), Some((&1, &5)));
+/// median.push(3);
+/// assert_eq!(median.median(), Some(&3));
+/// ```
+pub struct RunningMedian<T, L, U, I = usize>
+where
+    T: Ord,
+    L: Storage<ArrayLayout<T>>,
+    U: Storage<ArrayLayout<Reverse<T>>>,
+    I: Capacity,
+{
+    lower: BinaryHeap<T, L, I>,
+    upper: BinaryHeap<Reverse<T>, U, I>,
+}
+
+impl<T, L, U, I> RunningMedian<T, L, U, I>
+where
+    T: Ord,
+    L: Storage<ArrayLayout<T>>,
+    U: Storage<ArrayLayout<Reverse<T>>>,
+    I: Capacity,
+{
+    /// Constructs a new, empty `RunningMedian` from two storage blocks, one
+    /// for each half of the values.
+    ///
+    /// Since the lower half is allowed to hold one more value than the upper
+    /// half, up to `min(2 * lower.capacity(), 2 * upper.capacity() + 1)`
+    /// values can be tracked.
+    ///
+    /// # Panics
+    /// Panics if either storage block's capacity cannot be represented by `I`.
+    ///
+    /// # Examples
+    /// ```
+    /// use core::mem::MaybeUninit;
+    /// use coca::collections::running_median::RunningMedian;
+    ///
+    /// let mut lower = [MaybeUninit::uninit(); 3];
+    /// let mut upper = [MaybeUninit::uninit(); 2];
+    /// let mut median = RunningMedian::<u32, _, _>::from_storage(&mut lower[..], &mut upper[..]);
+    /// assert_eq!(median.capacity(), 5);
+    ///
+    /// median.extend([9, 2, 7, 4, 4]);
+    /// assert_eq!(median.median(), Some(&4));
+    /// assert!(median.try_push(0).is_err());
+    /// ```
+    pub fn from_storage(lower: L, upper: U) -> Self {
+        RunningMedian {
+            lower: BinaryHeap::from(lower),
+            upper: BinaryHeap::from(upper),
+        }
+    }
+
+    /// Returns the number of values that can be tracked.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        let lower = self.lower.capacity().saturating_mul(2);
+        let upper = self.upper.capacity().saturating_mul(2).saturating_add(1);
+        usize::min(lower, upper)
+    }
+
+    /// Returns the number of values seen so far.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.lower.len() + self.upper.len()
+    }
+
+    /// Returns `true` if no values have been seen so far.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.lower.is_empty()
+    }
+
+    /// Returns `true` if no more values can be tracked.
+    #[inline]
+    pub fn is_full(&self) -> bool {
+        self.len() >= self.capacity()
+    }
+
+    /// Returns a reference to the median of all values seen so far, or
+    /// [`None`] if no values have been seen.
+    ///
+    /// If an even number of values has been seen, this is the lesser of the
+    /// two middle values; see [`median_pair`](RunningMedian::median_pair)
+    /// for retrieving both.
+    #[inline]
+    pub fn median(&self) -> Option<&T> {
+        self.lower.peek()
+    }
+
+    /// Returns references to the two middle values of all values seen so
+    /// far, or [`None`] if no values have been seen.
+    ///
+    /// If an odd number of values has been seen, both references point to
+    /// the median.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut median = coca::collections::InlineRunningMedian::<u32, 4>::new();
+    /// median.extend([1, 2, 8, 6]);
+    ///
+    /// let (lo, hi) = median.median_pair().unwrap();
+    /// assert_eq!((lo, hi), (&2, &6));
+    /// assert_eq!((lo + hi) / 2, 4);
+    /// ```
+    pub fn median_pair(&self) -> Option<(&T, &T)> {
+        let lo = self.lower.peek()?;
+        if self.lower.len() > self.upper.len() {
+            Some((lo, lo))
+        } else {
+            self.upper.peek().map(|Reverse(hi)| (lo, hi))
+        }
+    }
+
+    /// Adds a value to the tracker, returning it back in a [`CapacityError`]
+    /// if the tracker is already full.
+    pub fn try_push(&mut self, value: T) -> Result<(), CapacityError<T>> {
+        if self.is_full() {
+            return Err(CapacityError::with_value(value));
+        }
+
+        // The halves are kept balanced so that the lower half holds either
+        // as many values as the upper half, or exactly one more. Values are
+        // exchanged at the top of a half before inserting, so that neither
+        // exceeds its final length, even temporarily.
+        let lower_is_larger = self.lower.len() > self.upper.len();
+        let below_max = matches!(self.lower.peek(), Some(max) if value < *max);
+        let above_min = matches!(self.upper.peek(), Some(Reverse(min)) if *min < value);
+
+        match (below_max, lower_is_larger) {
+            (true, true) => {
+                if let Some(mut top) = self.lower.peek_mut() {
+                    let max = core::mem::replace(&mut *top, value);
+                    drop(top);
+                    self.upper.push(Reverse(max));
+                }
+            }
+            (false, true) => self.upper.push(Reverse(value)),
+            (_, false) if above_min => {
+                if let Some(mut top) = self.upper.peek_mut() {
+                    let min = core::mem::replace(&mut top.0, value);
+                    drop(top);
+                    self.lower.push(min);
+                }
+            }
+            (_, false) => self.lower.push(value),
+        }
+
+        Ok(())
+    }
+
+    /// Adds a value to the tracker.
+    ///
+    /// # Panics
+    /// Panics if the tracker is already full. See
+    /// [`try_push`](RunningMedian::try_push) for a checked version that
+    /// never panics.
+    #[track_caller]
+    pub fn push(&mut self, value: T) {
+        #[cold]
+        #[inline(never)]
+        #[track_caller]
+        fn assert_failed() -> ! {
+            panic!("running median is already at capacity")
+        }
+
+        if self.try_push(value).is_err() {
+            assert_failed();
+        }
+    }
+
+    /// Forgets all values seen so far.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.lower.clear();
+        self.upper.clear();
+    }
+}
+
+impl<T, L, U, I> Extend<T> for RunningMedian<T, L, U, I>
+where
+    T: Ord,
+    L: Storage<ArrayLayout<T>>,
+    U: Storage<ArrayLayout<Reverse<T>>>,
+    I: Capacity,
+{
+    /// Adds all values yielded by an iterator to the tracker.
+    ///
+    /// # Panics
+    /// Panics if the iterator yields more values than the tracker has room for.
+    fn extend<It: IntoIterator<Item = T>>(&mut self, iter: It) {
+        iter.into_iter().for_each(|value| self.push(value));
+    }
+}
+
+impl<T, L, U, I> Debug for RunningMedian<T, L, U, I>
+where
+    T: Ord + Debug,
+    L: Storage<ArrayLayout<T>>,
+    U: Storage<ArrayLayout<Reverse<T>>>,
+    I: Capacity,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RunningMedian")
+            .field("len", &self.len())
+            .field("median", &self.median_pair())
+            .finish()
+    }
+}
+
+impl<T: Ord, I: Capacity, const C: usize>
+    RunningMedian<T, [MaybeUninit<T>; C], [MaybeUninit<Reverse<T>>; C], I>
+{
+    /// Constructs a new, empty `RunningMedian` backed by two inline arrays,
+    /// able to track up to `2 * C` values.
+    ///
+    /// # Panics
+    /// Panics if `C` cannot be represented as a value of type `I`.
+    pub fn new() -> Self {
+        RunningMedian {
+            lower: BinaryHeap::new(),
+            upper: BinaryHeap::new(),
+        }
+    }
+}
+
+impl<T: Ord, I: Capacity, const C: usize> Default
+    for RunningMedian<T, [MaybeUninit<T>; C], [MaybeUninit<Reverse<T>>; C], I>
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::collections::InlineRunningMedian;
+
+    #[test]
+    fn running_median_of_known_sequence() {
+        let input = [5, 15, 1, 3, 2, 8, 7, 9, 10, 6, 11, 4];
+        let medians = [5, 5, 5, 3, 3, 3, 5, 5, 7, 6, 7, 6];
+        let pairs = [
+            (5, 5),
+            (5, 15),
+            (5, 5),
+            (3, 5),
+            (3, 3),
+            (3, 5),
+            (5, 5),
+            (5, 7),
+            (7, 7),
+            (6, 7),
+            (7, 7),
+            (6, 7),
+        ];
+
+        let mut median = InlineRunningMedian::<u32, 6>::new();
+        assert_eq!(median.capacity(), 12);
+        for (i, &x) in input.iter().enumerate() {
+            median.push(x);
+            assert_eq!(median.len(), i + 1);
+            assert_eq!(median.median(), Some(&medians[i]));
+            let (lo, hi) = pairs[i];
+            assert_eq!(median.median_pair(), Some((&lo, &hi)));
+        }
+
+        assert!(median.is_full());
+        assert_eq!(median.try_push(0).unwrap_err().into_inner(), 0);
+
+        median.clear();
+        assert!(median.is_empty());
+        assert_eq!(median.median(), None);
+        assert_eq!(median.median_pair(), None);
+    }
+
+    #[test]
+    fn running_median_matches_sorted_reference() {
+        use crate::collections::InlineVec;
+        use rand::{rngs::SmallRng, Rng, SeedableRng};
+
+        let mut rng = SmallRng::from_seed(crate::test_utils::RNG_SEED);
+        let mut median = InlineRunningMedian::<u32, 33>::new();
+        let mut seen = InlineVec::<u32, 66>::new();
+
+        while !median.is_full() {
+            let x = rng.gen_range(0..50);
+            median.push(x);
+            seen.push(x);
+
+            let mut sorted = seen.clone();
+            sorted.sort_unstable();
+            let lo = &sorted[(sorted.len() - 1) / 2];
+            let hi = &sorted[sorted.len() / 2];
+            assert_eq!(median.median_pair(), Some((lo, hi)));
+        }
+        assert_eq!(seen.len(), 66);
+    }
+}
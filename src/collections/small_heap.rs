@@ -0,0 +1,508 @@
+//! A priority queue that stores up to `N` items inline, and spills into
+//! heap-allocated storage beyond that.
+//!
+//! Unlike all other collection types in this crate, a [`SmallHeap`] does not
+//! have a fixed capacity: pushing an item onto a full heap moves all items
+//! into a newly allocated storage block with twice the capacity instead of
+//! failing. The first such move, away from the inline array, is called
+//! *spilling*. This is an explicit opt-in for the common case of heaps that
+//! are usually small, but may occasionally grow large; use [`InlineHeap`] or
+//! [`AllocHeap`] if the capacity should stay fixed.
+
+use core::fmt::{self, Debug, Formatter};
+use core::iter::{FromIterator, FusedIterator};
+use core::ops::{Deref, DerefMut};
+
+use crate::collections::{binary_heap, vec};
+use crate::collections::{AllocHeap, AllocVec, InlineHeap};
+use crate::storage::{AllocStorage, ArrayLayout, Capacity, InlineStorage};
+use crate::CapacityError;
+
+enum Repr<T: Ord, const N: usize, I: Capacity> {
+    Inline(InlineHeap<T, N, I>),
+    Alloc(AllocHeap<T, I>),
+}
+
+/// A binary heap storing up to `N` items inline, and spilling into
+/// heap-allocated storage when more items are pushed.
+///
+/// See the [module-level documentation](crate::collections::small_heap) for more.
+///
+/// # Examples
+/// ```
+/// use coca::collections::small_heap::SmallHeap;
+///
+/// let mut heap = SmallHeap::<u32, 2>::new();
+/// heap.push(3);
+/// heap.push(1);
+/// assert!(!heap.spilled());
+///
+/// heap.push(4);
+/// assert!(heap.spilled());
+/// assert_eq!(heap.pop(), Some(4));
+/// assert_eq!(heap.pop(), Some(3));
+/// assert_eq!(heap.pop(), Some(1));
+/// ```
+pub struct SmallHeap<T: Ord, const N: usize, I: Capacity = usize> {
+    repr: Repr<T, N, I>,
+}
+
+impl<T: Ord, const N: usize, I: Capacity> SmallHeap<T, N, I> {
+    /// Constructs a new, empty `SmallHeap` with inline storage for `N` items.
+    ///
+    /// # Panics
+    /// Panics if `N` cannot be represented as a value of type `I`.
+    pub fn new() -> Self {
+        SmallHeap {
+            repr: Repr::Inline(InlineHeap::new()),
+        }
+    }
+
+    /// Returns `true` if the items have been moved from inline storage into
+    /// heap-allocated storage.
+    #[inline]
+    pub fn spilled(&self) -> bool {
+        matches!(self.repr, Repr::Alloc(_))
+    }
+
+    /// Returns the number of items the heap can hold without reallocating.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        match &self.repr {
+            Repr::Inline(heap) => heap.capacity(),
+            Repr::Alloc(heap) => heap.capacity(),
+        }
+    }
+
+    /// Returns the number of items in the heap.
+    #[inline]
+    pub fn len(&self) -> usize {
+        match &self.repr {
+            Repr::Inline(heap) => heap.len(),
+            Repr::Alloc(heap) => heap.len(),
+        }
+    }
+
+    /// Returns `true` if the heap contains no items.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns a reference to the greatest item in the heap, or [`None`] if
+    /// it is empty.
+    #[inline]
+    pub fn peek(&self) -> Option<&T> {
+        match &self.repr {
+            Repr::Inline(heap) => heap.peek(),
+            Repr::Alloc(heap) => heap.peek(),
+        }
+    }
+
+    /// Returns a mutable reference to the greatest item in the heap, or
+    /// [`None`] if it is empty.
+    ///
+    /// The heap property is restored when the returned [`PeekMut`] is
+    /// dropped, as with [`BinaryHeap::peek_mut`](binary_heap::BinaryHeap::peek_mut).
+    ///
+    /// # Examples
+    /// ```
+    /// use coca::collections::small_heap::{PeekMut, SmallHeap};
+    ///
+    /// let mut heap: SmallHeap<u32, 4> = [3, 1, 4].iter().copied().collect();
+    /// if let Some(mut top) = heap.peek_mut() {
+    ///     *top = 0;
+    /// }
+    /// assert_eq!(heap.peek(), Some(&3));
+    ///
+    /// let top = heap.peek_mut().unwrap();
+    /// assert_eq!(PeekMut::pop(top), 3);
+    /// assert_eq!(heap.len(), 2);
+    /// ```
+    pub fn peek_mut(&mut self) -> Option<PeekMut<'_, T, N, I>> {
+        let repr = match &mut self.repr {
+            Repr::Inline(heap) => PeekMutRepr::Inline(heap.peek_mut()?),
+            Repr::Alloc(heap) => PeekMutRepr::Alloc(heap.peek_mut()?),
+        };
+        Some(PeekMut { repr })
+    }
+
+    /// Removes the greatest item from the heap and returns it, or [`None`]
+    /// if it is empty.
+    ///
+    /// This never moves the items back into inline storage.
+    #[inline]
+    pub fn pop(&mut self) -> Option<T> {
+        match &mut self.repr {
+            Repr::Inline(heap) => heap.pop(),
+            Repr::Alloc(heap) => heap.pop(),
+        }
+    }
+
+    /// Pushes an item onto the heap.
+    ///
+    /// If the heap is full, all items are first moved into a newly allocated
+    /// storage block with twice the capacity.
+    ///
+    /// # Panics
+    /// Panics if the heap is full and its capacity cannot be increased any
+    /// further, because it is already the maximum representable by `I`.
+    pub fn push(&mut self, item: T) {
+        self.grow_if_full();
+        match &mut self.repr {
+            Repr::Inline(heap) => heap.push(item),
+            Repr::Alloc(heap) => heap.push(item),
+        }
+    }
+
+    /// Pushes an item onto the heap, returning it back in a
+    /// [`CapacityError`] if the heap is full and its capacity cannot be
+    /// increased any further, because it is already the maximum
+    /// representable by `I`.
+    ///
+    /// Otherwise, this behaves like [`push`](SmallHeap::push).
+    ///
+    /// # Examples
+    /// ```
+    /// use coca::collections::small_heap::SmallHeap;
+    ///
+    /// let mut heap = SmallHeap::<u8, 2, u8>::new();
+    /// for x in 0..255 {
+    ///     assert!(heap.try_push(x).is_ok());
+    /// }
+    /// assert_eq!(heap.try_push(255).unwrap_err().into_inner(), 255);
+    /// ```
+    pub fn try_push(&mut self, item: T) -> Result<(), CapacityError<T>> {
+        self.grow_if_full();
+        match &mut self.repr {
+            Repr::Inline(heap) => heap.try_push(item),
+            Repr::Alloc(heap) => heap.try_push(item),
+        }
+    }
+
+    // Doubles the capacity if the heap is full, as far as `I` allows.
+    fn grow_if_full(&mut self) {
+        let len = self.len();
+        if len == self.capacity() {
+            let new_capacity = usize::min(len.saturating_mul(2).max(1), I::MAX_REPRESENTABLE);
+            self.grow(new_capacity);
+        }
+    }
+
+    // Moves all items into a newly allocated storage block, keeping their
+    // order, so that the heap property is preserved.
+    fn grow(&mut self, new_capacity: usize) {
+        if new_capacity <= self.len() {
+            return;
+        }
+
+        let mut vec = AllocVec::<T, I>::with_capacity(I::from_usize(new_capacity));
+        match &mut self.repr {
+            Repr::Inline(heap) => vec.extend(heap.drain()),
+            Repr::Alloc(heap) => vec.extend(heap.drain()),
+        }
+        self.repr = Repr::Alloc(AllocHeap::from(vec));
+    }
+
+    /// Returns an iterator visiting all items in the heap in arbitrary order.
+    pub fn iter(&self) -> core::slice::Iter<'_, T> {
+        match &self.repr {
+            Repr::Inline(heap) => heap.into_iter(),
+            Repr::Alloc(heap) => heap.into_iter(),
+        }
+    }
+
+    /// Clears the heap, returning an iterator over the removed items in
+    /// arbitrary order.
+    ///
+    /// The current storage block is retained.
+    pub fn drain(&mut self) -> Drain<'_, T, N, I> {
+        let repr = match &mut self.repr {
+            Repr::Inline(heap) => DrainRepr::Inline(heap.drain()),
+            Repr::Alloc(heap) => DrainRepr::Alloc(heap.drain()),
+        };
+        Drain { repr }
+    }
+
+    /// Retains only the items specified by the predicate, dropping all
+    /// others.
+    ///
+    /// See [`BinaryHeap::retain`](binary_heap::BinaryHeap::retain) for details.
+    pub fn retain<F: FnMut(&T) -> bool>(&mut self, f: F) {
+        match &mut self.repr {
+            Repr::Inline(heap) => heap.retain(f),
+            Repr::Alloc(heap) => heap.retain(f),
+        }
+    }
+
+    /// Drops all items from the heap, retaining the current storage block.
+    #[inline]
+    pub fn clear(&mut self) {
+        match &mut self.repr {
+            Repr::Inline(heap) => heap.clear(),
+            Repr::Alloc(heap) => heap.clear(),
+        }
+    }
+
+    /// Consumes the heap and returns a vector containing its items in
+    /// arbitrary order.
+    ///
+    /// If the heap has spilled, its storage block is reused; otherwise, the
+    /// items are moved into a newly allocated storage block of capacity `N`.
+    pub fn into_vec(self) -> AllocVec<T, I> {
+        match self.repr {
+            Repr::Inline(mut heap) => {
+                let mut vec = AllocVec::with_capacity(I::from_usize(N));
+                vec.extend(heap.drain());
+                vec
+            }
+            Repr::Alloc(heap) => heap.into_vec(),
+        }
+    }
+
+    /// Consumes the heap and returns a vector containing its items in
+    /// sorted (ascending) order.
+    ///
+    /// If the heap has spilled, its storage block is reused; otherwise, the
+    /// items are moved into a newly allocated storage block of capacity `N`.
+    ///
+    /// # Examples
+    /// ```
+    /// use coca::collections::small_heap::SmallHeap;
+    ///
+    /// let heap: SmallHeap<u32, 4> = [3, 1, 4, 1, 5].iter().copied().collect();
+    /// assert!(heap.spilled());
+    /// assert_eq!(heap.into_sorted_vec(), &[1, 1, 3, 4, 5][..]);
+    /// ```
+    pub fn into_sorted_vec(self) -> AllocVec<T, I> {
+        match self.repr {
+            Repr::Inline(mut heap) => {
+                let mut vec = AllocVec::with_capacity(I::from_usize(N));
+                vec.extend(heap.drain_sorted_rev());
+                vec
+            }
+            Repr::Alloc(heap) => heap.into_sorted_vec(),
+        }
+    }
+}
+
+impl<T: Ord, const N: usize, I: Capacity> Default for SmallHeap<T, N, I> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Ord, const N: usize, I: Capacity> Extend<T> for SmallHeap<T, N, I> {
+    fn extend<It: IntoIterator<Item = T>>(&mut self, iter: It) {
+        iter.into_iter().for_each(|item| self.push(item));
+    }
+}
+
+impl<T: Ord, const N: usize, I: Capacity> FromIterator<T> for SmallHeap<T, N, I> {
+    /// Creates a heap from an iterator, spilling into heap-allocated storage
+    /// if it yields more than `N` items.
+    fn from_iter<It: IntoIterator<Item = T>>(iter: It) -> Self {
+        let mut heap = Self::new();
+        heap.extend(iter);
+        heap
+    }
+}
+
+impl<'a, T: Ord, const N: usize, I: Capacity> IntoIterator for &'a SmallHeap<T, N, I> {
+    type Item = &'a T;
+    type IntoIter = core::slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<T: Ord + Debug, const N: usize, I: Capacity> Debug for SmallHeap<T, N, I> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+/// Structure wrapping a mutable reference to the greatest item on a `SmallHeap`.
+///
+/// This `struct` is created by the [`SmallHeap::peek_mut()`] method. See its
+/// documentation for more.
+pub struct PeekMut<'a, T: Ord, const N: usize, I: Capacity = usize> {
+    repr: PeekMutRepr<'a, T, N, I>,
+}
+
+enum PeekMutRepr<'a, T: Ord, const N: usize, I: Capacity> {
+    Inline(binary_heap::PeekMut<'a, T, InlineStorage<T, N>, I>),
+    Alloc(binary_heap::PeekMut<'a, T, AllocStorage<ArrayLayout<T>>, I>),
+}
+
+impl<T: Ord, const N: usize, I: Capacity> PeekMut<'_, T, N, I> {
+    /// Removes the peeked value from the heap and returns it.
+    pub fn pop(this: Self) -> T {
+        match this.repr {
+            PeekMutRepr::Inline(peek) => binary_heap::PeekMut::pop(peek),
+            PeekMutRepr::Alloc(peek) => binary_heap::PeekMut::pop(peek),
+        }
+    }
+}
+
+impl<T: Ord, const N: usize, I: Capacity> Deref for PeekMut<'_, T, N, I> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        match &self.repr {
+            PeekMutRepr::Inline(peek) => peek,
+            PeekMutRepr::Alloc(peek) => peek,
+        }
+    }
+}
+
+impl<T: Ord, const N: usize, I: Capacity> DerefMut for PeekMut<'_, T, N, I> {
+    fn deref_mut(&mut self) -> &mut T {
+        match &mut self.repr {
+            PeekMutRepr::Inline(peek) => peek,
+            PeekMutRepr::Alloc(peek) => peek,
+        }
+    }
+}
+
+impl<T: Ord + Debug, const N: usize, I: Capacity> Debug for PeekMut<'_, T, N, I> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("PeekMut").field(&**self).finish()
+    }
+}
+
+/// A draining iterator over the items of a `SmallHeap`.
+///
+/// This `struct` is created by [`SmallHeap::drain()`].
+/// See its documentation for more.
+pub struct Drain<'a, T, const N: usize, I: Capacity = usize> {
+    repr: DrainRepr<'a, T, N, I>,
+}
+
+enum DrainRepr<'a, T, const N: usize, I: Capacity> {
+    Inline(vec::Drain<'a, T, InlineStorage<T, N>, I>),
+    Alloc(vec::Drain<'a, T, AllocStorage<ArrayLayout<T>>, I>),
+}
+
+impl<T, const N: usize, I: Capacity> Iterator for Drain<'_, T, N, I> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        match &mut self.repr {
+            DrainRepr::Inline(drain) => drain.next(),
+            DrainRepr::Alloc(drain) => drain.next(),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match &self.repr {
+            DrainRepr::Inline(drain) => drain.size_hint(),
+            DrainRepr::Alloc(drain) => drain.size_hint(),
+        }
+    }
+}
+
+impl<T, const N: usize, I: Capacity> DoubleEndedIterator for Drain<'_, T, N, I> {
+    fn next_back(&mut self) -> Option<T> {
+        match &mut self.repr {
+            DrainRepr::Inline(drain) => drain.next_back(),
+            DrainRepr::Alloc(drain) => drain.next_back(),
+        }
+    }
+}
+
+impl<T, const N: usize, I: Capacity> ExactSizeIterator for Drain<'_, T, N, I> {}
+impl<T, const N: usize, I: Capacity> FusedIterator for Drain<'_, T, N, I> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn spills_once_and_keeps_heap_order() {
+        use rand::{rngs::SmallRng, Rng, SeedableRng};
+
+        let mut rng = SmallRng::from_seed(crate::test_utils::RNG_SEED);
+        let mut heap = SmallHeap::<u32, 8, u8>::new();
+        let mut spills = 0;
+        let mut pushed = AllocVec::<u32>::with_capacity(100);
+
+        for _ in 0..100 {
+            let was_spilled = heap.spilled();
+            let x = rng.gen_range(0..1000);
+            heap.push(x);
+            pushed.push(x);
+            if heap.spilled() != was_spilled {
+                spills += 1;
+                assert_eq!(heap.len(), 9);
+                assert_eq!(heap.capacity(), 16);
+            }
+        }
+
+        assert_eq!(spills, 1);
+        assert_eq!(heap.len(), 100);
+        assert_eq!(heap.capacity(), 128);
+
+        pushed.sort_unstable();
+        for &x in pushed.iter().rev() {
+            assert_eq!(heap.pop(), Some(x));
+        }
+        assert!(heap.is_empty());
+        assert!(heap.spilled());
+    }
+
+    #[test]
+    fn forwarded_methods_agree_before_and_after_spilling() {
+        use crate::collections::InlineVec;
+
+        for len in [4, 20] {
+            let items = (0..len).map(|x| x * 7 % 23);
+            let mut expected: InlineVec<u32, 32> = items.clone().collect();
+            expected.sort_unstable();
+
+            let heap: SmallHeap<u32, 8, u8> = items.clone().collect();
+            assert_eq!(heap.spilled(), len > 8);
+            assert_eq!(heap.into_sorted_vec().as_slice(), expected.as_slice());
+
+            let heap: SmallHeap<u32, 8, u8> = items.clone().collect();
+            let mut vec = heap.into_vec();
+            vec.sort_unstable();
+            assert_eq!(vec.as_slice(), expected.as_slice());
+
+            let mut heap: SmallHeap<u32, 8, u8> = items.clone().collect();
+            let mut drained: InlineVec<u32, 32> = heap.drain().collect();
+            drained.sort_unstable();
+            assert_eq!(drained, expected);
+            assert!(heap.is_empty());
+
+            let mut heap: SmallHeap<u32, 8, u8> = items.clone().collect();
+            *heap.peek_mut().unwrap() = 0;
+            let second = expected[len as usize - 2];
+            assert_eq!(heap.peek(), Some(&second));
+            assert_eq!(PeekMut::pop(heap.peek_mut().unwrap()), second);
+
+            heap.retain(|&x| x % 2 == 0);
+            let evens = expected[..len as usize - 2].iter().filter(|&&x| x % 2 == 0);
+            assert_eq!(heap.len(), evens.count() + 1);
+            assert!(heap.iter().all(|&x| x % 2 == 0));
+        }
+    }
+
+    #[test]
+    fn try_push_fails_at_index_type_limit() {
+        let mut heap = SmallHeap::<u8, 100, u8>::new();
+        heap.extend(0..255);
+        assert_eq!(heap.try_push(255), Err(CapacityError::with_value(255)));
+        assert_eq!(heap.len(), 255);
+    }
+
+    #[test]
+    #[should_panic(expected = "binary heap is already at capacity")]
+    fn growth_is_limited_by_index_type() {
+        let mut heap = SmallHeap::<u8, 100, u8>::new();
+        heap.extend(0..255);
+        assert_eq!(heap.capacity(), 255);
+        heap.push(255);
+    }
+}
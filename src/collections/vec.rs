@@ -42,7 +42,7 @@ impl<T, S: Storage<ArrayLayout<T>>, I: Capacity> From<S> for Vec<T, S, I> {
     /// This may panic if the index type I cannot represent `buf.capacity()`.
     fn from(buf: S) -> Self {
         if buf.capacity() > I::MAX_REPRESENTABLE {
-            buffer_too_large_for_index_type::<I>();
+            buffer_too_large_for_index_type::<I>(buf.capacity());
         }
 
         Vec {
@@ -166,6 +166,30 @@ impl<T, S: Storage<ArrayLayout<T>>, I: Capacity> Vec<T, S, I> {
         unsafe { Some(ptr_at_index(&self.buf, self.len()).read()) }
     }
 
+    /// Removes and returns the last element from the vector if the predicate
+    /// returns `true`, or [`None`] if the predicate returns `false` or the
+    /// vector is empty.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut backing_region = [core::mem::MaybeUninit::<u32>::uninit(); 4];
+    /// let mut vec = coca::collections::SliceVec::<u32>::from(&mut backing_region[..]);
+    /// vec.push(1); vec.push(2); vec.push(3); vec.push(4);
+    ///
+    /// let pred = |x: &mut u32| *x % 2 == 0;
+    /// assert_eq!(vec.pop_if(pred), Some(4));
+    /// assert_eq!(vec, &[1, 2, 3][..]);
+    /// assert_eq!(vec.pop_if(pred), None);
+    /// ```
+    pub fn pop_if<F: FnOnce(&mut T) -> bool>(&mut self, f: F) -> Option<T> {
+        let last = self.last_mut()?;
+        if f(last) {
+            self.pop()
+        } else {
+            None
+        }
+    }
+
     /// Extracts a slice containing the entire vector.
     ///
     /// Equivalent to `&s[..]`.
@@ -182,6 +206,58 @@ impl<T, S: Storage<ArrayLayout<T>>, I: Capacity> Vec<T, S, I> {
         self
     }
 
+    /// Returns a raw pointer to the vector's buffer.
+    ///
+    /// The caller must ensure that the vector outlives the pointer this
+    /// function returns. Only the first `len()` elements are initialized and
+    /// may be read through the pointer. Modifying the vector may invalidate
+    /// the pointer; the memory it points to must never be written to through
+    /// this pointer or any pointer derived from it. Use
+    /// [`as_mut_ptr`](Vec::as_mut_ptr) if you need to mutate the contents.
+    ///
+    /// Unlike `self.as_slice().as_ptr()`, this does not create an intermediate
+    /// reference, so the pointer is derived from the entire storage block.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut vec = coca::collections::InlineVec::<u32, 4>::new();
+    /// vec.extend([1, 2, 4]);
+    ///
+    /// let ptr = vec.as_ptr();
+    /// for i in 0..vec.len() {
+    ///     assert_eq!(unsafe { *ptr.add(i) }, 1 << i);
+    /// }
+    /// ```
+    #[inline]
+    pub fn as_ptr(&self) -> *const T {
+        self.buf.get_ptr().cast()
+    }
+
+    /// Returns a raw mutable pointer to the vector's buffer.
+    ///
+    /// The caller must ensure that the vector outlives the pointer this
+    /// function returns. Only the first `len()` elements are initialized,
+    /// though the pointer may be used to write up to `capacity()` elements;
+    /// see [`set_len`](Vec::set_len). Modifying the vector may invalidate
+    /// the pointer.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut vec = coca::collections::InlineVec::<u32, 4>::new();
+    /// let ptr = vec.as_mut_ptr();
+    /// unsafe {
+    ///     for i in 0..4 {
+    ///         ptr.add(i).write(i as u32 * 2);
+    ///     }
+    ///     vec.set_len(4);
+    /// }
+    /// assert_eq!(vec, [0, 2, 4, 6]);
+    /// ```
+    #[inline]
+    pub fn as_mut_ptr(&mut self) -> *mut T {
+        self.buf.get_mut_ptr().cast()
+    }
+
     /// Constructs and returns a new `Vec` from a slice of this vector's underlying storage.
     ///
     /// ## Panics
@@ -343,6 +419,15 @@ impl<T, S: Storage<ArrayLayout<T>>, I: Capacity> Vec<T, S, I> {
 
     /// Returns a mutable reference to the element at the specified index, or
     /// [`None`] if the index is out of bounds.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut vec = coca::collections::InlineVec::<u32, 4>::new();
+    /// vec.push(1); vec.push(2); vec.push(3);
+    /// *vec.get_mut(1).unwrap() = 4;
+    /// assert_eq!(vec, &[1, 4, 3][..]);
+    /// assert_eq!(vec.get_mut(3), None);
+    /// ```
     #[inline]
     pub fn get_mut(&mut self, index: I) -> Option<&mut T> {
         let index = index.as_usize();
@@ -353,8 +438,82 @@ impl<T, S: Storage<ArrayLayout<T>>, I: Capacity> Vec<T, S, I> {
         unsafe { Some(&mut *mut_ptr_at_index(&mut self.buf, index)) }
     }
 
-    /// Appends an element to the back of the vector, returning `Err(value)` if
-    /// it is already at capacity.
+    /// Returns a reference to the first element of the vector, or [`None`] if it is empty.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut vec = coca::collections::InlineVec::<u32, 4>::new();
+    /// assert_eq!(vec.first(), None);
+    /// vec.push(1);
+    /// assert_eq!(vec.first(), Some(&1));
+    /// vec.push(2);
+    /// assert_eq!(vec.first(), Some(&1));
+    /// ```
+    #[inline]
+    pub fn first(&self) -> Option<&T> {
+        self.get(I::from_usize(0))
+    }
+
+    /// Returns a mutable reference to the first element of the vector, or
+    /// [`None`] if it is empty.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut vec = coca::collections::InlineVec::<u32, 4>::new();
+    /// assert_eq!(vec.first_mut(), None);
+    /// vec.push(1); vec.push(2);
+    /// *vec.first_mut().unwrap() = 3;
+    /// assert_eq!(vec, &[3, 2][..]);
+    /// ```
+    #[inline]
+    pub fn first_mut(&mut self) -> Option<&mut T> {
+        self.get_mut(I::from_usize(0))
+    }
+
+    /// Returns a reference to the last element of the vector, or [`None`] if it is empty.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut vec = coca::collections::InlineVec::<u32, 4>::new();
+    /// assert_eq!(vec.last(), None);
+    /// vec.push(1);
+    /// assert_eq!(vec.last(), Some(&1));
+    /// vec.push(2);
+    /// assert_eq!(vec.last(), Some(&2));
+    /// ```
+    #[inline]
+    pub fn last(&self) -> Option<&T> {
+        let len = self.len();
+        if len == 0 {
+            return None;
+        }
+
+        unsafe { Some(&*ptr_at_index(&self.buf, len - 1)) }
+    }
+
+    /// Returns a mutable reference to the last element of the vector, or
+    /// [`None`] if it is empty.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut vec = coca::collections::InlineVec::<u32, 4>::new();
+    /// assert_eq!(vec.last_mut(), None);
+    /// vec.push(1); vec.push(2);
+    /// *vec.last_mut().unwrap() = 3;
+    /// assert_eq!(vec, &[1, 3][..]);
+    /// ```
+    #[inline]
+    pub fn last_mut(&mut self) -> Option<&mut T> {
+        let len = self.len();
+        if len == 0 {
+            return None;
+        }
+
+        unsafe { Some(&mut *mut_ptr_at_index(&mut self.buf, len - 1)) }
+    }
+
+    /// Appends an element to the back of the vector, returning it back in a
+    /// [`CapacityError`] if the vector is already at capacity.
     ///
     /// # Examples
     /// ```
@@ -363,12 +522,12 @@ impl<T, S: Storage<ArrayLayout<T>>, I: Capacity> Vec<T, S, I> {
     /// assert!(vec.try_push(1).is_ok());
     /// assert!(vec.try_push(2).is_ok());
     /// assert!(vec.try_push(3).is_ok());
-    /// assert_eq!(vec.try_push(4), Err(4));
+    /// assert_eq!(vec.try_push(4).unwrap_err().into_inner(), 4);
     /// ```
     #[inline]
-    pub fn try_push(&mut self, value: T) -> Result<(), T> {
+    pub fn try_push(&mut self, value: T) -> Result<(), CapacityError<T>> {
         if self.is_full() {
-            return Err(value);
+            return Err(CapacityError::with_value(value));
         }
 
         let len = self.len();
@@ -380,6 +539,27 @@ impl<T, S: Storage<ArrayLayout<T>>, I: Capacity> Vec<T, S, I> {
         Ok(())
     }
 
+    /// Appends an element to the back of the vector, returning it back if
+    /// the vector is already at capacity.
+    ///
+    /// This matches the signature of `std::vec::Vec::push_within_capacity`,
+    /// to ease migration from standard vectors. It is otherwise equivalent
+    /// to [`try_push`](Vec::try_push), which returns a [`CapacityError`]
+    /// instead.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut vec = coca::collections::InlineVec::<u32, 2>::new();
+    /// assert_eq!(vec.push_within_capacity(1), Ok(()));
+    /// assert_eq!(vec.push_within_capacity(2), Ok(()));
+    /// assert_eq!(vec.push_within_capacity(3), Err(3));
+    /// assert_eq!(vec, [1, 2]);
+    /// ```
+    #[inline]
+    pub fn push_within_capacity(&mut self, value: T) -> Result<(), T> {
+        self.try_push(value).map_err(CapacityError::into_inner)
+    }
+
     /// Appends an element to the back of the vector.
     ///
     /// # Panics
@@ -402,6 +582,10 @@ impl<T, S: Storage<ArrayLayout<T>>, I: Capacity> Vec<T, S, I> {
     ///
     /// If `len` is greater than the vector's current length, this has no effect.
     ///
+    /// Like the standard library's `Vec`, the removed elements are dropped
+    /// in order, starting from the lowest index. See
+    /// [`truncate_from_back`](Vec::truncate_from_back) for the reverse order.
+    ///
     /// # Examples
     /// ```
     /// let mut backing_region = [core::mem::MaybeUninit::<u32>::uninit(); 4];
@@ -422,13 +606,110 @@ impl<T, S: Storage<ArrayLayout<T>>, I: Capacity> Vec<T, S, I> {
             return;
         }
 
-        for i in new_len..old_len {
+        // update the length first, so that a panicking destructor can't
+        // cause any of the removed elements to be dropped again
+        self.len = len;
+        unsafe {
+            let tail = mut_ptr_at_index(&mut self.buf, new_len);
+            ptr::drop_in_place(ptr::slice_from_raw_parts_mut(tail, old_len - new_len));
+        }
+    }
+
+    /// Shortens the vector, keeping the first `len` elements and dropping the
+    /// rest in reverse order, starting from the highest index.
+    ///
+    /// If `len` is greater than the vector's current length, this has no effect.
+    ///
+    /// This is useful when elements must be released in the opposite order
+    /// of their insertion, e.g. for guards with nested scopes. See
+    /// [`truncate`](Vec::truncate) for dropping in insertion order instead.
+    ///
+    /// # Examples
+    /// ```
+    /// use core::cell::RefCell;
+    ///
+    /// struct Logged<'a>(u32, &'a RefCell<coca::collections::InlineVec<u32, 4>>);
+    /// impl Drop for Logged<'_> {
+    ///     fn drop(&mut self) {
+    ///         self.1.borrow_mut().push(self.0);
+    ///     }
+    /// }
+    ///
+    /// let log = RefCell::new(coca::collections::InlineVec::new());
+    /// let mut vec = coca::collections::InlineVec::<Logged, 4>::new();
+    /// vec.extend((1..=4).map(|i| Logged(i, &log)));
+    ///
+    /// vec.truncate_from_back(1);
+    /// assert_eq!(*log.borrow(), [4, 3, 2]);
+    /// ```
+    pub fn truncate_from_back(&mut self, len: I) {
+        let new_len = len.as_usize();
+        let old_len = self.len.as_usize();
+
+        for i in (new_len..old_len).rev() {
+            self.len = I::from_usize(i);
             unsafe {
                 mut_ptr_at_index(&mut self.buf, i).drop_in_place();
             }
         }
+    }
 
-        self.len = len;
+    /// Retains only the elements within the specified range, dropping all
+    /// others and moving the retained ones to the front of the vector.
+    ///
+    /// Range bounds beyond the vector's length are clamped to it. If the
+    /// range is empty, or its start is greater than its end, the vector is
+    /// cleared. The removed elements are dropped in order, starting from the
+    /// lowest index.
+    ///
+    /// If one of the removed elements panics when dropped, the retained
+    /// elements are leaked.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut vec = coca::collections::InlineVec::<u32, 8>::new();
+    /// vec.extend(1..=8);
+    ///
+    /// vec.retain_range(2..6);
+    /// assert_eq!(vec, [3, 4, 5, 6]);
+    ///
+    /// vec.retain_range(1..);
+    /// assert_eq!(vec, [4, 5, 6]);
+    ///
+    /// vec.retain_range(2..1);
+    /// assert!(vec.is_empty());
+    /// ```
+    pub fn retain_range<R: RangeBounds<I>>(&mut self, range: R) {
+        use core::ops::Bound;
+
+        let len = self.len();
+        let start = match range.start_bound() {
+            Bound::Included(x) => x.as_usize(),
+            Bound::Excluded(x) => x.as_usize().saturating_add(1),
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(x) => x.as_usize().saturating_add(1),
+            Bound::Excluded(x) => x.as_usize(),
+            Bound::Unbounded => len,
+        }
+        .min(len);
+
+        if start >= end {
+            self.clear();
+            return;
+        }
+
+        // the length is reset first, so that a panicking destructor can't
+        // cause any element to be dropped twice
+        self.len = I::from_usize(0);
+        unsafe {
+            let base = mut_ptr_at_index(&mut self.buf, 0);
+            ptr::drop_in_place(ptr::slice_from_raw_parts_mut(base, start));
+            ptr::drop_in_place(ptr::slice_from_raw_parts_mut(base.add(end), len - end));
+            ptr::copy(base.add(start), base, end - start);
+        }
+        self.len = I::from_usize(end - start);
     }
 
     /// Clears the vector, dropping all values.
@@ -439,6 +720,32 @@ impl<T, S: Storage<ArrayLayout<T>>, I: Capacity> Vec<T, S, I> {
         self.truncate(I::from_usize(0));
     }
 
+    /// Clears the vector, dropping all values, and returns the entire
+    /// storage block as a slice of uninitialized elements.
+    ///
+    /// The returned slice always covers the full capacity. This allows
+    /// refilling the vector in place, after which [`set_len`](Vec::set_len)
+    /// can be used to mark the written elements as initialized.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut vec = coca::collections::InlineVec::<u8, 8>::new();
+    /// vec.extend(*b"stale");
+    ///
+    /// let spare = vec.clear_to_spare();
+    /// assert_eq!(spare.len(), 8);
+    /// for (slot, byte) in spare.iter_mut().zip(b"fresh") {
+    ///     slot.write(*byte);
+    /// }
+    ///
+    /// unsafe { vec.set_len(5) };
+    /// assert_eq!(vec, *b"fresh");
+    /// ```
+    pub fn clear_to_spare(&mut self) -> &mut [MaybeUninit<T>] {
+        self.clear();
+        self.spare_capacity_mut()
+    }
+
     /// Swaps two elements in the vector.
     ///
     /// # Panics
@@ -455,123 +762,469 @@ impl<T, S: Storage<ArrayLayout<T>>, I: Capacity> Vec<T, S, I> {
     /// ```
     #[inline]
     pub fn swap(&mut self, fst: I, snd: I) {
+        #[cold]
+        #[inline(never)]
+        fn assert_failed(idx: usize, len: usize) -> ! {
+            panic!("swap index (is {}) should be < len (is {})", idx, len);
+        }
+
+        let len = self.len();
+        for idx in [fst.as_usize(), snd.as_usize()] {
+            if idx >= len {
+                assert_failed(idx, len);
+            }
+        }
+
+        unsafe { self.swap_unchecked(fst, snd) };
+    }
+
+    /// Swaps two elements in the vector, without doing bounds checking.
+    ///
+    /// For a safe alternative see [`swap`](Vec::swap).
+    ///
+    /// # Safety
+    /// Calling this method with an out-of-bounds index is undefined behavior.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut vec = coca::collections::InlineVec::<u32, 4>::new();
+    /// vec.extend([1, 2, 3, 4]);
+    ///
+    /// unsafe { vec.swap_unchecked(0, 3) };
+    /// assert_eq!(vec, &[4, 2, 3, 1][..]);
+    /// ```
+    #[inline]
+    pub unsafe fn swap_unchecked(&mut self, fst: I, snd: I) {
         let fst = fst.as_usize();
         let snd = snd.as_usize();
-        self.as_mut_slice().swap(fst, snd);
+        debug_assert!(fst < self.len() && snd < self.len());
+
+        let base = self.as_mut_ptr();
+        ptr::swap(base.add(fst), base.add(snd));
     }
 
-    /// Removes an element from the vector and returns it.
+    /// Divides the vector's elements into two mutable slices at an index.
     ///
-    /// The removed element is replaced by the last element of the vector. This
-    /// does not preserve ordering, but it is O(1).
+    /// The first slice contains the elements in `[0, mid)`, the second one
+    /// those in `[mid, len)`. The vector's spare capacity is not included.
+    /// To split at a `usize` index instead, use
+    /// [`as_mut_slice().split_at_mut(mid)`](slice::split_at_mut).
     ///
     /// # Panics
-    /// Panics if `index` is out of bounds.
+    /// Panics if `mid > len`.
     ///
     /// # Examples
     /// ```
-    /// let mut backing_region = [core::mem::MaybeUninit::<u32>::uninit(); 4];
-    /// let mut vec = coca::collections::SliceVec::<u32>::from(&mut backing_region[..]);
-    /// vec.push(1); vec.push(2); vec.push(3); vec.push(4);
+    /// let mut vec = coca::collections::InlineVec::<u32, 8, u8>::new();
+    /// vec.extend([1, 2, 3, 4, 5]);
     ///
-    /// vec.swap_remove(1);
-    /// assert_eq!(vec, &[1, 4, 3][..]);
+    /// let (left, right) = vec.split_at_mut(2);
+    /// left[0] = 10;
+    /// right[0] = 30;
+    /// assert_eq!(vec, [10, 2, 30, 4, 5]);
     /// ```
     #[inline]
-    pub fn swap_remove(&mut self, index: I) -> T {
+    pub fn split_at_mut(&mut self, mid: I) -> (&mut [T], &mut [T]) {
         #[cold]
         #[inline(never)]
-        fn assert_failed(idx: usize, len: usize) -> ! {
-            panic!(
-                "swap_remove index (is {}) should be < len (is {})",
-                idx, len
-            );
+        fn assert_failed(mid: usize, len: usize) -> ! {
+            panic!("mid (is {}) should be <= len (is {})", mid, len);
         }
 
-        let idx = index.as_usize();
-        let len = self.len.as_usize();
-        if idx >= len {
-            assert_failed(idx, len);
+        let mid = mid.as_usize();
+        if mid > self.len() {
+            assert_failed(mid, self.len());
         }
 
-        unsafe {
-            let last = ptr_at_index(&self.buf, len - 1).read();
-            let hole = mut_ptr_at_index(&mut self.buf, idx);
-            self.len = I::from_usize(self.len() - 1);
-            ptr::replace(hole, last)
-        }
+        self.as_mut_slice().split_at_mut(mid)
     }
 
-    /// Inserts an element at position `index` within the vector, shifting all
-    /// elements after it to the right.
+    /// Splits the vector's elements into arrays of `N` elements each,
+    /// starting at the beginning, and a remainder of fewer than `N` elements.
     ///
     /// # Panics
-    /// Panics if the vector is already full, or if `index` is out of bounds.
-    /// See [`try_insert`](Vec::try_insert) for a checked version.
-    pub fn insert(&mut self, index: I, element: T) {
-        #[cold]
-        #[inline(never)]
-        fn assert_failed() -> ! {
-            panic!("vector is already at capacity")
-        }
-
-        let result = self.try_insert(index, element);
-        if result.is_err() {
-            assert_failed();
-        }
+    /// Panics if `N` is zero.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut vec = coca::collections::InlineVec::<u8, 8>::new();
+    /// vec.extend(*b"abcdefg");
+    ///
+    /// let (chunks, rest) = vec.as_chunks::<2>();
+    /// assert_eq!(chunks, &[*b"ab", *b"cd", *b"ef"]);
+    /// assert_eq!(rest, b"g");
+    /// ```
+    #[track_caller]
+    pub fn as_chunks<const N: usize>(&self) -> (&[[T; N]], &[T]) {
+        assert!(N != 0, "chunk size must be non-zero");
+        let count = self.len() / N;
+        let (chunks, rest) = self.as_slice().split_at(count * N);
+        // SAFETY: `chunks` holds exactly `count * N` elements, and arrays
+        // have the same layout as the same number of consecutive elements
+        let chunks = unsafe { core::slice::from_raw_parts(chunks.as_ptr().cast(), count) };
+        (chunks, rest)
     }
 
-    /// Inserts an element at position `index` within the vector, shifting all
-    /// elements after it to the right.
+    /// Splits the vector's elements into mutable arrays of `N` elements
+    /// each, starting at the beginning, and a remainder of fewer than `N`
+    /// elements.
     ///
-    /// Returns `Err(element)` if the vector is already at capacity.
+    /// # Panics
+    /// Panics if `N` is zero.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut vec = coca::collections::InlineVec::<u8, 8>::new();
+    /// vec.extend(*b"abcdefg");
+    ///
+    /// let (chunks, rest) = vec.as_chunks_mut::<3>();
+    /// chunks.iter_mut().for_each(|c| c.reverse());
+    /// rest[0] = b'!';
+    /// assert_eq!(vec, *b"cbafed!");
+    /// ```
+    #[track_caller]
+    pub fn as_chunks_mut<const N: usize>(&mut self) -> (&mut [[T; N]], &mut [T]) {
+        assert!(N != 0, "chunk size must be non-zero");
+        let count = self.len() / N;
+        let (chunks, rest) = self.as_mut_slice().split_at_mut(count * N);
+        // SAFETY: see `as_chunks`
+        let chunks = unsafe { core::slice::from_raw_parts_mut(chunks.as_mut_ptr().cast(), count) };
+        (chunks, rest)
+    }
+
+    /// Copies elements from the `src` range to another part of the vector,
+    /// starting at `dest`. The two ranges may overlap.
+    /// To use `usize` indices instead, call
+    /// [`as_mut_slice().copy_within(src, dest)`](slice::copy_within).
     ///
     /// # Panics
-    /// Panics if `index` is out of bounds.
+    /// Panics if the starting point of `src` is greater than its end point,
+    /// if the end point is greater than the length of the vector, or if
+    /// `dest + src.len()` is greater than the length of the vector.
     ///
     /// # Examples
     /// ```
-    /// let mut backing_region = [core::mem::MaybeUninit::<u32>::uninit(); 4];
-    /// let mut vec = coca::collections::SliceVec::<u32>::from(&mut backing_region[..]);
-    /// vec.push(1); vec.push(2); vec.push(3);
+    /// let mut vec = coca::collections::InlineVec::<u8, 8, u8>::new();
+    /// vec.extend(*b"abcdef");
     ///
-    /// assert!(vec.try_insert(3, 4).is_ok());
-    /// assert!(vec.try_insert(4, 5).is_err());
-    /// assert_eq!(vec, &[1, 2, 3, 4][..]);
+    /// vec.copy_within(1..4, 3);
+    /// assert_eq!(vec, *b"abcbcd");
     /// ```
-    pub fn try_insert(&mut self, index: I, element: T) -> Result<(), T> {
+    #[track_caller]
+    pub fn copy_within<R: RangeBounds<I>>(&mut self, src: R, dest: I)
+    where
+        T: Copy,
+    {
         #[cold]
         #[inline(never)]
-        fn assert_failed(index: usize, len: usize) -> ! {
+        #[track_caller]
+        fn assert_failed(dest: usize, max: usize) -> ! {
             panic!(
-                "insertion index (is {}) should be <= len (is {})",
-                index, len
+                "dest (is {}) should be <= len - src.len() (is {})",
+                dest, max
             );
         }
 
-        if self.is_full() {
-            return Err(element);
-        }
-
-        let idx = index.as_usize();
-        let len = self.len.as_usize();
-        if idx > len {
-            assert_failed(idx, len);
-        }
-
-        let p = mut_ptr_at_index(&mut self.buf, idx);
-        unsafe {
-            ptr::copy(p, p.add(1), len - idx);
-            ptr::write(p, element);
+        let Range { start, end } = normalize_range(src, self.len());
+        let dest = dest.as_usize();
+        let max = self.len() - (end - start);
+        if dest > max {
+            assert_failed(dest, max);
         }
 
-        self.len = I::from_usize(len + 1);
-        Ok(())
+        self.as_mut_slice().copy_within(start..end, dest);
     }
 
-    /// Appends as many elements from `iter` to the `Vec` as possible.
+    /// Sorts the vector, but might not preserve the order of equal elements.
     ///
-    /// Returns the iterator of remaining elements if the vector is filled, or
+    /// This sort is unstable (i.e. may reorder equal elements), in-place (i.e.
+    /// does not allocate), and O(n log(n)) worst-case. It forwards to
+    /// [`slice::sort_unstable`], which is available in `core`; the stable
+    /// `sort` variants require a scratch buffer from the `alloc` crate and are
+    /// therefore not provided.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut backing_region = [core::mem::MaybeUninit::<i32>::uninit(); 5];
+    /// let mut vec = coca::collections::SliceVec::<i32>::from(&mut backing_region[..]);
+    /// vec.extend([-5, 4, 1, -3, 2]);
+    ///
+    /// vec.sort_unstable();
+    /// assert_eq!(vec, &[-5, -3, 1, 2, 4][..]);
+    /// ```
+    #[inline]
+    pub fn sort_unstable(&mut self)
+    where
+        T: Ord,
+    {
+        self.as_mut_slice().sort_unstable();
+    }
+
+    /// Sorts the vector with a comparator function, but might not preserve
+    /// the order of equal elements.
+    ///
+    /// See [`sort_unstable`](Vec::sort_unstable) for more.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut backing_region = [core::mem::MaybeUninit::<i32>::uninit(); 5];
+    /// let mut vec = coca::collections::SliceVec::<i32>::from(&mut backing_region[..]);
+    /// vec.extend([5, 4, 1, 3, 2]);
+    ///
+    /// vec.sort_unstable_by(|a, b| b.cmp(a));
+    /// assert_eq!(vec, &[5, 4, 3, 2, 1][..]);
+    /// ```
+    #[inline]
+    pub fn sort_unstable_by<F: FnMut(&T, &T) -> Ordering>(&mut self, compare: F) {
+        self.as_mut_slice().sort_unstable_by(compare);
+    }
+
+    /// Sorts the vector with a key extraction function, but might not preserve
+    /// the order of equal elements.
+    ///
+    /// See [`sort_unstable`](Vec::sort_unstable) for more.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut backing_region = [core::mem::MaybeUninit::<i32>::uninit(); 5];
+    /// let mut vec = coca::collections::SliceVec::<i32>::from(&mut backing_region[..]);
+    /// vec.extend([-5, 4, 1, -3, 2]);
+    ///
+    /// vec.sort_unstable_by_key(|k| k.abs());
+    /// assert_eq!(vec, &[1, 2, -3, 4, -5][..]);
+    /// ```
+    #[inline]
+    pub fn sort_unstable_by_key<K: Ord, F: FnMut(&T) -> K>(&mut self, f: F) {
+        self.as_mut_slice().sort_unstable_by_key(f);
+    }
+
+    /// Fills the vector with elements by cloning `value`, overwriting and
+    /// dropping all current elements without changing the length.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut vec = coca::collections::InlineVec::<u32, 8>::new();
+    /// vec.extend(1..=4);
+    ///
+    /// vec.fill(7);
+    /// assert_eq!(vec, &[7, 7, 7, 7][..]);
+    /// ```
+    #[inline]
+    pub fn fill(&mut self, value: T)
+    where
+        T: Clone,
+    {
+        self.as_mut_slice().fill(value);
+    }
+
+    /// Fills the vector with elements returned by calling a closure repeatedly,
+    /// overwriting and dropping all current elements without changing the length.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut vec = coca::collections::InlineVec::<u32, 8>::new();
+    /// vec.extend(1..=4);
+    ///
+    /// let mut next = 10;
+    /// vec.fill_with(|| { next += 1; next });
+    /// assert_eq!(vec, &[11, 12, 13, 14][..]);
+    /// ```
+    #[inline]
+    pub fn fill_with<F: FnMut() -> T>(&mut self, f: F) {
+        self.as_mut_slice().fill_with(f);
+    }
+
+    /// Returns an iterator over all contiguous windows of length `size`.
+    /// The windows overlap. If the vector is shorter than `size`, the
+    /// iterator returns no values.
+    ///
+    /// Equivalent to `s.as_slice().windows(size)`.
+    ///
+    /// # Panics
+    /// Panics if `size` is 0.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut vec = coca::collections::InlineVec::<u32, 8>::new();
+    /// vec.extend(1..=4);
+    ///
+    /// let mut iter = vec.windows(2);
+    /// assert_eq!(iter.next(), Some(&[1, 2][..]));
+    /// assert_eq!(iter.next(), Some(&[2, 3][..]));
+    /// assert_eq!(iter.next(), Some(&[3, 4][..]));
+    /// assert_eq!(iter.next(), None);
+    /// ```
+    #[inline]
+    pub fn windows(&self, size: usize) -> core::slice::Windows<'_, T> {
+        self.as_slice().windows(size)
+    }
+
+    /// Returns an iterator over `chunk_size` elements of the vector at a time,
+    /// starting at the beginning of the vector. The chunks do not overlap; if
+    /// `chunk_size` does not divide the length of the vector, the last chunk
+    /// will be shorter.
+    ///
+    /// Equivalent to `s.as_slice().chunks(chunk_size)`.
+    ///
+    /// # Panics
+    /// Panics if `chunk_size` is 0.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut vec = coca::collections::InlineVec::<u32, 8>::new();
+    /// vec.extend(1..=5);
+    ///
+    /// let mut iter = vec.chunks(2);
+    /// assert_eq!(iter.next(), Some(&[1, 2][..]));
+    /// assert_eq!(iter.next(), Some(&[3, 4][..]));
+    /// assert_eq!(iter.next(), Some(&[5][..]));
+    /// assert_eq!(iter.next(), None);
+    /// ```
+    #[inline]
+    pub fn chunks(&self, chunk_size: usize) -> core::slice::Chunks<'_, T> {
+        self.as_slice().chunks(chunk_size)
+    }
+
+    /// Returns an iterator over `chunk_size` elements of the vector at a time,
+    /// starting at the beginning of the vector. The chunks are mutable slices
+    /// and do not overlap; if `chunk_size` does not divide the length of the
+    /// vector, the last chunk will be shorter.
+    ///
+    /// Equivalent to `s.as_mut_slice().chunks_mut(chunk_size)`.
+    ///
+    /// # Panics
+    /// Panics if `chunk_size` is 0.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut vec = coca::collections::InlineVec::<u32, 8>::new();
+    /// vec.extend([0; 5]);
+    ///
+    /// let mut count = 1;
+    /// for chunk in vec.chunks_mut(2) {
+    ///     chunk.iter_mut().for_each(|x| *x = count);
+    ///     count += 1;
+    /// }
+    /// assert_eq!(vec, &[1, 1, 2, 2, 3][..]);
+    /// ```
+    #[inline]
+    pub fn chunks_mut(&mut self, chunk_size: usize) -> core::slice::ChunksMut<'_, T> {
+        self.as_mut_slice().chunks_mut(chunk_size)
+    }
+
+    /// Removes an element from the vector and returns it.
+    ///
+    /// The removed element is replaced by the last element of the vector. This
+    /// does not preserve ordering, but it is O(1).
+    ///
+    /// # Panics
+    /// Panics if `index` is out of bounds.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut backing_region = [core::mem::MaybeUninit::<u32>::uninit(); 4];
+    /// let mut vec = coca::collections::SliceVec::<u32>::from(&mut backing_region[..]);
+    /// vec.push(1); vec.push(2); vec.push(3); vec.push(4);
+    ///
+    /// vec.swap_remove(1);
+    /// assert_eq!(vec, &[1, 4, 3][..]);
+    /// ```
+    #[inline]
+    pub fn swap_remove(&mut self, index: I) -> T {
+        #[cold]
+        #[inline(never)]
+        fn assert_failed(idx: usize, len: usize) -> ! {
+            panic!(
+                "swap_remove index (is {}) should be < len (is {})",
+                idx, len
+            );
+        }
+
+        let idx = index.as_usize();
+        let len = self.len.as_usize();
+        if idx >= len {
+            assert_failed(idx, len);
+        }
+
+        unsafe {
+            let last = ptr_at_index(&self.buf, len - 1).read();
+            let hole = mut_ptr_at_index(&mut self.buf, idx);
+            self.len = I::from_usize(self.len() - 1);
+            ptr::replace(hole, last)
+        }
+    }
+
+    /// Inserts an element at position `index` within the vector, shifting all
+    /// elements after it to the right.
+    ///
+    /// # Panics
+    /// Panics if the vector is already full, or if `index` is out of bounds.
+    /// See [`try_insert`](Vec::try_insert) for a checked version.
+    pub fn insert(&mut self, index: I, element: T) {
+        #[cold]
+        #[inline(never)]
+        fn assert_failed() -> ! {
+            panic!("vector is already at capacity")
+        }
+
+        let result = self.try_insert(index, element);
+        if result.is_err() {
+            assert_failed();
+        }
+    }
+
+    /// Inserts an element at position `index` within the vector, shifting all
+    /// elements after it to the right.
+    ///
+    /// Returns a [`CapacityError`] containing `element` if the vector is
+    /// already at capacity.
+    ///
+    /// # Panics
+    /// Panics if `index` is out of bounds.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut backing_region = [core::mem::MaybeUninit::<u32>::uninit(); 4];
+    /// let mut vec = coca::collections::SliceVec::<u32>::from(&mut backing_region[..]);
+    /// vec.push(1); vec.push(2); vec.push(3);
+    ///
+    /// assert!(vec.try_insert(3, 4).is_ok());
+    /// assert!(vec.try_insert(4, 5).is_err());
+    /// assert_eq!(vec, &[1, 2, 3, 4][..]);
+    /// ```
+    pub fn try_insert(&mut self, index: I, element: T) -> Result<(), CapacityError<T>> {
+        #[cold]
+        #[inline(never)]
+        fn assert_failed(index: usize, len: usize) -> ! {
+            panic!(
+                "insertion index (is {}) should be <= len (is {})",
+                index, len
+            );
+        }
+
+        if self.is_full() {
+            return Err(CapacityError::with_value(element));
+        }
+
+        let idx = index.as_usize();
+        let len = self.len.as_usize();
+        if idx > len {
+            assert_failed(idx, len);
+        }
+
+        let p = mut_ptr_at_index(&mut self.buf, idx);
+        unsafe {
+            ptr::copy(p, p.add(1), len - idx);
+            ptr::write(p, element);
+        }
+
+        self.len = I::from_usize(len + 1);
+        Ok(())
+    }
+
+    /// Appends as many elements from `iter` to the `Vec` as possible.
+    ///
+    /// Returns the iterator of remaining elements if the vector is filled, or
     /// `None` if the iterator runs out of elements first.
     ///
     /// # Examples
@@ -617,6 +1270,64 @@ impl<T, S: Storage<ArrayLayout<T>>, I: Capacity> Vec<T, S, I> {
         Some(iter)
     }
 
+    /// Returns `true` if the vector has room for at least `additional` more
+    /// elements.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut vec = coca::collections::InlineVec::<u32, 4>::new();
+    /// vec.push(1);
+    ///
+    /// assert!(vec.can_hold(3));
+    /// assert!(!vec.can_hold(4));
+    /// assert!(!vec.can_hold(usize::MAX));
+    /// ```
+    #[inline]
+    pub fn can_hold(&self, additional: usize) -> bool {
+        additional <= self.capacity() - self.len()
+    }
+
+    /// Appends all elements from `iter` to the `Vec`, returning `Err` if they
+    /// don't all fit.
+    ///
+    /// In that case, the vector is filled to capacity, and an iterator over
+    /// the remaining elements is returned. To determine whether any elements
+    /// remain once the vector is full, one more element must be taken from
+    /// `iter`; the returned [`Peekable`](core::iter::Peekable) retains it, so
+    /// no elements are lost.
+    ///
+    /// Unlike [`extend_to_capacity`](Vec::extend_to_capacity), this returns
+    /// `Ok` if the iterator runs out of elements exactly as the vector is
+    /// filled.
+    ///
+    /// # Examples
+    /// ```
+    /// use coca::collections::InlineVec;
+    /// let mut v = InlineVec::<u32, 5>::new();
+    ///
+    /// assert!(v.checked_extend(1..=3).is_ok());
+    /// assert!(v.checked_extend(4..=5).is_ok());
+    /// assert!(v.is_full());
+    ///
+    /// v.truncate(3);
+    /// let rest = v.checked_extend(4..=7).unwrap_err();
+    /// assert_eq!(&v, &[1, 2, 3, 4, 5]);
+    /// assert!(rest.eq(6..=7));
+    /// ```
+    pub fn checked_extend<It: core::iter::IntoIterator<Item = T>>(
+        &mut self,
+        iter: It,
+    ) -> Result<(), core::iter::Peekable<It::IntoIter>> {
+        let mut iter = iter.into_iter().peekable();
+        self.extend_to_capacity(&mut iter);
+
+        if iter.peek().is_some() {
+            Err(iter)
+        } else {
+            Ok(())
+        }
+    }
+
     /// Places an element at position `index` within the vector, returning the
     /// element previously stored there.
     ///
@@ -726,6 +1437,86 @@ impl<T, S: Storage<ArrayLayout<T>>, I: Capacity> Vec<T, S, I> {
         self.drain_filter(|_, item| !f(&*item));
     }
 
+    /// Retains only the elements for which `f` returns [`RetainDecision::Keep`],
+    /// until it returns [`RetainDecision::StopKeepingRest`].
+    ///
+    /// Elements are visited in their original order, and may be modified by
+    /// `f`. Once `f` returns `StopKeepingRest`, that element and all following
+    /// elements are kept without being visited. This avoids a full scan when
+    /// the rest of the vector is known to be retained.
+    ///
+    /// If `f` panics, the element being visited and all following elements
+    /// are kept.
+    ///
+    /// # Examples
+    /// ```
+    /// use coca::collections::vec::RetainDecision;
+    ///
+    /// let mut vec = coca::collections::InlineVec::<u32, 8>::new();
+    /// vec.extend([1, 2, 3, 0, 5, 6]);
+    /// vec.retain_while(|x| match *x {
+    ///     0 => RetainDecision::StopKeepingRest,
+    ///     x if x % 2 == 0 => RetainDecision::Remove,
+    ///     _ => RetainDecision::Keep,
+    /// });
+    ///
+    /// assert_eq!(vec, [1, 3, 0, 5, 6]);
+    /// ```
+    pub fn retain_while<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&mut T) -> RetainDecision,
+    {
+        struct Guard<'a, T, S: Storage<ArrayLayout<T>>, I: Capacity> {
+            vec: &'a mut Vec<T, S, I>,
+            original_len: usize,
+            read_index: usize,
+            write_index: usize,
+        }
+
+        impl<T, S: Storage<ArrayLayout<T>>, I: Capacity> Drop for Guard<'_, T, S, I> {
+            fn drop(&mut self) {
+                let tail_len = self.original_len - self.read_index;
+                let base = self.vec.as_mut_ptr();
+                unsafe {
+                    ptr::copy(
+                        base.add(self.read_index),
+                        base.add(self.write_index),
+                        tail_len,
+                    );
+                }
+                self.vec.len = I::from_usize(self.write_index + tail_len);
+            }
+        }
+
+        let original_len = self.len();
+        self.len = I::from_usize(0);
+        let mut g = Guard {
+            vec: self,
+            original_len,
+            read_index: 0,
+            write_index: 0,
+        };
+
+        let base = g.vec.as_mut_ptr();
+        while g.read_index < g.original_len {
+            let cur = unsafe { base.add(g.read_index) };
+            match f(unsafe { &mut *cur }) {
+                RetainDecision::Keep => {
+                    if g.read_index != g.write_index {
+                        unsafe { ptr::copy_nonoverlapping(cur, base.add(g.write_index), 1) };
+                    }
+                    g.read_index += 1;
+                    g.write_index += 1;
+                }
+                RetainDecision::Remove => {
+                    g.read_index += 1;
+                    unsafe { ptr::drop_in_place(cur) };
+                }
+                RetainDecision::StopKeepingRest => break,
+            }
+        }
+    }
+
     /// Creates a draining iterator that removes the specified range in the vector
     /// and yields the removed items.
     ///
@@ -768,11 +1559,56 @@ impl<T, S: Storage<ArrayLayout<T>>, I: Capacity> Vec<T, S, I> {
         }
     }
 
-    /// Creates an iterator which uses a closure to determine if an element
-    /// should be removed.
+    /// Creates a splicing iterator that replaces the specified range in the
+    /// vector with the items yielded by `replace_with`, and yields the removed
+    /// items. `replace_with` does not need to yield as many items as `range`.
     ///
-    /// If the closure returns `true`, the element is removed and yielded.
-    /// If the closure returns `false`, the element will remain in the vector
+    /// The range is removed even if the iterator is not consumed until the
+    /// end. The replacement items are only taken from `replace_with` once the
+    /// `Splice` value is dropped, after all removed items that were not yet
+    /// yielded are dropped.
+    ///
+    /// Because the capacity is fixed, the vector's length after splicing must
+    /// not exceed its capacity. Replacement items are written into the gap
+    /// left by the removed range first; once that is full, the elements after
+    /// the range are moved to the end of the storage block to make room for
+    /// more, so that `replace_with` is consumed in a single pass, without
+    /// knowing its length in advance.
+    ///
+    /// # Panics
+    /// Panics if the starting point is greater than the end point or if the end
+    /// point is greater than the length of the vector.
+    ///
+    /// Dropping the returned `Splice` panics if `replace_with` yields more items
+    /// than fit into the vector. In that case, the vector contains as many of
+    /// the replacement items as fit, and the rest are dropped.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut vec = coca::collections::InlineVec::<u32, 8>::new();
+    /// vec.extend([1, 2, 3, 4]);
+    ///
+    /// let mut removed = coca::collections::InlineVec::<u32, 4>::new();
+    /// removed.extend(vec.splice(1..3, [7, 8, 9]));
+    /// assert_eq!(removed, [2, 3]);
+    /// assert_eq!(vec, [1, 7, 8, 9, 4]);
+    /// ```
+    pub fn splice<R, It>(&mut self, range: R, replace_with: It) -> Splice<'_, T, S, I, It::IntoIter>
+    where
+        R: RangeBounds<I>,
+        It: IntoIter<Item = T>,
+    {
+        Splice {
+            drain: self.drain(range),
+            replace_with: replace_with.into_iter(),
+        }
+    }
+
+    /// Creates an iterator which uses a closure to determine if an element
+    /// should be removed.
+    ///
+    /// If the closure returns `true`, the element is removed and yielded.
+    /// If the closure returns `false`, the element will remain in the vector
     /// and will not be yielded by the iterator.
     ///
     /// When the iterator **is** dropped, all remaining items matching the
@@ -802,6 +1638,50 @@ impl<T, S: Storage<ArrayLayout<T>>, I: Capacity> Vec<T, S, I> {
         self.drain_filter_range(.., filter)
     }
 
+    /// Removes consecutive repeated elements from the vector, returning an
+    /// iterator over the removed elements.
+    ///
+    /// Of each run of equal elements, only the first one is kept. The others
+    /// are yielded in order of their original position.
+    ///
+    /// When the iterator **is** dropped, all remaining duplicates are removed
+    /// from the vector, even if the iterator was not fully consumed. If the
+    /// iterator **is not** dropped (with [`core::mem::forget`] for example),
+    /// the vector may be left empty.
+    ///
+    /// If a comparison panics, the vector keeps all elements that were not
+    /// yet examined, including the two being compared, and no further
+    /// duplicates are removed.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut vec = coca::collections::InlineVec::<u32, 8>::new();
+    /// vec.extend([1, 1, 2, 3, 3, 3, 1]);
+    ///
+    /// let mut removed = coca::collections::InlineVec::<u32, 8>::new();
+    /// removed.extend(vec.dedup_drain());
+    ///
+    /// assert_eq!(vec, [1, 2, 3, 1]);
+    /// assert_eq!(removed, [1, 3, 3]);
+    /// ```
+    pub fn dedup_drain(&mut self) -> DedupDrain<'_, T, S, I>
+    where
+        T: PartialEq,
+    {
+        let original_len = self.len();
+        self.len = I::from_usize(0);
+
+        let kept = original_len.min(1);
+        DedupDrain {
+            parent: self,
+            eq: T::eq,
+            original_len,
+            read_index: kept,
+            write_index: kept,
+            panic_flag: false,
+        }
+    }
+
     /// Creates an iterator which uses a closure to determine if an element
     /// in the specified range should be removed.
     ///
@@ -857,6 +1737,55 @@ impl<T, S: Storage<ArrayLayout<T>>, I: Capacity> Vec<T, S, I> {
     }
 }
 
+impl<T: Clone, S: Storage<ArrayLayout<T>>, I: Capacity> Vec<T, S, I> {
+    /// Clones and appends all elements of each slice in `parts` to the `Vec`,
+    /// in order.
+    ///
+    /// Returns [`Err`] without modifying the vector if the remaining space is
+    /// insufficient to hold all the parts combined.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut vec = coca::collections::InlineVec::<u8, 6>::new();
+    /// assert!(vec.try_concat(&[b"ab", b"", b"cd"]).is_ok());
+    /// assert!(vec.try_concat(&[b"e", b"fg"]).is_err());
+    /// assert_eq!(vec, *b"abcd");
+    /// assert!(vec.try_concat(&[b"e", b"f"]).is_ok());
+    /// assert_eq!(vec, *b"abcdef");
+    /// ```
+    pub fn try_concat(&mut self, parts: &[&[T]]) -> crate::Result<()> {
+        let additional = parts
+            .iter()
+            .try_fold(0usize, |acc, part| acc.checked_add(part.len()));
+        match additional {
+            Some(n) if n <= self.capacity() - self.len() => {}
+            _ => return CapacityError::new(),
+        }
+
+        for part in parts {
+            for x in *part {
+                self.push(x.clone());
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Clones and appends all elements of each slice in `parts` to the `Vec`,
+    /// in order.
+    ///
+    /// # Panics
+    /// Panics if the remaining space is insufficient to hold all the parts
+    /// combined, in which case the vector is left unchanged. See
+    /// [`try_concat`](Vec::try_concat) for a checked version that never panics.
+    #[track_caller]
+    #[inline]
+    pub fn concat(&mut self, parts: &[&[T]]) {
+        self.try_concat(parts)
+            .expect("the combined length of all parts must not exceed the remaining space");
+    }
+}
+
 impl<T: Copy, S: Storage<ArrayLayout<T>>, I: Capacity> Vec<T, S, I> {
     /// Copies and appends all elements in a slice to the `Vec`.
     ///
@@ -1263,9 +2192,35 @@ where
     }
 }
 
-impl<T: PartialOrd, S: Storage<ArrayLayout<T>>, I: Capacity> PartialOrd for Vec<T, S, I> {
+impl<T, AS, AI, BS, BI> PartialOrd<Vec<T, BS, BI>> for Vec<T, AS, AI>
+where
+    T: PartialOrd,
+    AS: Storage<ArrayLayout<T>>,
+    BS: Storage<ArrayLayout<T>>,
+    AI: Capacity,
+    BI: Capacity,
+{
+    /// Compares the vectors' contents [lexicographically](Ord#lexicographical-comparison),
+    /// regardless of their storage and index types.
+    ///
+    /// # Examples
+    /// ```
+    /// use coca::collections::{InlineVec, SliceVec};
+    /// use core::mem::MaybeUninit;
+    ///
+    /// let mut backing_region = [MaybeUninit::<u32>::uninit(); 8];
+    /// let mut a = SliceVec::<u32>::from(&mut backing_region[..]);
+    /// a.extend([1, 2, 3]);
+    ///
+    /// let mut b = InlineVec::<u32, 4, u8>::new();
+    /// b.extend([1, 2]);
+    ///
+    /// assert!(a > b);
+    /// b.push(4);
+    /// assert!(a < b);
+    /// ```
     #[inline]
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+    fn partial_cmp(&self, other: &Vec<T, BS, BI>) -> Option<Ordering> {
         self.as_slice().partial_cmp(other.as_slice())
     }
 }
@@ -1399,6 +2354,22 @@ impl<'a, T, S: Storage<ArrayLayout<T>>, I: Capacity> IntoIter for &'a mut Vec<T,
     type Item = &'a mut T;
     type IntoIter = core::slice::IterMut<'a, T>;
 
+    /// Creates an iterator yielding mutable references to the vector's
+    /// elements, equivalent to [`iter_mut`](slice::iter_mut).
+    ///
+    /// # Examples
+    /// ```
+    /// let mut vec = coca::collections::InlineVec::<u32, 8>::new();
+    /// vec.extend([1, 2, 3]);
+    ///
+    /// vec.iter_mut().for_each(|x| *x *= 2);
+    /// assert_eq!(vec, [2, 4, 6]);
+    ///
+    /// for x in &mut vec {
+    ///     *x *= 2;
+    /// }
+    /// assert_eq!(vec, [4, 8, 12]);
+    /// ```
     fn into_iter(self) -> Self::IntoIter {
         self.as_mut_slice().iter_mut()
     }
@@ -1473,6 +2444,203 @@ impl<'p, T, S: Storage<ArrayLayout<T>>, I: Capacity> Drop for Drain<'p, T, S, I>
     }
 }
 
+/// A splicing iterator for `Vec<T>`.
+///
+/// This `struct` is created by [`Vec::splice`]. See its documentation for more.
+pub struct Splice<'p, T, S: Storage<ArrayLayout<T>>, I: Capacity, It: Iterator<Item = T>> {
+    drain: Drain<'p, T, S, I>,
+    replace_with: It,
+}
+
+impl<T, S: Storage<ArrayLayout<T>>, I: Capacity, It: Iterator<Item = T>> Iterator
+    for Splice<'_, T, S, I, It>
+{
+    type Item = T;
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.drain.size_hint()
+    }
+
+    fn next(&mut self) -> Option<T> {
+        self.drain.next()
+    }
+}
+
+impl<T, S: Storage<ArrayLayout<T>>, I: Capacity, It: Iterator<Item = T>> DoubleEndedIterator
+    for Splice<'_, T, S, I, It>
+{
+    fn next_back(&mut self) -> Option<T> {
+        self.drain.next_back()
+    }
+}
+
+impl<T, S: Storage<ArrayLayout<T>>, I: Capacity, It: Iterator<Item = T>> ExactSizeIterator
+    for Splice<'_, T, S, I, It>
+{
+}
+impl<T, S: Storage<ArrayLayout<T>>, I: Capacity, It: Iterator<Item = T>> FusedIterator
+    for Splice<'_, T, S, I, It>
+{
+}
+
+impl<T, S: Storage<ArrayLayout<T>>, I: Capacity, It: Iterator<Item = T>> Drop
+    for Splice<'_, T, S, I, It>
+{
+    fn drop(&mut self) {
+        #[cold]
+        #[inline(never)]
+        fn assert_failed(capacity: usize) -> ! {
+            panic!(
+                "spliced vector length should be <= capacity (is {})",
+                capacity
+            )
+        }
+
+        self.drain.by_ref().for_each(drop);
+
+        // The gap between `target_start` and `target_end` is filled with
+        // replacement items; when the drain is dropped afterwards, the tail
+        // is moved to `target_start`, whether or not the gap is full, and
+        // even if `replace_with` panics.
+        let drain = &mut self.drain;
+        loop {
+            while drain.target_start < drain.target_end {
+                match self.replace_with.next() {
+                    Some(item) => unsafe {
+                        mut_ptr_at_index(&mut drain.parent.buf, drain.target_start).write(item);
+                    },
+                    None => return,
+                }
+                drain.target_start += 1;
+            }
+
+            let item = self.replace_with.next();
+            if item.is_none() {
+                return;
+            }
+
+            let capacity = drain.parent.capacity();
+            if drain.original_len == capacity {
+                drop(item);
+                assert_failed(capacity);
+            }
+
+            // make as much room as possible by moving the tail to the end
+            let tail_len = drain.original_len - drain.target_end;
+            let new_end = capacity - tail_len;
+            unsafe {
+                let src = mut_ptr_at_index(&mut drain.parent.buf, drain.target_end);
+                let dst = mut_ptr_at_index(&mut drain.parent.buf, new_end);
+                ptr::copy(src, dst, tail_len);
+            }
+            drain.original_len = capacity;
+            drain.target_end = new_end;
+
+            if let Some(item) = item {
+                unsafe {
+                    mut_ptr_at_index(&mut drain.parent.buf, drain.target_start).write(item);
+                }
+                drain.target_start += 1;
+            }
+        }
+    }
+}
+
+/// The decision returned by the closure passed to [`Vec::retain_while`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum RetainDecision {
+    /// Keep the visited element, and continue with the next one.
+    Keep,
+    /// Remove the visited element, and continue with the next one.
+    Remove,
+    /// Keep the visited element and all following elements without visiting
+    /// them.
+    StopKeepingRest,
+}
+
+/// An iterator over the consecutive duplicates removed from a vector.
+///
+/// This struct is created by [`Vec::dedup_drain`]. See its documentation for more.
+pub struct DedupDrain<'p, T, S: Storage<ArrayLayout<T>>, I: Capacity> {
+    parent: &'p mut Vec<T, S, I>,
+    eq: fn(&T, &T) -> bool,
+    original_len: usize,
+    read_index: usize,
+    write_index: usize,
+    // set while comparing, so that a panicking comparison isn't repeated on drop
+    panic_flag: bool,
+}
+
+impl<T, S: Storage<ArrayLayout<T>>, I: Capacity> Iterator for DedupDrain<'_, T, S, I> {
+    type Item = T;
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.original_len - self.read_index))
+    }
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let base = self.parent.as_mut_ptr();
+        while self.read_index < self.original_len {
+            // the elements in `0..write_index` are kept, those in
+            // `read_index..original_len` are yet to be examined
+            unsafe {
+                let src = base.add(self.read_index);
+                let last_kept = base.add(self.write_index - 1);
+
+                self.panic_flag = true;
+                let is_duplicate = (self.eq)(&*src, &*last_kept);
+                self.panic_flag = false;
+
+                self.read_index += 1;
+                if is_duplicate {
+                    return Some(src.read());
+                }
+
+                let dst = base.add(self.write_index);
+                if src != dst {
+                    ptr::copy_nonoverlapping(src, dst, 1);
+                }
+            }
+            self.write_index += 1;
+        }
+
+        None
+    }
+}
+
+impl<T, S: Storage<ArrayLayout<T>>, I: Capacity> FusedIterator for DedupDrain<'_, T, S, I> {}
+
+impl<T, S: Storage<ArrayLayout<T>>, I: Capacity> Drop for DedupDrain<'_, T, S, I> {
+    fn drop(&mut self) {
+        struct Guard<'a, 'p, T, S: Storage<ArrayLayout<T>>, I: Capacity>(
+            &'a mut DedupDrain<'p, T, S, I>,
+        );
+
+        impl<T, S: Storage<ArrayLayout<T>>, I: Capacity> Drop for Guard<'_, '_, T, S, I> {
+            fn drop(&mut self) {
+                // keep the elements that were not examined, in case a
+                // comparison panicked before all of them were visited
+                let drain = &mut *self.0;
+                let tail_len = drain.original_len - drain.read_index;
+                let base = drain.parent.as_mut_ptr();
+                unsafe {
+                    ptr::copy(
+                        base.add(drain.read_index),
+                        base.add(drain.write_index),
+                        tail_len,
+                    );
+                }
+                drain.parent.len = I::from_usize(drain.write_index + tail_len);
+            }
+        }
+
+        let guard = Guard(self);
+        if !guard.0.panic_flag {
+            guard.0.for_each(drop);
+        }
+    }
+}
+
 /// An iterator which uses a closure to determine if an element should be removed.
 ///
 /// This struct is created by [`Vec::drain_filter`]. See its documentation for more.
@@ -1641,96 +2809,466 @@ impl<T, I: Capacity> crate::collections::SliceVec<'_, T, I> {
             }
         }
     }
-}
 
-#[cfg(feature = "alloc")]
-#[cfg_attr(docs_rs, doc(cfg(feature = "alloc")))]
-impl<T, I: Capacity> crate::collections::AllocVec<T, I> {
-    /// Constructs a new, empty `AllocVec<T, I>` with the specified capacity.
+    /// Consumes the vector and splits it into two independent vectors, with
+    /// the elements in `[0, mid)` going to the first one, and those in
+    /// `[mid, len)` to the second one.
+    ///
+    /// The underlying slice is split at `mid`, so the first vector is full,
+    /// and the second one receives all of the spare capacity. See
+    /// [`split_and_shrink_to`](Vec::split_and_shrink_to) for splitting
+    /// storage beyond the last element.
     ///
     /// # Panics
-    /// Panics if the specified capacity cannot be represented by a `usize`.
-    pub fn with_capacity(capacity: I) -> Self {
-        let cap = capacity.as_usize();
-        if capacity != I::from_usize(cap) {
-            buffer_too_large_for_index_type::<I>();
+    /// Panics if `mid > len`.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut buf = [core::mem::MaybeUninit::uninit(); 8];
+    /// let mut v = coca::collections::SliceVec::<'_, u32>::from(&mut buf[..]);
+    /// v.extend(1..=5);
+    ///
+    /// let (mut front, mut back) = v.into_split(2);
+    /// assert_eq!(front, [1, 2]);
+    /// assert!(front.is_full());
+    /// assert_eq!(back, [3, 4, 5]);
+    /// assert_eq!(back.capacity(), 6);
+    ///
+    /// front.pop();
+    /// back.push(6);
+    /// assert_eq!(front, [1]);
+    /// assert_eq!(back, [3, 4, 5, 6]);
+    /// ```
+    pub fn into_split(mut self, mid: I) -> (Self, Self) {
+        #[cold]
+        #[inline(never)]
+        fn assert_failed(mid: usize, len: usize) -> ! {
+            panic!("mid (is {}) should be <= len (is {})", mid, len);
         }
 
-        Vec {
-            len: I::from_usize(0),
-            buf: crate::storage::AllocStorage::with_capacity(cap),
-            elem: PhantomData,
+        if mid.as_usize() > self.len() {
+            assert_failed(mid.as_usize(), self.len());
         }
+
+        let back = self.split_and_shrink_to(mid);
+        (self, back)
     }
 }
 
-#[cfg(feature = "alloc")]
-#[cfg_attr(docs_rs, doc(cfg(feature = "alloc")))]
-impl<T: Clone, I: Capacity> Clone for crate::collections::AllocVec<T, I> {
-    fn clone(&self) -> Self {
-        let mut result = Self::with_capacity(I::from_usize(self.capacity()));
-        result.extend(self.iter().cloned());
-        result
-    }
+/// A read-only view of a slice of initialized elements, presenting it as a
+/// full vector.
+///
+/// This allows borrowed data to be used where the `Vec` API is expected,
+/// without copying it. Mutation is rejected at compile time.
+///
+/// # Examples
+/// ```
+/// use coca::collections::vec::ReadOnlySliceVec;
+///
+/// let data = [1, 2, 3];
+/// let view = ReadOnlySliceVec::<'_, i32, u8>::from_initialized_slice(&data);
+/// assert_eq!(view.len(), 3);
+/// assert_eq!(view.capacity(), 3);
+/// assert!(view.is_full());
+///
+/// assert_eq!(view.get(1), Some(&2));
+/// assert_eq!(view[2u8], 3);
+/// assert_eq!(view.binary_search(&3), Ok(2));
+///
+/// let mut sum = 0;
+/// for x in view.iter() {
+///     sum += x;
+/// }
+/// assert_eq!(sum, 6);
+/// ```
+pub struct ReadOnlySliceVec<'a, T, I: Capacity = usize> {
+    slice: &'a [T],
+    idx: PhantomData<I>,
 }
 
-impl<T, I: Capacity, const C: usize> Vec<T, InlineStorage<T, C>, I> {
-    /// Constructs a new, empty `Vec` backed by an inline array.
+impl<'a, T, I: Capacity> ReadOnlySliceVec<'a, T, I> {
+    /// Constructs a read-only view of a slice of initialized elements.
     ///
     /// # Panics
-    /// Panics if `C` cannot be represented as a value of type `I`.
-    ///
-    /// # Examples
-    /// ```
-    /// let vec = coca::collections::InlineVec::<u32, 6>::new();
-    /// assert_eq!(vec.capacity(), 6);
-    /// assert_eq!(vec.len(), 0);
-    /// ```
-    #[inline]
-    pub fn new() -> Self {
-        if C > I::MAX_REPRESENTABLE {
-            buffer_too_large_for_index_type::<I>();
+    /// Panics if the index type `I` cannot represent `slice.len()`.
+    pub fn from_initialized_slice(slice: &'a [T]) -> Self {
+        if slice.len() > I::MAX_REPRESENTABLE {
+            buffer_too_large_for_index_type::<I>(slice.len());
         }
 
-        Vec {
-            len: I::from_usize(0),
-            buf: unsafe { MaybeUninit::uninit().assume_init() },
-            elem: PhantomData,
+        ReadOnlySliceVec {
+            slice,
+            idx: PhantomData,
         }
     }
-}
 
-impl<T, I: Capacity, const C: usize> Default for Vec<T, InlineStorage<T, C>, I> {
-    fn default() -> Self {
-        Self::new()
+    /// Returns the number of elements the view can hold, which is always
+    /// equal to its length.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.slice.len()
     }
-}
 
-impl<T: Clone, I: Capacity, const C: usize> core::clone::Clone for Vec<T, InlineStorage<T, C>, I> {
-    fn clone(&self) -> Self {
-        let mut ret = Self::new();
-        ret.clone_from(self);
-        ret
+    /// Returns the number of elements in the view, also referred to as its *length*.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.slice.len()
     }
 
-    fn clone_from(&mut self, source: &Self) {
-        self.clear();
-        for next in source {
-            self.push(next.clone());
-        }
+    /// Returns `true` if the view contains no elements.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.slice.is_empty()
     }
-}
 
-impl<T: Clone, I: Capacity, const C: usize> From<&[T]> for Vec<T, InlineStorage<T, C>, I> {
-    fn from(source: &[T]) -> Self {
-        if C > I::MAX_REPRESENTABLE {
-            buffer_too_large_for_index_type::<I>();
-        }
+    /// Returns `true` if the view contains the maximum number of elements,
+    /// which is always the case.
+    #[inline]
+    pub fn is_full(&self) -> bool {
+        true
+    }
 
-        assert!(
-            source.len() <= C,
-            "source should not have more than {} elements (has {})",
-            C,
+    /// Returns a reference to the element at the specified index, or [`None`]
+    /// if the index is out of bounds.
+    #[inline]
+    pub fn get(&self, index: I) -> Option<&'a T> {
+        self.slice.get(index.as_usize())
+    }
+
+    /// Extracts the underlying slice.
+    #[inline]
+    pub fn as_slice(&self) -> &'a [T] {
+        self.slice
+    }
+}
+
+impl<T, I: Capacity> Clone for ReadOnlySliceVec<'_, T, I> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T, I: Capacity> Copy for ReadOnlySliceVec<'_, T, I> {}
+
+impl<'a, T, I: Capacity> From<&'a [T]> for ReadOnlySliceVec<'a, T, I> {
+    /// Equivalent to [`ReadOnlySliceVec::from_initialized_slice`].
+    fn from(slice: &'a [T]) -> Self {
+        Self::from_initialized_slice(slice)
+    }
+}
+
+impl<T, I: Capacity> core::ops::Deref for ReadOnlySliceVec<'_, T, I> {
+    type Target = [T];
+    fn deref(&self) -> &[T] {
+        self.slice
+    }
+}
+
+impl<T, I: Capacity> core::ops::Index<I> for ReadOnlySliceVec<'_, T, I> {
+    type Output = T;
+    fn index(&self, index: I) -> &Self::Output {
+        &self.slice[index.as_usize()]
+    }
+}
+
+impl<T: core::fmt::Debug, I: Capacity> core::fmt::Debug for ReadOnlySliceVec<'_, T, I> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.slice.fmt(f)
+    }
+}
+
+impl<'a, T, I: Capacity> IntoIter for ReadOnlySliceVec<'a, T, I> {
+    type Item = &'a T;
+    type IntoIter = core::slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.slice.iter()
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(docs_rs, doc(cfg(feature = "alloc")))]
+impl<T, I: Capacity> crate::collections::AllocVec<T, I> {
+    /// Constructs a new, empty `AllocVec<T, I>` with the specified capacity.
+    ///
+    /// # Panics
+    /// Panics if the specified capacity cannot be represented by a `usize`.
+    pub fn with_capacity(capacity: I) -> Self {
+        let cap = capacity.as_usize();
+        if capacity != I::from_usize(cap) {
+            crate::storage::capacity_too_large_for_usize(capacity);
+        }
+
+        Vec {
+            len: I::from_usize(0),
+            buf: crate::storage::AllocStorage::with_capacity(cap),
+            elem: PhantomData,
+        }
+    }
+
+    /// Checks that at least `additional` more elements can be inserted into
+    /// the vector, returning [`Err`] otherwise.
+    ///
+    /// Unlike `std::vec::Vec::try_reserve`, this never reallocates, since the
+    /// capacity of an `AllocVec` is fixed after construction; it exists to
+    /// ease migration from the standard library's vector type, and to make
+    /// that contract explicit.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut vec = coca::collections::AllocVec::<u32>::with_capacity(4);
+    /// vec.extend([1, 2]);
+    ///
+    /// assert!(vec.try_reserve(2).is_ok());
+    /// assert!(vec.try_reserve(3).is_err());
+    /// assert_eq!(vec.capacity(), 4);
+    /// ```
+    pub fn try_reserve(&mut self, additional: I) -> crate::Result<()> {
+        if additional.as_usize() > self.capacity() - self.len() {
+            return CapacityError::new();
+        }
+
+        Ok(())
+    }
+
+    /// Asserts that at least `additional` more elements can be inserted into
+    /// the vector.
+    ///
+    /// This never reallocates; see [`try_reserve`](Vec::try_reserve).
+    ///
+    /// # Panics
+    /// Panics if the remaining capacity is less than `additional`.
+    #[track_caller]
+    pub fn reserve(&mut self, additional: I) {
+        #[cold]
+        #[inline(never)]
+        #[track_caller]
+        fn assert_failed(additional: usize, remaining: usize) -> ! {
+            panic!(
+                "additional capacity (is {}) should be <= remaining capacity (is {})",
+                additional, remaining
+            );
+        }
+
+        if self.try_reserve(additional).is_err() {
+            assert_failed(additional.as_usize(), self.capacity() - self.len());
+        }
+    }
+
+    /// Asserts that at least `additional` more elements can be inserted into
+    /// the vector.
+    ///
+    /// Since this never reallocates, it is equivalent to
+    /// [`reserve`](Vec::reserve).
+    ///
+    /// # Panics
+    /// Panics if the remaining capacity is less than `additional`.
+    #[inline]
+    #[track_caller]
+    pub fn reserve_exact(&mut self, additional: I) {
+        self.reserve(additional);
+    }
+
+    /// Shrinks the capacity of the vector with a lower bound, reallocating
+    /// the underlying storage block.
+    ///
+    /// The capacity will remain at least as large as both the length and
+    /// the supplied value. If the current capacity is less than the lower
+    /// limit, this is a no-op.
+    ///
+    /// # Panics
+    /// Panics if reallocation fails.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut vec = coca::collections::AllocVec::<u32>::with_capacity(10);
+    /// vec.extend([1, 2, 3]);
+    ///
+    /// vec.shrink_to(4);
+    /// assert_eq!(vec.capacity(), 4);
+    /// vec.shrink_to(0);
+    /// assert_eq!(vec.capacity(), 3);
+    /// assert_eq!(vec, &[1, 2, 3][..]);
+    /// ```
+    pub fn shrink_to(&mut self, min_capacity: I) {
+        let new_cap = usize::max(self.len(), min_capacity.as_usize());
+        if new_cap < self.capacity() {
+            self.buf.shrink_to(new_cap);
+        }
+    }
+
+    /// Shrinks the capacity of the vector as much as possible, reallocating
+    /// the underlying storage block.
+    ///
+    /// Equivalent to `s.shrink_to(0)`.
+    ///
+    /// # Panics
+    /// Panics if reallocation fails.
+    #[inline]
+    pub fn shrink_to_fit(&mut self) {
+        self.shrink_to(I::from_usize(0));
+    }
+
+    /// Converts the vector into [`Box<[T]>`](alloc::boxed::Box), shrinking
+    /// the underlying storage block to fit first.
+    ///
+    /// This reuses the vector's allocation, and does not copy any elements.
+    ///
+    /// # Panics
+    /// Panics if reallocation fails.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut vec = coca::collections::AllocVec::<u32>::with_capacity(10);
+    /// vec.extend([1, 2, 3]);
+    ///
+    /// let slice = vec.into_boxed_slice();
+    /// assert_eq!(&*slice, &[1, 2, 3]);
+    /// ```
+    pub fn into_boxed_slice(mut self) -> alloc::boxed::Box<[T]> {
+        self.shrink_to_fit();
+        let (buf, len) = self.into_raw_parts();
+        let len = len.as_usize();
+        let ptr = buf.into_raw().cast::<T>();
+        unsafe { alloc::boxed::Box::from_raw(ptr::slice_from_raw_parts_mut(ptr, len)) }
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(docs_rs, doc(cfg(feature = "alloc")))]
+impl<T: Clone, I: Capacity> Clone for crate::collections::AllocVec<T, I> {
+    fn clone(&self) -> Self {
+        let mut result = Self::with_capacity(I::from_usize(self.capacity()));
+        result.extend(self.iter().cloned());
+        result
+    }
+}
+
+impl<T, I: Capacity, const C: usize> Vec<T, InlineStorage<T, C>, I> {
+    /// Constructs a new, empty `Vec` backed by an inline array.
+    ///
+    /// # Panics
+    /// Panics if `C` cannot be represented as a value of type `I`.
+    ///
+    /// # Examples
+    /// ```
+    /// let vec = coca::collections::InlineVec::<u32, 6>::new();
+    /// assert_eq!(vec.capacity(), 6);
+    /// assert_eq!(vec.len(), 0);
+    /// ```
+    #[inline]
+    pub fn new() -> Self {
+        if C > I::MAX_REPRESENTABLE {
+            buffer_too_large_for_index_type::<I>(C);
+        }
+
+        Vec {
+            len: I::from_usize(0),
+            buf: unsafe { MaybeUninit::uninit().assume_init() },
+            elem: PhantomData,
+        }
+    }
+
+    /// Constructs a new, full `Vec` backed by an inline array, where each
+    /// element is the result of calling `f` with its index, in ascending
+    /// order.
+    ///
+    /// If `f` panics, all elements initialized up to that point are dropped.
+    ///
+    /// # Panics
+    /// Panics if `C` cannot be represented as a value of type `I`.
+    ///
+    /// # Examples
+    /// ```
+    /// let vec = coca::collections::InlineVec::<usize, 4>::from_fn(|i| i * i);
+    /// assert!(vec.is_full());
+    /// assert_eq!(vec, [0, 1, 4, 9]);
+    /// ```
+    pub fn from_fn<F: FnMut(usize) -> T>(mut f: F) -> Self {
+        let mut result = Self::new();
+        for i in 0..C {
+            // the length is incremented after every write, so a panic in `f`
+            // drops exactly the elements initialized so far
+            result.push(f(i));
+        }
+        result
+    }
+}
+
+macro_rules! impl_new_const {
+    ($($idx:ty),*) => {$(
+        impl<T, const C: usize> Vec<T, InlineStorage<T, C>, $idx> {
+            /// Constructs a new, empty `Vec` backed by an inline array, in a
+            /// `const` context.
+            ///
+            /// Unlike [`new`](Vec::new), this is a `const fn`, so it can be
+            /// used to initialize a `const` or `static` item. Since trait
+            /// methods can't be called in `const` contexts, it is only
+            /// available with the built-in index types.
+            ///
+            /// # Panics
+            /// Panics if `C` cannot be represented as a value of the index
+            /// type. In a `const` context, this is a compile-time error.
+            ///
+            /// # Examples
+            /// ```
+            /// use coca::collections::InlineVec;
+            ///
+            /// const EMPTY: InlineVec<u32, 4> = InlineVec::<u32, 4>::new_const();
+            /// let mut vec = EMPTY;
+            /// vec.push(1);
+            ///
+            /// assert_eq!(vec.capacity(), 4);
+            /// assert_eq!(EMPTY.len(), 0);
+            /// ```
+            pub const fn new_const() -> Self {
+                if C > <$idx as Capacity>::MAX_REPRESENTABLE {
+                    panic!("inline storage block cannot be fully indexed by the index type");
+                }
+
+                Vec {
+                    len: 0,
+                    buf: unsafe { MaybeUninit::uninit().assume_init() },
+                    elem: PhantomData,
+                }
+            }
+        }
+    )*};
+}
+
+impl_new_const!(u8, u16, u32, u64, usize);
+
+impl<T, I: Capacity, const C: usize> Default for Vec<T, InlineStorage<T, C>, I> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Clone, I: Capacity, const C: usize> core::clone::Clone for Vec<T, InlineStorage<T, C>, I> {
+    fn clone(&self) -> Self {
+        let mut ret = Self::new();
+        ret.clone_from(self);
+        ret
+    }
+
+    fn clone_from(&mut self, source: &Self) {
+        self.clear();
+        for next in source {
+            self.push(next.clone());
+        }
+    }
+}
+
+impl<T: Clone, I: Capacity, const C: usize> From<&[T]> for Vec<T, InlineStorage<T, C>, I> {
+    fn from(source: &[T]) -> Self {
+        if C > I::MAX_REPRESENTABLE {
+            buffer_too_large_for_index_type::<I>(C);
+        }
+
+        assert!(
+            source.len() <= C,
+            "source should not have more than {} elements (has {})",
+            C,
             source.len()
         );
 
@@ -1745,7 +3283,7 @@ impl<T: Clone, I: Capacity, const C: usize> From<&[T]> for Vec<T, InlineStorage<
 impl<T: Clone, I: Capacity, const C: usize> From<&mut [T]> for Vec<T, InlineStorage<T, C>, I> {
     fn from(source: &mut [T]) -> Self {
         if C > I::MAX_REPRESENTABLE {
-            buffer_too_large_for_index_type::<I>();
+            buffer_too_large_for_index_type::<I>(C);
         }
 
         assert!(
@@ -1763,6 +3301,29 @@ impl<T: Clone, I: Capacity, const C: usize> From<&mut [T]> for Vec<T, InlineStor
     }
 }
 
+impl<T, I: Capacity, const C: usize> From<[T; C]> for Vec<T, InlineStorage<T, C>, I> {
+    /// Moves the elements of an array into a full vector backed by an
+    /// inline array of the same size.
+    ///
+    /// # Examples
+    /// ```
+    /// let vec = coca::collections::InlineVec::<u32, 3>::from([1, 2, 3]);
+    /// assert!(vec.is_full());
+    /// assert_eq!(vec, [1, 2, 3]);
+    /// ```
+    fn from(source: [T; C]) -> Self {
+        let source = core::mem::ManuallyDrop::new(source);
+        // SAFETY: `MaybeUninit<T>` has the same layout as `T`, and the
+        // elements are not dropped in place because of the `ManuallyDrop`
+        let buf = unsafe { ptr::read(ptr::addr_of!(*source).cast::<InlineStorage<T, C>>()) };
+
+        let mut ret = Self::from(buf);
+        // SAFETY: all `C` elements were initialized by the source array
+        unsafe { ret.set_len(I::from_usize(C)) };
+        ret
+    }
+}
+
 impl<V, T, S, I, const N: usize> PartialEq<Vec<T, S, I>> for [V; N]
 where
     V: PartialEq<T>,
@@ -1801,9 +3362,676 @@ impl<T, I: Capacity, const C: usize> core::iter::FromIterator<T>
     }
 }
 
+/// Performs a stable two-way merge of the sorted slices `a` and `b`,
+/// appending clones of their elements to `dst` in ascending order.
+///
+/// Of any two equal elements, those from `a` are placed before those from `b`,
+/// and elements from the same slice keep their relative order. If either
+/// input is not sorted, the order of the output is unspecified.
+///
+/// # Panics
+/// Panics if `dst` does not have enough spare capacity for `a.len() + b.len()`
+/// additional elements. In this case, `dst` is left unchanged.
+///
+/// # Examples
+/// ```
+/// let a = [1, 3, 5, 7];
+/// let b = [2, 3, 4];
+/// let mut backing = [core::mem::MaybeUninit::uninit(); 8];
+/// let mut dst = coca::collections::SliceVec::<u32>::from(&mut backing[..]);
+///
+/// coca::collections::vec::merge_into(&a, &b, &mut dst);
+/// assert_eq!(dst, [1, 2, 3, 3, 4, 5, 7]);
+/// ```
+#[track_caller]
+pub fn merge_into<T, S, I>(a: &[T], b: &[T], dst: &mut Vec<T, S, I>)
+where
+    T: Clone + Ord,
+    S: Storage<ArrayLayout<T>>,
+    I: Capacity,
+{
+    merge_into_by(a, b, dst, T::cmp);
+}
+
+/// Performs a stable two-way merge of the slices `a` and `b`, which must be
+/// sorted with respect to the comparator function `compare`, appending clones
+/// of their elements to `dst`.
+///
+/// See [`merge_into`] for details.
+///
+/// # Panics
+/// Panics if `dst` does not have enough spare capacity for `a.len() + b.len()`
+/// additional elements. In this case, `dst` is left unchanged.
+///
+/// # Examples
+/// ```
+/// let a = [7, 5, 3];
+/// let b = [6, 5, 0];
+/// let mut dst = coca::collections::InlineVec::<u32, 6>::new();
+///
+/// coca::collections::vec::merge_into_by(&a, &b, &mut dst, |x, y| y.cmp(x));
+/// assert_eq!(dst, [7, 6, 5, 5, 3, 0]);
+/// ```
+#[track_caller]
+pub fn merge_into_by<T, S, I, F>(a: &[T], b: &[T], dst: &mut Vec<T, S, I>, mut compare: F)
+where
+    T: Clone,
+    S: Storage<ArrayLayout<T>>,
+    I: Capacity,
+    F: FnMut(&T, &T) -> Ordering,
+{
+    #[cold]
+    #[inline(never)]
+    #[track_caller]
+    fn assert_failed(required: Option<usize>, available: usize) -> ! {
+        match required {
+            Some(required) => panic!(
+                "destination spare capacity (is {}) should be >= combined length of inputs (is {})",
+                available, required
+            ),
+            None => panic!(
+                "destination spare capacity (is {}) should be >= combined length of inputs (overflows usize)",
+                available
+            ),
+        }
+    }
+
+    let required = a.len().checked_add(b.len());
+    let available = dst.capacity() - dst.len();
+    match required {
+        Some(required) if required <= available => {}
+        _ => assert_failed(required, available),
+    }
+
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() && j < b.len() {
+        if compare(&b[j], &a[i]) == Ordering::Less {
+            dst.push(b[j].clone());
+            j += 1;
+        } else {
+            dst.push(a[i].clone());
+            i += 1;
+        }
+    }
+
+    for x in a[i..].iter().chain(&b[j..]) {
+        dst.push(x.clone());
+    }
+}
+
+/// Performs a stable two-way merge of the slices `a` and `b`, which must be
+/// sorted with respect to the key extraction function `f`, appending clones
+/// of their elements to `dst`.
+///
+/// See [`merge_into`] for details.
+///
+/// # Panics
+/// Panics if `dst` does not have enough spare capacity for `a.len() + b.len()`
+/// additional elements. In this case, `dst` is left unchanged.
+///
+/// # Examples
+/// ```
+/// let a = [(1, 'a'), (2, 'a')];
+/// let b = [(1, 'b'), (3, 'b')];
+/// let mut dst = coca::collections::InlineVec::<(u32, char), 4>::new();
+///
+/// coca::collections::vec::merge_into_by_key(&a, &b, &mut dst, |&(k, _)| k);
+/// assert_eq!(dst, [(1, 'a'), (1, 'b'), (2, 'a'), (3, 'b')]);
+/// ```
+#[track_caller]
+pub fn merge_into_by_key<T, S, I, K, F>(a: &[T], b: &[T], dst: &mut Vec<T, S, I>, mut f: F)
+where
+    T: Clone,
+    S: Storage<ArrayLayout<T>>,
+    I: Capacity,
+    K: Ord,
+    F: FnMut(&T) -> K,
+{
+    merge_into_by(a, b, dst, |x, y| f(x).cmp(&f(y)));
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+
+    #[test]
+    fn max_capacity_of_index_types() {
+        assert_eq!(u8::max_capacity(), 255);
+        assert_eq!(u16::max_capacity(), 65535);
+        assert_eq!(u32::max_capacity(), 0xFFFF_FFFF);
+        assert_eq!(u64::max_capacity(), usize::MAX);
+        assert_eq!(usize::max_capacity(), usize::MAX);
+    }
+
+    #[test]
+    #[should_panic(expected = "capacity 300 exceeds u8 index max of 255")]
+    fn index_type_too_small_for_storage() {
+        let mut backing_region = [core::mem::MaybeUninit::<u32>::uninit(); 300];
+        let _ = SliceVec::<u32, u8>::from(&mut backing_region[..]);
+    }
+
+    #[test]
+    fn split_at_mut_covers_live_elements() {
+        let mut vec = InlineVec::<u32, 8, u8>::new();
+        vec.extend(0..5);
+
+        let (left, right) = vec.split_at_mut(0);
+        assert!(left.is_empty());
+        assert_eq!(right, [0, 1, 2, 3, 4]);
+
+        let (left, right) = vec.split_at_mut(5);
+        assert_eq!(left, [0, 1, 2, 3, 4]);
+        assert!(right.is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "mid (is 6) should be <= len (is 5)")]
+    fn split_at_mut_past_len() {
+        let mut vec = InlineVec::<u32, 8, u8>::new();
+        vec.extend(0..5);
+        let _ = vec.split_at_mut(6);
+    }
+
+    #[test]
+    fn into_split_produces_independent_halves() {
+        use crate::test_utils::*;
+
+        let drop_count = DropCounter::new();
+        let mut backing_region: [core::mem::MaybeUninit<Droppable<u32>>; 8] =
+            unsafe { core::mem::MaybeUninit::uninit().assume_init() };
+        let mut vec = SliceVec::<Droppable<u32>, u8>::from(&mut backing_region[..]);
+        for i in 0..6 {
+            vec.push(drop_count.new_droppable(i));
+        }
+
+        let (mut front, mut back) = vec.into_split(4);
+        assert_eq!(front.capacity(), 4);
+        assert_eq!(back.capacity(), 4);
+        assert!(front.iter().map(|d| d.value).eq(0..4));
+        assert!(back.iter().map(|d| d.value).eq(4..6));
+
+        back.push(drop_count.new_droppable(6));
+        back.push(drop_count.new_droppable(7));
+        assert!(back.is_full());
+        assert!(front.iter().map(|d| d.value).eq(0..4));
+
+        front.clear();
+        assert_eq!(drop_count.dropped(), 4);
+        assert!(back.iter().map(|d| d.value).eq(4..8));
+
+        drop(back);
+        assert_eq!(drop_count.dropped(), 8);
+
+        let mut backing_region = [core::mem::MaybeUninit::<u32>::uninit(); 4];
+        let mut vec = SliceVec::<u32>::from(&mut backing_region[..]);
+        vec.extend([1, 2]);
+        let (front, back) = vec.into_split(0);
+        assert!(front.is_empty() && front.capacity() == 0);
+        assert_eq!(back, [1, 2]);
+    }
+
+    #[test]
+    fn try_push_returns_rejected_value() {
+        use crate::test_utils::*;
+        use core::fmt::Write;
+
+        let drop_count = DropCounter::new();
+        let mut vec = InlineVec::<Droppable<u32>, 1>::new();
+        vec.push(drop_count.new_droppable(1));
+
+        match vec.try_push(drop_count.new_droppable(2)) {
+            Ok(()) => unreachable!(),
+            Err(e) => {
+                let mut msg = crate::InlineString::<32>::new();
+                write!(msg, "{}", e).unwrap();
+                assert_eq!(msg, "insufficient capacity");
+
+                let value = e.into_inner();
+                assert_eq!(value.value, 2);
+                assert_eq!(drop_count.dropped(), 0);
+            }
+        }
+        assert_eq!(drop_count.dropped(), 1);
+    }
+
+    #[test]
+    fn try_insert_returns_rejected_value() {
+        let mut vec = InlineVec::<u32, 2, u8>::new();
+        vec.extend([1, 2]);
+
+        match vec.try_insert(1, 3) {
+            Err(CapacityError { value }) => assert_eq!(value, 3),
+            Ok(()) => unreachable!(),
+        }
+        assert_eq!(vec, [1, 2]);
+    }
+
+    #[test]
+    fn truncate_drop_order() {
+        use crate::test_utils::{DropLog, Logged};
+
+        let log = DropLog::default();
+        let mut vec = InlineVec::<Logged, 8>::new();
+        vec.extend((0..8).map(|i| Logged::new(i, &log)));
+
+        vec.truncate(8);
+        vec.truncate_from_back(8);
+        assert!(log.borrow().is_empty());
+
+        vec.truncate(5);
+        assert_eq!(*log.borrow(), [5, 6, 7]);
+        assert_eq!(vec.len(), 5);
+
+        log.borrow_mut().clear();
+        vec.truncate_from_back(2);
+        assert_eq!(*log.borrow(), [4, 3, 2]);
+        assert_eq!(vec.len(), 2);
+
+        log.borrow_mut().clear();
+        vec.truncate_from_back(0);
+        assert_eq!(*log.borrow(), [1, 0]);
+        assert!(vec.is_empty());
+    }
+
+    #[test]
+    fn from_fn_fills_in_index_order() {
+        let vec = InlineVec::<usize, 4>::from_fn(|i| i * i);
+        assert_eq!(vec, [0, 1, 4, 9]);
+
+        let mut calls = InlineVec::<usize, 5>::new();
+        let vec = InlineVec::<char, 5, u8>::from_fn(|i| {
+            calls.push(i);
+            (b'a' + i as u8) as char
+        });
+        assert_eq!(vec, ['a', 'b', 'c', 'd', 'e']);
+        assert_eq!(calls, [0, 1, 2, 3, 4]);
+
+        let empty = InlineVec::<u32, 0>::from_fn(|_| unreachable!());
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn retain_range_drops_everything_else() {
+        use crate::test_utils::{DropLog, Logged};
+
+        fn check<R: core::ops::RangeBounds<usize>>(range: R, retained: &[u32], dropped: &[u32]) {
+            let log = DropLog::default();
+            let mut vec = InlineVec::<Logged, 8>::new();
+            vec.extend((0..8).map(|i| Logged::new(i, &log)));
+
+            vec.retain_range(range);
+            assert!(vec.iter().map(|l| l.value).eq(retained.iter().copied()));
+            assert_eq!(log.borrow().as_slice(), dropped);
+
+            drop(vec);
+            assert_eq!(log.borrow().len(), 8);
+        }
+
+        check(2..5, &[2, 3, 4], &[0, 1, 5, 6, 7]);
+        check(..3, &[0, 1, 2], &[3, 4, 5, 6, 7]);
+        check(6.., &[6, 7], &[0, 1, 2, 3, 4, 5]);
+        check(3..=20, &[3, 4, 5, 6, 7], &[0, 1, 2]);
+        check(.., &[0, 1, 2, 3, 4, 5, 6, 7], &[]);
+        check(4..4, &[], &[0, 1, 2, 3, 4, 5, 6, 7]);
+        #[allow(clippy::reversed_empty_ranges)]
+        check(5..2, &[], &[0, 1, 2, 3, 4, 5, 6, 7]);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn reserving_never_reallocates() {
+        let mut vec = crate::collections::AllocVec::<u32>::with_capacity(4);
+        vec.extend([1, 2, 3]);
+        let ptr = vec.as_ptr();
+
+        assert!(vec.try_reserve(0).is_ok());
+        assert!(vec.try_reserve(1).is_ok());
+        assert_eq!(vec.try_reserve(2), Err(CapacityError::default()));
+        vec.reserve(1);
+        vec.reserve_exact(1);
+
+        assert_eq!(vec.capacity(), 4);
+        assert_eq!(vec.as_ptr(), ptr);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    #[should_panic(expected = "additional capacity (is 2) should be <= remaining capacity (is 1)")]
+    fn reserving_beyond_capacity() {
+        let mut vec = crate::collections::AllocVec::<u32, u8>::with_capacity(4);
+        vec.extend([1, 2, 3]);
+        vec.reserve_exact(2);
+    }
+
+    #[test]
+    fn splice_with_fewer_equal_and_more_items() {
+        let mut vec = InlineVec::<u32, 8>::new();
+        vec.extend([0, 1, 2, 3, 4, 5]);
+
+        let removed = vec.splice(1..4, [9]);
+        assert_eq!(removed.len(), 3);
+        assert!(removed.eq([1, 2, 3].iter().copied()));
+        assert_eq!(vec, [0, 9, 4, 5]);
+
+        assert!(vec.splice(1..3, [7, 8]).eq([9, 4].iter().copied()));
+        assert_eq!(vec, [0, 7, 8, 5]);
+
+        assert!(vec.splice(2..2, 10..14).eq(None));
+        assert_eq!(vec, [0, 7, 10, 11, 12, 13, 8, 5]);
+
+        assert!(vec
+            .splice(.., 0..8)
+            .eq([0, 7, 10, 11, 12, 13, 8, 5].iter().copied()));
+        assert_eq!(vec, [0, 1, 2, 3, 4, 5, 6, 7]);
+
+        vec.truncate(3);
+        drop(vec.splice(3.., 3..8));
+        assert_eq!(vec, [0, 1, 2, 3, 4, 5, 6, 7]);
+    }
+
+    #[test]
+    fn partially_consumed_splice_drops_the_rest() {
+        use crate::test_utils::*;
+
+        let drop_count = DropCounter::new();
+        let mut vec = InlineVec::<Droppable<'_, u32>, 6>::new();
+        vec.extend((0..4).map(|i| drop_count.new_droppable(i)));
+
+        let mut splice = vec.splice(0..3, (10..14).map(|i| drop_count.new_droppable(i)));
+        assert_eq!(splice.next_back().map(|d| d.value), Some(2));
+        assert_eq!(drop_count.dropped(), 1);
+        drop(splice);
+        assert_eq!(drop_count.dropped(), 3);
+
+        assert!(vec
+            .iter()
+            .map(|d| d.value)
+            .eq([10, 11, 12, 13, 3].iter().copied()));
+    }
+
+    #[test]
+    #[should_panic(expected = "spliced vector length should be <= capacity (is 4)")]
+    fn splice_beyond_capacity() {
+        let mut vec = InlineVec::<u32, 4>::new();
+        vec.extend([1, 2, 3]);
+        vec.splice(1..2, [4, 5, 6]);
+    }
+
+    #[test]
+    fn copy_within_matches_reference() {
+        let reference = |src: Range<usize>, dest: usize| {
+            let mut a = [0u8, 1, 2, 3, 4, 5, 6, 7, 8, 9];
+            let mut tmp = [0u8; 10];
+            tmp[..src.len()].copy_from_slice(&a[src.clone()]);
+            a[dest..dest + src.len()].copy_from_slice(&tmp[..src.len()]);
+            a
+        };
+
+        for (src, dest) in [
+            (0..3, 5),
+            (5..9, 0),
+            (2..6, 4),
+            (4..8, 2),
+            (0..10, 0),
+            (3..3, 10),
+        ] {
+            let mut vec = InlineVec::<u8, 10, u16>::new();
+            vec.extend(0..10);
+            vec.copy_within(src.start as u16..src.end as u16, dest as u16);
+            assert_eq!(vec, reference(src, dest));
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "dest (is 8) should be <= len - src.len() (is 7)")]
+    fn copy_within_out_of_bounds() {
+        let mut vec = InlineVec::<u8, 10>::new();
+        vec.extend(0..10);
+        vec.copy_within(..=2, 8);
+    }
+
+    #[test]
+    fn clear_to_spare_drops_and_exposes_capacity() {
+        use crate::test_utils::*;
+
+        let counter = DropCounter::new();
+        let mut vec = InlineVec::<Droppable<u32>, 6, u8>::new();
+        for i in 0..4 {
+            vec.push(counter.new_droppable(i));
+        }
+
+        let spare = vec.clear_to_spare();
+        assert_eq!(counter.dropped(), 4);
+        assert_eq!(spare.len(), 6);
+        for (i, slot) in spare.iter_mut().take(5).enumerate() {
+            slot.write(counter.new_droppable(10 + i as u32));
+        }
+
+        unsafe { vec.set_len(5) };
+        assert_eq!(vec.len(), 5);
+        assert_eq!(vec[4].value, 14);
+        drop(vec);
+        assert_eq!(counter.dropped(), 9);
+    }
+
+    #[test]
+    fn push_within_capacity_returns_rejected_value() {
+        use crate::test_utils::*;
+
+        let counter = DropCounter::new();
+        let mut vec = InlineVec::<Droppable<u32>, 1>::new();
+        assert!(vec.push_within_capacity(counter.new_droppable(1)).is_ok());
+
+        let rejected = vec
+            .push_within_capacity(counter.new_droppable(2))
+            .unwrap_err();
+        assert_eq!(rejected.value, 2);
+        assert_eq!(counter.dropped(), 0);
+        assert_eq!(vec[0].value, 1);
+    }
+
+    #[test]
+    fn dedup_drain_yields_removed_duplicates() {
+        use crate::test_utils::*;
+
+        let mut vec = InlineVec::<u32, 12, u8>::new();
+        vec.extend([1, 1, 2, 3, 3, 3, 1, 4, 4]);
+        let removed: InlineVec<u32, 12> = vec.dedup_drain().collect();
+        assert_eq!(vec, [1, 2, 3, 1, 4]);
+        assert_eq!(removed, [1, 3, 3, 4]);
+
+        vec.clear();
+        assert_eq!(vec.dedup_drain().next(), None);
+        vec.push(7);
+        assert_eq!(vec.dedup_drain().next(), None);
+        assert_eq!(vec, [7]);
+
+        let counter = DropCounter::new();
+        let mut vec = InlineVec::<Droppable<u32>, 8>::new();
+        for x in [5, 5, 5, 6, 6, 7] {
+            vec.push(counter.new_droppable(x));
+        }
+
+        let mut iter = vec.dedup_drain();
+        assert_eq!(iter.next().map(|d| d.value), Some(5));
+        assert_eq!(counter.dropped(), 1);
+        drop(iter);
+        assert_eq!(counter.dropped(), 3);
+
+        let values: InlineVec<u32, 8> = vec.iter().map(|d| d.value).collect();
+        assert_eq!(values, [5, 6, 7]);
+        drop(vec);
+        assert_eq!(counter.dropped(), 6);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn dedup_drain_keeps_unexamined_elements_on_panic() {
+        use crate::test_utils::*;
+
+        #[derive(Debug)]
+        struct Fragile(u32);
+        impl PartialEq for Fragile {
+            fn eq(&self, other: &Self) -> bool {
+                assert!(self.0 != 3 && other.0 != 3, "comparison failed");
+                self.0 == other.0
+            }
+        }
+
+        let counter = DropCounter::new();
+        let mut vec = InlineVec::<Droppable<Fragile>, 8>::new();
+        for x in [1, 1, 2, 2, 3, 3, 4] {
+            vec.push(counter.new_droppable(Fragile(x)));
+        }
+
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            vec.dedup_drain().for_each(drop);
+        }));
+        assert!(result.is_err());
+        assert_eq!(counter.dropped(), 2);
+
+        let values: InlineVec<u32, 8> = vec.iter().map(|d| d.value.0).collect();
+        assert_eq!(values, [1, 2, 3, 3, 4]);
+        drop(vec);
+        assert_eq!(counter.dropped(), 7);
+    }
+
+    #[test]
+    fn retain_while_decisions_and_drops() {
+        use crate::test_utils::*;
+
+        let counter = DropCounter::new();
+        let mut vec = InlineVec::<Droppable<u32>, 8>::new();
+        for x in [1, 2, 3, 4, 99, 6, 7, 8] {
+            vec.push(counter.new_droppable(x));
+        }
+
+        let mut visited = 0;
+        vec.retain_while(|d| {
+            visited += 1;
+            match d.value {
+                99 => {
+                    d.value = 5;
+                    RetainDecision::StopKeepingRest
+                }
+                x if x % 2 == 0 => RetainDecision::Remove,
+                _ => RetainDecision::Keep,
+            }
+        });
+
+        assert_eq!(visited, 5);
+        assert_eq!(counter.dropped(), 2);
+        let values: InlineVec<u32, 8> = vec.iter().map(|d| d.value).collect();
+        assert_eq!(values, [1, 3, 5, 6, 7, 8]);
+
+        vec.retain_while(|_| RetainDecision::Remove);
+        assert!(vec.is_empty());
+        assert_eq!(counter.dropped(), 8);
+
+        let mut vec = InlineVec::<u32, 4>::new();
+        vec.extend([1, 2, 3]);
+        vec.retain_while(|_| RetainDecision::Keep);
+        assert_eq!(vec, [1, 2, 3]);
+    }
+
+    #[test]
+    fn chunks_of_three() {
+        let mut vec = InlineVec::<u32, 10, u8>::new();
+        vec.extend(0..10);
+
+        let (chunks, rest) = vec.as_chunks::<3>();
+        assert_eq!(chunks, &[[0, 1, 2], [3, 4, 5], [6, 7, 8]]);
+        assert_eq!(rest, &[9]);
+
+        let (chunks, rest) = vec.as_chunks_mut::<3>();
+        for chunk in chunks.iter_mut() {
+            chunk[0] += 100;
+        }
+        rest[0] += 100;
+        assert_eq!(vec, [100, 1, 2, 103, 4, 5, 106, 7, 8, 109]);
+
+        let (chunks, rest) = vec.as_chunks::<11>();
+        assert!(chunks.is_empty());
+        assert_eq!(rest.len(), 10);
+
+        vec.clear();
+        let (chunks, rest) = vec.as_chunks::<3>();
+        assert!(chunks.is_empty() && rest.is_empty());
+    }
+
+    #[test]
+    fn try_concat_is_all_or_nothing() {
+        let mut vec = crate::collections::InlineVec::<u8, 5>::new();
+        vec.push(0);
+
+        assert!(vec.try_concat(&[]).is_ok());
+        assert_eq!(vec, [0]);
+
+        assert!(vec.try_concat(&[&[1, 2], &[], &[3, 4, 5]]).is_err());
+        assert_eq!(vec, [0]);
+
+        assert!(vec.try_concat(&[&[1], &[2, 3], &[4]]).is_ok());
+        assert_eq!(vec, [0, 1, 2, 3, 4]);
+
+        assert!(vec.try_concat(&[]).is_ok());
+        assert!(vec.try_concat(&[&[]]).is_ok());
+        assert!(vec.try_concat(&[&[5]]).is_err());
+        assert_eq!(vec, [0, 1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn merge_into_is_stable() {
+        let a = [(1, 'a'), (2, 'a'), (2, 'b'), (4, 'a')];
+        let b = [(0, 'c'), (2, 'c'), (4, 'c'), (5, 'c')];
+        let mut dst = crate::collections::InlineVec::<(u8, char), 8>::new();
+        merge_into_by_key(&a, &b, &mut dst, |&(k, _)| k);
+        assert_eq!(
+            dst,
+            [
+                (0, 'c'),
+                (1, 'a'),
+                (2, 'a'),
+                (2, 'b'),
+                (2, 'c'),
+                (4, 'a'),
+                (4, 'c'),
+                (5, 'c')
+            ]
+        );
+    }
+
+    #[test]
+    fn merge_into_with_empty_inputs() {
+        let mut dst = crate::collections::InlineVec::<u8, 4>::new();
+        merge_into(&[], &[], &mut dst);
+        assert!(dst.is_empty());
+
+        merge_into(&[1, 2], &[], &mut dst);
+        merge_into(&[], &[0, 3], &mut dst);
+        assert_eq!(dst, [1, 2, 0, 3]);
+
+        merge_into(&[], &[], &mut dst);
+        assert_eq!(dst.len(), 4);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "destination spare capacity (is 3) should be >= combined length of inputs (is 4)"
+    )]
+    fn merge_into_without_capacity() {
+        let mut dst = crate::collections::InlineVec::<u8, 4>::new();
+        dst.push(9);
+        merge_into(&[1, 2], &[3, 4], &mut dst);
+    }
+
+    #[test]
+    #[should_panic(expected = "combined length of inputs (overflows usize)")]
+    fn merge_into_with_overflowing_length() {
+        let a = [(); usize::MAX];
+        let mut dst = crate::collections::InlineVec::<(), 4>::new();
+        merge_into(&a, &[()], &mut dst);
+    }
+
     use crate::collections::{ArenaVec, InlineVec, SliceVec};
 
     #[test]
@@ -1880,6 +4108,284 @@ mod tests {
         assert_eq!(drop_count.dropped(), 16);
     }
 
+    #[test]
+    fn sort_unstable_randomized_and_adversarial_inputs() {
+        use rand::{rngs::SmallRng, RngCore, SeedableRng};
+
+        fn check(input: &[u32]) {
+            let mut vec = InlineVec::<u32, 64>::new();
+            vec.extend_from_slice(input);
+            vec.sort_unstable();
+
+            // reference: simple insertion sort
+            let mut expected = InlineVec::<u32, 64>::new();
+            for &x in input {
+                let idx = expected
+                    .iter()
+                    .position(|&y| y > x)
+                    .unwrap_or(expected.len());
+                expected.insert(idx, x);
+            }
+
+            assert_eq!(vec, expected);
+        }
+
+        let mut rng = SmallRng::from_seed(crate::test_utils::RNG_SEED);
+        for _ in 0..100 {
+            let len = rng.next_u32() as usize % 64;
+            let mut input = [0u32; 64];
+            input[..len]
+                .iter_mut()
+                .for_each(|x| *x = rng.next_u32() % 16);
+            check(&input[..len]);
+        }
+
+        let ascending: InlineVec<u32, 64> = (0..64).collect();
+        let descending: InlineVec<u32, 64> = (0..64).rev().collect();
+        let sawtooth: InlineVec<u32, 64> = (0..64).map(|x| x % 8).collect();
+        let organ_pipe: InlineVec<u32, 64> = (0..32).chain((0..32).rev()).collect();
+
+        check(&ascending);
+        check(&descending);
+        check(&sawtooth);
+        check(&organ_pipe);
+        check(&[7; 64]);
+        check(&[]);
+    }
+
+    #[test]
+    fn fill_drops_overwritten_elements() {
+        use crate::test_utils::*;
+
+        let drop_count = DropCounter::new();
+        let mut vec = InlineVec::<Droppable<usize>, 8>::new();
+        for i in 0..6 {
+            vec.push(drop_count.new_droppable(i));
+        }
+
+        let mut next = 10;
+        vec.fill_with(|| {
+            next += 1;
+            drop_count.new_droppable(next)
+        });
+
+        assert_eq!(vec.len(), 6);
+        assert_eq!(drop_count.dropped(), 6);
+        assert!(vec.iter().map(|d| d.value).eq(11..=16));
+
+        drop(vec);
+        assert_eq!(drop_count.dropped(), 12);
+    }
+
+    #[test]
+    fn swap_boundary_and_interior_indices() {
+        let mut vec = InlineVec::<u32, 8, u8>::new();
+        vec.extend(0..5);
+
+        vec.swap(0, 4);
+        assert_eq!(vec, [4, 1, 2, 3, 0]);
+        vec.swap(1, 3);
+        assert_eq!(vec, [4, 3, 2, 1, 0]);
+        vec.swap(2, 2);
+        assert_eq!(vec, [4, 3, 2, 1, 0]);
+    }
+
+    #[test]
+    #[should_panic(expected = "swap index (is 5) should be < len (is 5)")]
+    fn swap_out_of_bounds() {
+        let mut vec = InlineVec::<u32, 8, u8>::new();
+        vec.extend(0..5);
+        vec.swap(0, 5);
+    }
+
+    #[test]
+    fn pop_if_removes_only_when_predicate_holds() {
+        let mut vec = InlineVec::<u32, 4, u8>::new();
+        let mut calls = 0;
+        assert_eq!(
+            vec.pop_if(|_| {
+                calls += 1;
+                true
+            }),
+            None
+        );
+        assert_eq!(calls, 0);
+
+        vec.extend([1, 2, 3]);
+        assert_eq!(vec.pop_if(|x| *x > 5), None);
+        assert_eq!(vec.len(), 3);
+
+        assert_eq!(
+            vec.pop_if(|x| {
+                *x += 10;
+                false
+            }),
+            None
+        );
+        assert_eq!(vec, [1, 2, 13]);
+
+        assert_eq!(vec.pop_if(|x| *x > 5), Some(13));
+        assert_eq!(vec.len(), 2);
+        assert_eq!(vec.pop_if(|_| true), Some(2));
+        assert_eq!(vec.pop_if(|_| true), Some(1));
+        assert_eq!(vec.pop_if(|_| true), None);
+        assert!(vec.is_empty());
+    }
+
+    #[test]
+    fn raw_pointers_match_slice() {
+        let mut vec = InlineVec::<u64, 8, u8>::new();
+        assert_eq!(vec.as_ptr(), vec.as_slice().as_ptr());
+
+        vec.extend([3, 1, 4, 1, 5]);
+        let ptr = vec.as_ptr();
+        assert_eq!(ptr, vec.as_slice().as_ptr());
+        let read = unsafe { core::slice::from_raw_parts(ptr, vec.len()) };
+        assert_eq!(read, vec.as_slice());
+
+        let ptr = vec.as_mut_ptr();
+        unsafe {
+            *ptr.add(1) = 9;
+            ptr.add(5).write(2);
+            vec.set_len(6);
+        }
+        assert_eq!(vec, [3, 9, 4, 1, 5, 2]);
+    }
+
+    #[test]
+    fn lexicographic_ordering() {
+        let mut backing_region = [core::mem::MaybeUninit::<u32>::uninit(); 8];
+        let mut slice_vec = SliceVec::<u32>::from(&mut backing_region[..]);
+        let mut inline_vec = InlineVec::<u32, 4, u8>::new();
+
+        // empty vectors are equal
+        assert_eq!(slice_vec.partial_cmp(&inline_vec), Some(Ordering::Equal));
+
+        // a proper prefix compares less, regardless of capacity
+        slice_vec.extend([1, 2, 3]);
+        inline_vec.extend([1, 2]);
+        assert!(inline_vec < slice_vec);
+        assert!(slice_vec > inline_vec);
+
+        // equal contents compare equal
+        inline_vec.push(3);
+        assert_eq!(inline_vec.partial_cmp(&slice_vec), Some(Ordering::Equal));
+        assert!(inline_vec <= slice_vec && inline_vec >= slice_vec);
+
+        // the first differing element decides, not the length
+        inline_vec[2] = 4;
+        slice_vec.extend([5, 6, 7]);
+        assert!(inline_vec > slice_vec);
+
+        // elements beyond the length are never compared
+        let mut a = InlineVec::<u32, 4>::new();
+        let mut b = InlineVec::<u32, 4>::new();
+        a.extend([9, 9, 9, 9]);
+        a.truncate(1);
+        b.push(9);
+        assert_eq!(a.cmp(&b), Ordering::Equal);
+
+        let mut sorted = InlineVec::<InlineVec<u32, 4>, 4>::new();
+        sorted.push(b.clone());
+        sorted.push(InlineVec::new());
+        sorted.push(a.clone());
+        a.push(0);
+        sorted.push(a);
+        sorted.sort_unstable();
+        assert!(sorted.iter().map(InlineVec::len).eq([0, 1, 1, 2]));
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn lexicographic_ordering_across_storage_types() {
+        use crate::collections::AllocVec;
+
+        let mut alloc_vec = AllocVec::<u32, u16>::with_capacity(4);
+        let mut inline_vec = InlineVec::<u32, 4, u8>::new();
+        alloc_vec.extend([2, 7]);
+        inline_vec.extend([2, 7, 1]);
+
+        assert!(alloc_vec < inline_vec);
+        assert!(inline_vec > alloc_vec);
+        alloc_vec.push(1);
+        assert_eq!(alloc_vec.partial_cmp(&inline_vec), Some(Ordering::Equal));
+        assert_eq!(alloc_vec, inline_vec);
+    }
+
+    #[test]
+    fn checked_extend_under_exact_and_over_capacity() {
+        let mut vec = InlineVec::<u32, 8, u8>::new();
+        assert!(vec.can_hold(8));
+        assert!(!vec.can_hold(9));
+        assert!(!vec.can_hold(usize::MAX));
+
+        assert!(vec.checked_extend(0..5).is_ok());
+        assert_eq!(vec, &[0, 1, 2, 3, 4][..]);
+        assert!(vec.can_hold(3));
+        assert!(!vec.can_hold(4));
+
+        assert!(vec.checked_extend(5..8).is_ok());
+        assert!(vec.is_full());
+        assert!(vec.can_hold(0));
+        assert!(!vec.can_hold(1));
+        assert!(!vec.can_hold(usize::MAX));
+
+        vec.truncate(6);
+        let mut rest = vec.checked_extend(6..10).unwrap_err();
+        assert_eq!(vec, &[0, 1, 2, 3, 4, 5, 6, 7][..]);
+        assert_eq!(rest.next(), Some(8));
+        assert_eq!(rest.next(), Some(9));
+        assert_eq!(rest.next(), None);
+
+        let mut rest = vec.checked_extend(core::iter::once(10)).unwrap_err();
+        assert_eq!(rest.next(), Some(10));
+        assert!(vec.checked_extend(core::iter::empty()).is_ok());
+    }
+
+    #[test]
+    fn new_const_in_static_and_const_items() {
+        static EMPTY_STATIC: InlineVec<u32, 16, u8> = InlineVec::<u32, 16, u8>::new_const();
+        const EMPTY_CONST: InlineVec<u32, 16, u16> = InlineVec::<u32, 16, u16>::new_const();
+
+        assert_eq!(EMPTY_STATIC.len(), 0);
+        assert_eq!(EMPTY_STATIC.capacity(), 16);
+
+        let mut vec = EMPTY_CONST;
+        assert!(vec.is_empty());
+        assert_eq!(vec.capacity(), 16);
+        vec.extend(0..16);
+        assert!(vec.is_full());
+        assert!(EMPTY_CONST.is_empty());
+    }
+
+    #[test]
+    fn index_by_range() {
+        let mut vec = InlineVec::<u32, 8, u8>::new();
+        vec.extend(0..5);
+
+        assert_eq!(vec[1..3], [1, 2]);
+        assert_eq!(vec[2..], [2, 3, 4]);
+        assert_eq!(vec[..2], [0, 1]);
+        assert_eq!(vec[..], [0, 1, 2, 3, 4]);
+        assert_eq!(vec[1..=3], [1, 2, 3]);
+        assert_eq!(vec[..=1], [0, 1]);
+        assert!(vec[3..3].is_empty());
+        assert!(vec[5..].is_empty());
+
+        vec[1..3].copy_from_slice(&[7, 8]);
+        vec[..1].fill(9);
+        vec[4..].fill(6);
+        assert_eq!(vec[..], [9, 7, 8, 3, 6]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn index_by_range_does_not_expose_spare_capacity() {
+        let mut vec = InlineVec::<u32, 8, u8>::new();
+        vec.extend(0..5);
+        let _ = &vec[3..6];
+    }
+
     #[test]
     #[should_panic]
     fn leaking_drain() {
@@ -1910,4 +4416,45 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn drain_in_reverse() {
+        let mut vec = InlineVec::<u32, 8>::new();
+        vec.extend(0..8);
+
+        let mut reversed = InlineVec::<u32, 8>::new();
+        reversed.extend(vec.drain(2..6).rev());
+        assert_eq!(reversed, [5, 4, 3, 2]);
+        assert_eq!(vec, [0, 1, 6, 7]);
+
+        reversed.clear();
+        reversed.extend(vec.drain(..).rev());
+        assert_eq!(reversed, [7, 6, 1, 0]);
+        assert!(vec.is_empty());
+
+        vec.extend(0..8);
+        let mut drain = vec.drain(1..7);
+        assert_eq!(drain.next_back(), Some(6));
+        assert_eq!(drain.next(), Some(1));
+        assert_eq!(drain.next_back(), Some(5));
+        assert_eq!(drain.len(), 3);
+        drop(drain);
+        assert_eq!(vec, [0, 7]);
+    }
+
+    #[test]
+    fn partially_consumed_reverse_drain_drops_the_rest() {
+        use crate::test_utils::*;
+
+        let drop_count = DropCounter::new();
+        let mut vec = InlineVec::<Droppable<()>, 8>::new();
+        for _ in 0..8 {
+            vec.push(drop_count.new_droppable(()));
+        }
+
+        let taken = vec.drain(..).rev().take(3).count();
+        assert_eq!(taken, 3);
+        assert_eq!(drop_count.dropped(), 8);
+        assert!(vec.is_empty());
+    }
 }
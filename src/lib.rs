@@ -111,6 +111,9 @@
 //!   compiler.
 //! - `profile`: Adds memory profiling in arena allocators. See the
 //!   [module-level documentation](arena#memory-profiling) for details.
+//! - `std`: Implies `alloc`, and adds implementations of the
+//!   [`std::error::Error`](https://doc.rust-lang.org/std/error/trait.Error.html)
+//!   trait for error types.
 //!
 //! None of these features are enabled by default.
 
@@ -118,6 +121,9 @@
 #[doc(hidden)]
 pub extern crate alloc;
 
+#[cfg(feature = "std")]
+extern crate std;
+
 pub mod arena;
 pub mod collections;
 pub mod storage;
@@ -131,6 +137,8 @@ pub mod object;
 #[cfg_attr(docs_rs, doc(cfg(feature = "unstable")))]
 pub use crate::object::InlineObject;
 
+pub use crate::collections::binary_heap::IteratorExt;
+
 use crate::storage::{ArenaStorage, ArrayLayout, InlineStorage, SliceStorage};
 use crate::string::String;
 
@@ -195,18 +203,68 @@ pub type InlineString<const C: usize, I = usize> = String<InlineStorage<u8, C>,
 /// the value to be inserted is returned back to the caller when the operation fails;
 /// in some cases, this is unnecessary (e.g. when ownership is not transferred, as with
 /// [`Vec::try_extend_from_slice`](crate::collections::vec::Vec::try_extend_from_slice))
-/// or would result in unwieldy type signatures. Such methods use this unit error type
-/// instead.
-#[derive(Copy, Clone, Debug, Default)]
-pub struct CapacityError;
+/// or would result in unwieldy type signatures. Such methods use the default
+/// `CapacityError<()>` instead.
+///
+/// Methods inserting the items of an iterator, such as
+/// [`Vec::checked_extend`](crate::collections::vec::Vec::checked_extend),
+/// return the unconsumed remainder of the iterator instead. The only method
+/// returning a bare value is [`Vec::push_within_capacity`](crate::collections::vec::Vec::push_within_capacity),
+/// matching the signature of its counterpart in the standard library.
+///
+/// # Examples
+/// ```
+/// use coca::{collections::InlineVec, CapacityError};
+///
+/// let mut vec = InlineVec::<char, 1>::new();
+/// vec.push('a');
+///
+/// let err = vec.try_push('b').unwrap_err();
+/// assert_eq!(err.to_string(), "insufficient capacity");
+/// assert_eq!(err, CapacityError { value: 'b' });
+/// assert_eq!(err.into_inner(), 'b');
+///
+/// match vec.try_push('c') {
+///     Ok(()) => unreachable!(),
+///     Err(CapacityError { value }) => assert_eq!(value, 'c'),
+/// }
+/// ```
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct CapacityError<T = ()> {
+    /// The value that could not be inserted.
+    pub value: T,
+}
 
 impl CapacityError {
     #[inline(always)]
     pub(crate) fn new<T>() -> core::result::Result<T, CapacityError> {
-        Err(Self)
+        Err(Self { value: () })
+    }
+}
+
+impl<T> CapacityError<T> {
+    #[inline(always)]
+    pub(crate) fn with_value(value: T) -> Self {
+        CapacityError { value }
+    }
+
+    /// Consumes the error, returning the value that could not be inserted.
+    #[inline]
+    pub fn into_inner(self) -> T {
+        self.value
     }
 }
 
+impl<T> core::fmt::Display for CapacityError<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("insufficient capacity")
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg_attr(docs_rs, doc(cfg(feature = "std")))]
+impl<T: core::fmt::Debug> std::error::Error for CapacityError<T> {}
+
 /// A specialized [`Result`](core::result::Result) type for operations on data structures with constant capacity.
 ///
 /// This type is broadly used across `coca` for most operations which grow a data structure.
@@ -216,7 +274,9 @@ pub type Result<T> = core::result::Result<T, CapacityError>;
 
 #[cfg(test)]
 mod test_utils {
-    use core::cell::Cell;
+    use core::cell::{Cell, RefCell};
+
+    use crate::collections::InlineVec;
 
     #[cfg(target_pointer_width = "64")]
     pub(crate) const RNG_SEED: [u8; 32] = [
@@ -266,4 +326,94 @@ mod test_utils {
             self.counter.drop_count.set(new_drop_count);
         }
     }
+
+    // Droppables compare by value only, so they can be stored in heaps.
+    impl<T: PartialEq> PartialEq for Droppable<'_, T> {
+        fn eq(&self, other: &Self) -> bool {
+            self.value == other.value
+        }
+    }
+
+    impl<T: Eq> Eq for Droppable<'_, T> {}
+
+    impl<T: PartialOrd> PartialOrd for Droppable<'_, T> {
+        fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+            self.value.partial_cmp(&other.value)
+        }
+    }
+
+    impl<T: Ord> Ord for Droppable<'_, T> {
+        fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+            self.value.cmp(&other.value)
+        }
+    }
+
+    pub(crate) type DropLog = RefCell<InlineVec<u32, 16>>;
+
+    // Appends its value to a shared log when dropped, for checking drop order.
+    #[derive(Debug)]
+    pub(crate) struct Logged<'a> {
+        pub value: u32,
+        log: &'a DropLog,
+    }
+
+    impl<'a> Logged<'a> {
+        pub(crate) fn new(value: u32, log: &'a DropLog) -> Self {
+            Logged { value, log }
+        }
+    }
+
+    impl Drop for Logged<'_> {
+        fn drop(&mut self) {
+            self.log.borrow_mut().push(self.value);
+        }
+    }
+
+    // Loggeds compare by value only, so they can be stored in heaps.
+    impl PartialEq for Logged<'_> {
+        fn eq(&self, other: &Self) -> bool {
+            self.value == other.value
+        }
+    }
+
+    impl Eq for Logged<'_> {}
+
+    impl PartialOrd for Logged<'_> {
+        fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+            Some(self.cmp(other))
+        }
+    }
+
+    impl Ord for Logged<'_> {
+        fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+            self.value.cmp(&other.value)
+        }
+    }
+
+    // Compares by key only, so the tag tells apart items that compare equal.
+    #[derive(Debug)]
+    pub(crate) struct Tagged {
+        pub key: u32,
+        pub tag: u32,
+    }
+
+    impl PartialEq for Tagged {
+        fn eq(&self, other: &Self) -> bool {
+            self.key == other.key
+        }
+    }
+
+    impl Eq for Tagged {}
+
+    impl PartialOrd for Tagged {
+        fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+            Some(self.cmp(other))
+        }
+    }
+
+    impl Ord for Tagged {
+        fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+            self.key.cmp(&other.key)
+        }
+    }
 }
@@ -24,15 +24,43 @@ pub unsafe trait Capacity: Copy + Debug + Eq + Hash + Ord {
     fn from_usize(i: usize) -> Self;
     /// Convert `self` into `usize`.
     fn as_usize(&self) -> usize;
+
+    /// Returns the largest capacity of a storage block that can be fully
+    /// indexed by this type, i.e. [`MAX_REPRESENTABLE`](Capacity::MAX_REPRESENTABLE).
+    ///
+    /// # Examples
+    /// ```
+    /// use coca::storage::Capacity;
+    /// assert_eq!(u8::max_capacity(), 255);
+    /// assert_eq!(u16::max_capacity(), 65535);
+    /// ```
+    #[inline]
+    fn max_capacity() -> usize {
+        Self::MAX_REPRESENTABLE
+    }
+}
+
+#[cold]
+#[inline(never)]
+#[track_caller]
+pub(crate) fn buffer_too_large_for_index_type<I: Capacity>(capacity: usize) {
+    panic!(
+        "capacity {} exceeds {} index max of {}",
+        capacity,
+        core::any::type_name::<I>(),
+        I::max_capacity()
+    );
 }
 
+#[cfg(feature = "alloc")]
 #[cold]
 #[inline(never)]
 #[track_caller]
-pub(crate) fn buffer_too_large_for_index_type<I: Capacity>() {
+pub(crate) fn capacity_too_large_for_usize<I: Capacity>(capacity: I) {
     panic!(
-        "provided storage block cannot be fully indexed by type {}",
-        core::any::type_name::<I>()
+        "capacity {:?} exceeds usize max of {}",
+        capacity,
+        usize::MAX
     );
 }
 
@@ -211,6 +239,24 @@ macro_rules! index_type {
     () => {}
 }
 
+/// Types that can be safely reinterpreted as bytes, and vice versa.
+///
+/// This is used by [`BinaryHeap::as_bytes`](crate::collections::binary_heap::BinaryHeap::as_bytes)
+/// and [`BinaryHeap::from_bytes_heapify`](crate::collections::binary_heap::BinaryHeap::from_bytes_heapify).
+///
+/// # Safety
+/// Implementors must not contain any padding bytes, uninhabited fields or
+/// pointers, and every bit pattern of the right size must be a valid value.
+/// It is implemented for all primitive integer types and arrays thereof.
+pub unsafe trait Pod: Copy + 'static {}
+
+macro_rules! impl_pod {
+    ($($t:ty),*) => { $( unsafe impl Pod for $t {} )* };
+}
+
+impl_pod!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+unsafe impl<T: Pod, const N: usize> Pod for [T; N] {}
+
 /// Types that specify a data structure's storage layout requirements.
 pub trait LayoutSpec {
     /// Constructs a [`Layout`] for a memory block capable of holding the
@@ -369,7 +415,7 @@ impl<R: LayoutSpec> AllocStorage<R> {
     pub fn with_capacity(capacity: usize) -> Self {
         let layout =
             R::layout_with_capacity(capacity).expect("layout error in AllocStorage::with_capacity");
-        let ptr = unsafe { alloc::alloc::alloc(layout) };
+        let ptr = unsafe { alloc_or_dangling(layout) };
         let ptr = NonNull::new(ptr).expect("allocation failure in AllocStorage::with_capacity");
         AllocStorage {
             ptr,
@@ -377,6 +423,103 @@ impl<R: LayoutSpec> AllocStorage<R> {
             spec: PhantomData,
         }
     }
+
+    /// Allocates a new storage block with the specified capacity with the
+    /// global allocator, returning an error if `capacity` is large enough to
+    /// cause a layout error, or if allocation fails.
+    ///
+    /// # Examples
+    /// ```
+    /// use coca::storage::{AllocStorage, ArrayLayout, TryReserveError};
+    ///
+    /// let storage = AllocStorage::<ArrayLayout<u64>>::try_with_capacity(16);
+    /// assert!(storage.is_ok());
+    ///
+    /// let storage = AllocStorage::<ArrayLayout<u64>>::try_with_capacity(usize::MAX);
+    /// assert!(matches!(storage, Err(TryReserveError::CapacityOverflow)));
+    /// ```
+    pub fn try_with_capacity(capacity: usize) -> Result<Self, TryReserveError> {
+        let layout =
+            R::layout_with_capacity(capacity).map_err(|_| TryReserveError::CapacityOverflow)?;
+        let ptr = unsafe { alloc_or_dangling(layout) };
+        let ptr = NonNull::new(ptr).ok_or(TryReserveError::AllocError { layout })?;
+        Ok(AllocStorage {
+            ptr,
+            cap: capacity,
+            spec: PhantomData,
+        })
+    }
+
+    /// Shrinks the storage block to the specified capacity, preserving the
+    /// contents of the first `capacity` items.
+    ///
+    /// # Panics
+    /// Panics if `capacity` is larger than the current capacity, or if
+    /// reallocation fails.
+    pub(crate) fn shrink_to(&mut self, capacity: usize) {
+        assert!(capacity <= self.cap);
+
+        let old_layout = R::layout_with_capacity(self.cap).expect("invalid AllocStorage");
+        let new_layout =
+            R::layout_with_capacity(capacity).expect("layout error in AllocStorage::shrink_to");
+        debug_assert_eq!(old_layout.align(), new_layout.align());
+
+        if new_layout.size() < old_layout.size() {
+            let ptr = unsafe {
+                if new_layout.size() == 0 {
+                    dealloc_unless_dangling(self.ptr.as_ptr(), old_layout);
+                    alloc_or_dangling(new_layout)
+                } else {
+                    alloc::alloc::realloc(self.ptr.as_ptr(), old_layout, new_layout.size())
+                }
+            };
+
+            self.ptr = NonNull::new(ptr).expect("allocation failure in AllocStorage::shrink_to");
+        }
+
+        self.cap = capacity;
+    }
+
+    /// Consumes the storage block without deallocating it, returning a
+    /// pointer to its beginning.
+    pub(crate) fn into_raw(self) -> *mut u8 {
+        let ptr = self.ptr.as_ptr();
+        core::mem::forget(self);
+        ptr
+    }
+}
+
+// The global allocator must not be called with zero-sized layouts, so
+// such storage blocks are represented by a well-aligned dangling pointer.
+#[cfg(feature = "alloc")]
+unsafe fn alloc_or_dangling(layout: Layout) -> *mut u8 {
+    if layout.size() == 0 {
+        layout.align() as *mut u8
+    } else {
+        alloc::alloc::alloc(layout)
+    }
+}
+
+#[cfg(feature = "alloc")]
+unsafe fn dealloc_unless_dangling(ptr: *mut u8, layout: Layout) {
+    if layout.size() != 0 {
+        alloc::alloc::dealloc(ptr, layout);
+    }
+}
+
+/// The error type for fallible allocation of an [`AllocStorage`] block.
+#[cfg(feature = "alloc")]
+#[cfg_attr(docs_rs, doc(cfg(feature = "alloc")))]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TryReserveError {
+    /// The requested capacity exceeds the maximum size of a memory block,
+    /// or cannot be represented by the index type.
+    CapacityOverflow,
+    /// The global allocator returned an error.
+    AllocError {
+        /// The layout of the allocation request that failed.
+        layout: Layout,
+    },
 }
 
 #[cfg(feature = "alloc")]
@@ -384,7 +527,7 @@ impl<R: LayoutSpec> AllocStorage<R> {
 impl<R: LayoutSpec> Drop for AllocStorage<R> {
     fn drop(&mut self) {
         let layout = R::layout_with_capacity(self.cap).expect("dropped an invalid AllocStorage");
-        unsafe { alloc::alloc::dealloc(self.ptr.as_ptr(), layout) };
+        unsafe { dealloc_unless_dangling(self.ptr.as_ptr(), layout) };
     }
 }
 
@@ -417,6 +560,30 @@ unsafe impl<R: LayoutSpec, S: Storage<R>> Storage<R> for alloc::boxed::Box<S> {
 }
 
 /// Shorthand for `[MaybeUninit<T>; C]` for use with generic data structures.
+///
+/// [`Storage`] is implemented for arrays of any size on the stable channel,
+/// so inline collections such as [`InlineVec`](crate::collections::InlineVec)
+/// and [`InlineHeap`](crate::collections::InlineHeap) do not require the
+/// `unstable` feature.
+///
+/// # Examples
+/// ```
+/// use coca::collections::{InlineHeap, InlineVec};
+///
+/// macro_rules! check_sizes {
+///     ($($n:literal),*) => {$(
+///         let mut vec = InlineVec::<u32, $n>::new();
+///         vec.extend(0..$n);
+///         assert!(vec.is_full());
+///
+///         let mut heap = InlineHeap::<u32, $n>::new();
+///         heap.extend(vec);
+///         assert_eq!(heap.peek(), Some(&($n - 1)));
+///     )*};
+/// }
+///
+/// check_sizes!(1, 2, 4, 8, 16, 32, 64, 100);
+/// ```
 pub type InlineStorage<T, const C: usize> = [MaybeUninit<T>; C];
 
 unsafe impl<T, const C: usize> Storage<ArrayLayout<T>> for InlineStorage<T, C> {
@@ -430,3 +597,14 @@ unsafe impl<T, const C: usize> Storage<ArrayLayout<T>> for InlineStorage<T, C> {
         C
     }
 }
+
+/// Creates an [`InlineStorage`] block with all slots uninitialized.
+///
+/// This is an implementation detail of [`heap_on_stack!`](crate::heap_on_stack),
+/// so that its expansion contains no `unsafe` code.
+#[doc(hidden)]
+#[inline(always)]
+pub fn uninit_inline_storage<T, const C: usize>() -> InlineStorage<T, C> {
+    // SAFETY: an array of `MaybeUninit` does not require initialization.
+    unsafe { MaybeUninit::uninit().assume_init() }
+}
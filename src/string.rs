@@ -333,7 +333,10 @@ impl<S: Storage<ArrayLayout<u8>>, I: Capacity> String<S, I> {
     #[inline]
     pub fn try_push(&mut self, ch: char) -> crate::Result<()> {
         match ch.len_utf8() {
-            1 => self.vec.try_push(ch as u8).map_err(|_| CapacityError),
+            1 => self
+                .vec
+                .try_push(ch as u8)
+                .or_else(|_| CapacityError::new()),
             _ => self
                 .vec
                 .try_extend_from_slice(ch.encode_utf8(&mut [0; 4]).as_bytes()),